@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use sdp_parser::Sdp;
+
+// the same corpus the unit tests parse, see fixtures/
+const FIXTURES: [(&str, &str); 2] = [
+    ("webrtc_offer", include_str!("../fixtures/webrtc_offer.sdp")),
+    ("sfu_offer", include_str!("../fixtures/sfu_offer.sdp")),
+];
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    for (name, fixture) in FIXTURES {
+        group.throughput(Throughput::Bytes(fixture.len() as u64));
+        group.bench_function(name, |b| b.iter(|| Sdp::parse(black_box(fixture))));
+    }
+
+    group.finish();
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+
+    for (name, fixture) in FIXTURES {
+        let sdp = Sdp::parse(fixture).unwrap();
+
+        group.throughput(Throughput::Bytes(fixture.len() as u64));
+        group.bench_function(name, |b| b.iter(|| black_box(&sdp).to_sdp_string()));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, serialize);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sdp_parser::Sdp;
+
+// parsing, serializing and validating arbitrary input must never panic
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(sdp) = Sdp::parse(text) {
+            let _ = sdp.to_sdp_string();
+            let _ = sdp.validate();
+        }
+    }
+});
@@ -0,0 +1,101 @@
+use crate::error::{Error, Result};
+use alloc::format;
+use core::fmt;
+use core::net::IpAddr;
+
+/// The address of an ICE candidate
+///
+/// a=candidate:1 1 UDP 2113667327 2001:db8::1 54400 typ host
+/// a=candidate:1 1 UDP 2113667327 4f1a5a3e-2b0c-4d8e-9e5c-1f7d3b2a6c9e.local 54400 typ host
+///
+/// Browsers hide the host addresses of their candidates behind random mDNS
+/// hostnames (draft-ietf-mmusic-mdns-ice-candidates), which only resolve on
+/// the local network, so they are passed through rather than parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Address<'a> {
+    Ip(IpAddr),
+    Mdns(&'a str),
+}
+
+impl<'a> Address<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        if value.ends_with(".local") {
+            return Ok(Address::Mdns(value));
+        }
+
+        parse_ip(value, None).map(Address::Ip)
+    }
+}
+
+impl<'a> fmt::Display for Address<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Address::Ip(ip) => write!(f, "{}", ip),
+            Address::Mdns(hostname) => write!(f, "{}", hostname),
+        }
+    }
+}
+
+// parse an IP literal, with or without the brackets of an IPv6 address,
+// checking it against the IP4 or IP6 address type of the line when given
+pub(crate) fn parse_ip(value: &str, ip_type: Option<&str>) -> Result<IpAddr> {
+    let literal = value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .unwrap_or(value);
+
+    let ip = literal
+        .parse::<IpAddr>()
+        .map_err(|_| Error::invalid_value("ip address", format!("'{}' is not an IP", value)))?;
+
+    let matches = match ip_type {
+        Some("IP4") => ip.is_ipv4(),
+        Some("IP6") => ip.is_ipv6(),
+        _ => true,
+    };
+
+    if !matches {
+        return Err(Error::invalid_value(
+            "ip address",
+            format!(
+                "'{}' doesn't match the address type {}",
+                value,
+                ip_type.unwrap_or_default()
+            ),
+        ));
+    }
+
+    Ok(ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn it_parses_ip_addresses() {
+        assert_eq!(
+            parse_ip("203.0.113.1", Some("IP4")),
+            Ok(IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1)))
+        );
+        assert_eq!(
+            parse_ip("[::1]", Some("IP6")),
+            Ok(IpAddr::V6(Ipv6Addr::LOCALHOST))
+        );
+        assert!(parse_ip("::1", Some("IP4")).is_err());
+        assert!(parse_ip("203.0.113.1", Some("IP6")).is_err());
+        assert!(parse_ip("example.com", None).is_err());
+    }
+
+    #[test]
+    fn it_passes_mdns_hostnames_through() {
+        let hostname = "4f1a5a3e-2b0c-4d8e-9e5c-1f7d3b2a6c9e.local";
+
+        assert_eq!(Address::new(hostname), Ok(Address::Mdns(hostname)));
+        assert_eq!(
+            Address::new("2001:db8::1").unwrap().to_string(),
+            "2001:db8::1"
+        );
+    }
+}
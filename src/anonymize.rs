@@ -0,0 +1,247 @@
+//! Redaction of the identifying values of a SDP, e.g. before logging it or
+//! attaching it to a bug report.
+//!
+//! Values are replaced in place with placeholders of the same shape, so the
+//! redacted SDP still parses and validates like the original.
+
+use crate::sdp::Sdp;
+
+/// Which values to redact, all of them by default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnonymizeOptions {
+    /// The addresses of the o, c and a=rtcp lines and of the candidates
+    pub addresses: bool,
+
+    /// ice-ufrag and ice-pwd
+    pub ice_credentials: bool,
+
+    /// The fingerprint hashes
+    pub fingerprints: bool,
+
+    /// The cname of each ssrc
+    pub cnames: bool,
+
+    /// The SDES keys of the crypto lines and the k lines
+    pub keys: bool,
+}
+
+impl Default for AnonymizeOptions {
+    fn default() -> Self {
+        Self {
+            addresses: true,
+            ice_credentials: true,
+            fingerprints: true,
+            cnames: true,
+            keys: true,
+        }
+    }
+}
+
+// documentation addresses (RFC 5737 and RFC 3849)
+const IP4_ADDRESS: &str = "192.0.2.1";
+const IP6_ADDRESS: &str = "2001:db8::1";
+
+// the shortest credentials RFC 8839 allows
+const ICE_UFRAG: &str = "xxxx";
+const ICE_PWD: &str = "xxxxxxxxxxxxxxxxxxxxxx";
+
+const CNAME: &str = "anonymous";
+const KEY: &str = "redacted";
+const HASH: &str = "00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00";
+
+pub(crate) fn anonymize(sdp: &mut Sdp, options: AnonymizeOptions) {
+    if options.addresses {
+        sdp.origin.ip_address = address(sdp.origin.ip_type);
+
+        if let Some(connection) = &mut sdp.connection {
+            connection.ip_address = address(connection.ip_type);
+        }
+    }
+
+    if options.ice_credentials {
+        sdp.ice_ufrag = sdp.ice_ufrag.map(|_| ICE_UFRAG);
+        sdp.ice_pwd = sdp.ice_pwd.map(|_| ICE_PWD);
+    }
+
+    if options.fingerprints {
+        sdp.fingerprints
+            .iter_mut()
+            .for_each(|fingerprint| fingerprint.hash = hash(fingerprint.hash));
+    }
+
+    if options.keys {
+        sdp.encryption_key = sdp.encryption_key.map(|_| KEY);
+    }
+
+    // the raw session attribute lines would still hold the original values
+    sdp.attributes.retain(|attribute| match attribute.key {
+        "ice-ufrag" | "ice-pwd" => !options.ice_credentials,
+        "fingerprint" => !options.fingerprints,
+        _ => true,
+    });
+
+    // nor may the lines of a lossless parse be written back
+    let redacting = options.addresses
+        || options.ice_credentials
+        || options.fingerprints
+        || options.cnames
+        || options.keys;
+
+    if redacting {
+        sdp.raw.clear();
+        sdp.media.iter_mut().for_each(|media| media.raw.clear());
+    }
+
+    for media in &mut sdp.media {
+        if options.addresses {
+            if let Some(connection) = &mut media.connection {
+                connection.ip_address = address(connection.ip_type);
+            }
+
+            if let Some(rtcp) = &mut media.rtcp {
+                rtcp.address = rtcp.address.map(|_| address(rtcp.ip_type.unwrap_or("IP4")));
+            }
+
+            for candidate in &mut media.candidates {
+                let ip_type = if candidate.ip.contains(':') {
+                    "IP6"
+                } else {
+                    "IP4"
+                };
+                candidate.ip = address(ip_type);
+
+                // the base address of a srflx, prflx or relay candidate
+                for (name, value) in &mut candidate.extensions {
+                    if *name == "raddr" {
+                        let ip_type = if value.contains(':') { "IP6" } else { "IP4" };
+                        *value = address(ip_type);
+                    }
+                }
+            }
+
+            for remote_candidate in &mut media.remote_candidates {
+                let ip_type = if remote_candidate.address.contains(':') {
+                    "IP6"
+                } else {
+                    "IP4"
+                };
+                remote_candidate.address = address(ip_type);
+            }
+        }
+
+        if options.ice_credentials {
+            media.ice_ufrag = media.ice_ufrag.map(|_| ICE_UFRAG);
+            media.ice_pwd = media.ice_pwd.map(|_| ICE_PWD);
+        }
+
+        if options.fingerprints {
+            media
+                .fingerprints
+                .iter_mut()
+                .for_each(|fingerprint| fingerprint.hash = hash(fingerprint.hash));
+        }
+
+        if options.cnames {
+            media
+                .ssrc
+                .iter_mut()
+                .filter(|ssrc| ssrc.attribute == "cname")
+                .for_each(|ssrc| ssrc.value = Some(CNAME));
+        }
+
+        if options.keys {
+            media.encryption_key = media.encryption_key.map(|_| KEY);
+            media
+                .crypto
+                .iter_mut()
+                .flat_map(|crypto| crypto.key_params.iter_mut())
+                .for_each(|key_param| key_param.key = KEY);
+        }
+    }
+}
+
+fn address(ip_type: &str) -> &'static str {
+    match ip_type {
+        "IP6" => IP6_ADDRESS,
+        _ => IP4_ADDRESS,
+    }
+}
+
+// a zeroed hash with as many bytes as the original
+fn hash(hash: &str) -> &'static str {
+    let bytes = hash.split(':').count().min(64);
+
+    &HASH[..bytes * 3 - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseOptions;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+c=IN IP4 203.0.113.1
+a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+a=fingerprint:sha-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7
+m=audio 54400 RTP/SAVPF 0
+a=candidate:0 1 UDP 2113667327 2001:db8:85a3::8a2e:370:7334 54400 typ host
+a=candidate:1 1 UDP 1694498815 198.51.100.7 61000 typ srflx raddr 203.0.113.1 rport 54402
+a=ssrc:1399694169 cname:alice@example.com
+a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:32";
+
+    #[test]
+    fn it_anonymizes_a_sdp() {
+        let mut sdp = Sdp::parse(SDP).unwrap();
+        sdp.anonymize(AnonymizeOptions::default());
+        let media = &sdp.media[0];
+
+        assert_eq!(sdp.origin.ip_address, IP4_ADDRESS);
+        assert_eq!(sdp.connection.as_ref().unwrap().ip_address, IP4_ADDRESS);
+        assert_eq!(sdp.ice_ufrag, Some(ICE_UFRAG));
+        assert!(sdp.attributes.is_empty());
+        assert_eq!(sdp.fingerprints[0].digest_bytes().unwrap(), vec![0; 20]);
+        assert_eq!(media.candidates[0].ip, IP6_ADDRESS);
+        assert_eq!(media.candidates[0].port, 54400);
+        assert_eq!(media.candidates[1].extension("raddr"), Some(IP4_ADDRESS));
+        assert_eq!(media.candidates[1].extension("rport"), Some("54402"));
+        assert!(!sdp.to_string().contains("203.0.113.1"));
+        assert_eq!(media.ssrc[0].value, Some(CNAME));
+        assert_eq!(media.crypto[0].key_params[0].key, KEY);
+        assert!(sdp.validate().is_empty());
+        assert!(Sdp::parse(&sdp.to_sdp_string()).is_ok());
+    }
+
+    #[test]
+    fn it_only_anonymizes_the_selected_values() {
+        let mut sdp = Sdp::parse(SDP).unwrap();
+        let options = AnonymizeOptions {
+            addresses: false,
+            ..Default::default()
+        };
+        sdp.anonymize(options);
+
+        assert_eq!(sdp.origin.ip_address, "203.0.113.1");
+        assert_eq!(sdp.ice_pwd, Some(ICE_PWD));
+    }
+
+    #[test]
+    fn it_anonymizes_a_lossless_parse() {
+        let sdp = format!("{}\na=rtcp:9 IN IP4 203.0.113.9", SDP);
+        let mut options = ParseOptions::default();
+        options.lossless = true;
+        let mut parsed = Sdp::parse_with(&sdp, &options).unwrap();
+        parsed.anonymize(AnonymizeOptions::default());
+        let written = parsed.to_sdp_string_lossless();
+
+        assert_eq!(written, parsed.to_sdp_string());
+        assert!(!written.contains("203.0.113.1"));
+        assert!(!written.contains("203.0.113.9"));
+        assert!(!written.contains("198.51.100.7"));
+        assert!(!written.contains("alice@example.com"));
+        assert!(parsed.raw.is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::Result;
+use crate::utils::{parse_number, parse_str};
+use core::fmt;
+
+/// SDP Application
+///
+/// m=application 9 UDP/DTLS/SCTP webrtc-datachannel
+/// a=sctp-port:5000
+/// a=max-message-size:262144
+///
+/// An application media section carries WebRTC data channels over SCTP, which
+/// runs on top of DTLS. The sctp-port is the SCTP port (not the UDP port in the
+/// m line) and max-message-size is the largest message the endpoint is willing to
+/// receive. Older endpoints use the legacy DTLS/SCTP protocol and describe the
+/// association with an a=sctpmap line instead.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Application<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sctp_port: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u64>,
+
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub sctpmap: Option<Sctpmap<'a>>,
+}
+
+/// SCTP Map
+///
+/// a=sctpmap:5000 webrtc-datachannel 1024
+///
+/// The legacy form of describing a data channel association: the SCTP port,
+/// the application using it and the maximum number of streams.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Sctpmap<'a> {
+    pub port: u16,
+    pub app: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streams: Option<u32>,
+}
+
+impl<'a> Sctpmap<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ');
+        let port = parse_number::<u16>(split.next(), "sctp port")?;
+        let app = parse_str(split.next(), "app")?;
+        let streams = match split.next() {
+            Some(streams) => Some(parse_number::<u32>(Some(streams), "streams")?),
+            None => None,
+        };
+
+        Ok(Self { port, app, streams })
+    }
+}
+
+impl<'a> fmt::Display for Sctpmap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.port, self.app)?;
+
+        if let Some(streams) = self.streams {
+            write!(f, " {}", streams)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_sctpmap() {
+        let sctpmap = "5000 webrtc-datachannel 1024";
+        let parsed = Sctpmap::new(sctpmap).unwrap();
+        let expected = Sctpmap {
+            port: 5000,
+            app: "webrtc-datachannel",
+            streams: Some(1024),
+        };
+
+        assert_eq!(parsed, expected);
+    }
+}
@@ -0,0 +1,339 @@
+//! proptest strategies for the model types, behind the `test-utils` feature.
+//!
+//! Generated values only use what the serializer writes back unchanged, so
+//! any of them can be checked to round trip:
+//!
+//! proptest! {
+//!     fn it_round_trips(sdp in any::<Sdp<'static>>()) {
+//!         assert_eq!(Sdp::parse(&sdp.to_sdp_string())?, sdp);
+//!     }
+//! }
+//!
+//! Every value borrows from the static tables below, as the model borrows
+//! from the message it was parsed from.
+
+use crate::attribute::Attribute;
+use crate::connection::Connection;
+use crate::direction::Direction;
+use crate::fingerprint::Fingerprint;
+use crate::group::Group;
+use crate::media::{Candidate, Fmtp, Format, Media, Rtpmap, Ssrc};
+use crate::origin::Origin;
+use crate::sdp::Sdp;
+use crate::time::{Time, TimeDescription};
+use crate::validate::SourceLine;
+use alloc::{vec, vec::Vec};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+// (payload, codec, rate, channels, fmtp)
+type Codec = (u8, &'static str, u32, Option<u8>, Option<&'static str>);
+
+const AUDIO_CODECS: [Codec; 4] = [
+    (
+        111,
+        "opus",
+        48000,
+        Some(2),
+        Some("minptime=10;useinbandfec=1"),
+    ),
+    (9, "G722", 8000, None, None),
+    (0, "PCMU", 8000, None, None),
+    (8, "PCMA", 8000, None, None),
+];
+
+const VIDEO_CODECS: [Codec; 4] = [
+    (96, "VP8", 90000, None, None),
+    (98, "VP9", 90000, None, Some("profile-id=0")),
+    (
+        102,
+        "H264",
+        90000,
+        None,
+        Some("packetization-mode=1;profile-level-id=42e01f"),
+    ),
+    (45, "AV1", 90000, None, None),
+];
+
+const MIDS: [&str; 4] = ["0", "1", "2", "3"];
+
+// the BUNDLE group of the first n mids, as the a=group line reads
+const BUNDLES: [&str; 4] = ["BUNDLE 0", "BUNDLE 0 1", "BUNDLE 0 1 2", "BUNDLE 0 1 2 3"];
+
+// (type, hash, the a=fingerprint value)
+const FINGERPRINTS: [(&str, &str, &str); 2] = [
+    (
+        "sha-256",
+        "49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E",
+        "sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E",
+    ),
+    (
+        "sha-1",
+        "42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7",
+        "sha-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7",
+    ),
+];
+
+const ADDRESSES: [(&str, &str); 3] = [
+    ("IP4", "203.0.113.1"),
+    ("IP4", "198.51.100.7"),
+    ("IP6", "2001:db8::1"),
+];
+
+const ICE_UFRAGS: [&str; 2] = ["F7gI", "8hhY"];
+const ICE_PWDS: [&str; 2] = ["x9cml/YzichV2+XlhiMu8g", "asd88fgpdd777uzjYhagZg"];
+const SETUPS: [&str; 3] = ["actpass", "active", "passive"];
+const CNAMES: [&str; 2] = ["4TOk42mSjXCkVIa6", "user@example.com"];
+
+pub fn direction() -> impl Strategy<Value = Direction> {
+    select(vec![
+        Direction::SendRecv,
+        Direction::SendOnly,
+        Direction::RecvOnly,
+        Direction::Inactive,
+    ])
+}
+
+pub fn origin() -> impl Strategy<Value = Origin<'static>> {
+    (
+        select(vec!["-", "alice"]),
+        any::<u64>(),
+        any::<u64>(),
+        select(ADDRESSES.to_vec()),
+    )
+        .prop_map(
+            |(username, session_id, session_version, (ip_type, ip_address))| Origin {
+                username,
+                session_id,
+                session_version,
+                network_type: "IN",
+                ip_type,
+                ip_address,
+            },
+        )
+}
+
+pub fn connection() -> impl Strategy<Value = Connection<'static>> {
+    select(ADDRESSES.to_vec()).prop_map(|(ip_type, ip_address)| Connection {
+        network_type: "IN",
+        ip_type,
+        ip_address,
+        ..Default::default()
+    })
+}
+
+pub fn candidate() -> impl Strategy<Value = Candidate<'static>> {
+    (
+        select(vec!["1", "2", "842163049"]),
+        1u16..=2,
+        select(vec!["udp", "tcp"]),
+        any::<u32>(),
+        select(ADDRESSES.to_vec()),
+        any::<u16>(),
+        select(vec!["host", "srflx", "relay"]),
+    )
+        .prop_map(
+            |(foundation, component, transport, priority, (_, ip), port, r#type)| Candidate {
+                foundation,
+                component,
+                transport,
+                priority,
+                ip,
+                port,
+                r#type,
+                extensions: vec![],
+            },
+        )
+}
+
+// an audio, video or data channel section, without a mid
+pub fn media() -> impl Strategy<Value = Media<'static>> {
+    let rtp = (
+        prop_oneof![
+            subsequence(AUDIO_CODECS.to_vec(), 1..=4).prop_map(|codecs| ("audio", codecs)),
+            subsequence(VIDEO_CODECS.to_vec(), 1..=4).prop_map(|codecs| ("video", codecs)),
+        ],
+        select(vec!["UDP/TLS/RTP/SAVPF", "RTP/AVP"]),
+        option::of(direction()),
+        any::<bool>(),
+        vec((any::<u32>(), select(CNAMES.to_vec())), 0..3),
+    )
+        .prop_map(
+            |((r#type, codecs), protocol, direction, rtcp_mux, ssrcs)| Media {
+                r#type,
+                protocol,
+                payloads: codecs.iter().map(|codec| codec.0).collect(),
+                rtpmap: codecs
+                    .iter()
+                    .map(|&(payload, codec, rate, channels, _)| Rtpmap {
+                        codec,
+                        payload,
+                        rate: Some(rate),
+                        channels,
+                        ..Default::default()
+                    })
+                    .collect(),
+                fmtp: codecs
+                    .iter()
+                    .filter_map(|&(payload, _, _, _, fmtp)| {
+                        Some(Fmtp {
+                            config: fmtp?.into(),
+                            payload: Format::Payload(payload),
+                            line: SourceLine::default(),
+                        })
+                    })
+                    .collect(),
+                direction,
+                rtcp_mux,
+                ssrc: ssrcs
+                    .into_iter()
+                    .map(|(id, cname)| Ssrc {
+                        id,
+                        attribute: "cname",
+                        value: Some(cname),
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+        );
+
+    let application = Just(Media {
+        r#type: "application",
+        protocol: "UDP/DTLS/SCTP",
+        formats: vec!["webrtc-datachannel"],
+        ..Default::default()
+    });
+
+    (
+        prop_oneof![3 => rtp, 1 => application],
+        any::<u16>(),
+        option::of(connection()),
+        vec(candidate(), 0..3),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(media, port, connection, candidates, end_of_candidates)| Media {
+                port,
+                connection,
+                candidates,
+                end_of_candidates,
+                ..media
+            },
+        )
+}
+
+pub fn sdp() -> impl Strategy<Value = Sdp<'static>> {
+    (
+        origin(),
+        select(vec!["-", "", "Conference"]),
+        (any::<u64>(), any::<u64>()),
+        option::of(connection()),
+        option::of((select(ICE_UFRAGS.to_vec()), select(ICE_PWDS.to_vec()))),
+        subsequence(FINGERPRINTS.to_vec(), 0..=2),
+        option::of(select(SETUPS.to_vec())),
+        any::<bool>(),
+        vec(media(), 0..=MIDS.len()),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(
+                origin,
+                session_name,
+                time,
+                connection,
+                ice,
+                fingerprints,
+                setup,
+                ice_lite,
+                media,
+                bundle,
+            )| {
+                let mut sdp = Sdp {
+                    origin,
+                    session_name,
+                    times: vec![TimeDescription::new(Time {
+                        start_time: time.0,
+                        stop_time: time.1,
+                    })],
+                    connection,
+                    ice_ufrag: ice.map(|(ufrag, _)| ufrag),
+                    ice_pwd: ice.map(|(_, pwd)| pwd),
+                    fingerprints: fingerprints
+                        .iter()
+                        .map(|&(r#type, hash, _)| Fingerprint { r#type, hash })
+                        .collect(),
+                    setup,
+                    ice_lite,
+                    media,
+                    ..Default::default()
+                };
+
+                for (media, mid) in sdp.media.iter_mut().zip(MIDS.iter()) {
+                    media.mid = Some(mid);
+                }
+
+                if bundle && !sdp.media.is_empty() {
+                    sdp.groups.push(Group {
+                        semantics: "BUNDLE",
+                        mids: MIDS[..sdp.media.len()].to_vec(),
+                    });
+                }
+
+                sdp.attributes = session_attributes(&sdp, &fingerprints);
+                sdp
+            },
+        )
+}
+
+// the session attribute lines the serializer writes for the typed fields,
+// as parsing them would record them
+fn session_attributes(
+    sdp: &Sdp<'static>,
+    fingerprints: &[(&'static str, &'static str, &'static str)],
+) -> Vec<Attribute<'static>> {
+    let mut attributes = vec![];
+    let mut push = |key, value| attributes.push(Attribute { key, value });
+
+    if sdp.ice_lite {
+        push("ice-lite", None);
+    }
+
+    if let (Some(ufrag), Some(pwd)) = (sdp.ice_ufrag, sdp.ice_pwd) {
+        push("ice-ufrag", Some(ufrag));
+        push("ice-pwd", Some(pwd));
+    }
+
+    for &(_, _, fingerprint) in fingerprints {
+        push("fingerprint", Some(fingerprint));
+    }
+
+    if let Some(setup) = sdp.setup {
+        push("setup", Some(setup));
+    }
+
+    if !sdp.groups.is_empty() {
+        push("group", Some(BUNDLES[sdp.media.len() - 1]));
+    }
+
+    attributes
+}
+
+impl Arbitrary for Sdp<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        sdp().boxed()
+    }
+}
+
+impl Arbitrary for Media<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        media().boxed()
+    }
+}
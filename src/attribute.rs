@@ -0,0 +1,52 @@
+use core::fmt;
+
+/// SDP Attribute
+///
+/// a=ice-ufrag:F7gI
+/// a=ice-lite
+///
+/// An attribute line as it appeared in the message, split into its key and,
+/// for property attributes, its value.  The session-level lines are kept in
+/// their original order alongside the typed fields they were parsed into,
+/// and attributes parsed by a registered `AttributeParser` keep theirs to be
+/// written back unchanged.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Attribute<'a> {
+    pub key: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<&'a str>,
+}
+
+impl<'a> fmt::Display for Attribute<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.key)?;
+
+        if let Some(value) = self.value {
+            write!(f, ":{}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn it_writes_an_attribute() {
+        let property = Attribute {
+            key: "ice-ufrag",
+            value: Some("F7gI"),
+        };
+        let flag = Attribute {
+            key: "ice-lite",
+            value: None,
+        };
+
+        assert_eq!(property.to_string(), "ice-ufrag:F7gI");
+        assert_eq!(flag.to_string(), "ice-lite");
+    }
+}
@@ -0,0 +1,184 @@
+use crate::error::{Error, Result};
+use crate::utils::parse_number;
+use core::fmt;
+
+/// SDP Bandwidth
+///
+/// b=AS:512
+/// b=TIAS:64000
+///
+/// The bandwidth the session or the media section is expected to use, by
+/// modifier: CT is the total of a conference and AS of the application, both
+/// in kilobits per second and including the headers of the packets (RFC 8866).
+/// TIAS is the bandwidth of the media alone, in bits per second, to be
+/// combined with the packet rate of a=maxprate for the transport overhead
+/// (RFC 3890).  RS and RR are the bits per second of RTCP for senders and
+/// receivers (RFC 3556).
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Bandwidth<'a> {
+    pub r#type: &'a str,
+    pub bandwidth: u64,
+}
+
+impl<'a> Bandwidth<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let (r#type, bandwidth) = value
+            .split_once(':')
+            .ok_or(Error::MissingField { field: "bandwidth" })?;
+
+        if r#type.is_empty() {
+            return Err(Error::MissingField {
+                field: "bandwidth type",
+            });
+        }
+
+        Ok(Self {
+            r#type,
+            bandwidth: parse_number::<u64>(Some(bandwidth), "bandwidth")?,
+        })
+    }
+
+    /// The typed modifier of the line
+    pub fn bandwidth_type(&self) -> BandwidthType<'a> {
+        BandwidthType::from(self.r#type)
+    }
+}
+
+impl<'a> fmt::Display for Bandwidth<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.r#type, self.bandwidth)
+    }
+}
+
+/// The registered bandwidth modifiers of b lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BandwidthType<'a> {
+    Ct,
+    As,
+    Tias,
+    Rs,
+    Rr,
+    Other(&'a str),
+}
+
+impl<'a> BandwidthType<'a> {
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            BandwidthType::Ct => "CT",
+            BandwidthType::As => "AS",
+            BandwidthType::Tias => "TIAS",
+            BandwidthType::Rs => "RS",
+            BandwidthType::Rr => "RR",
+            BandwidthType::Other(other) => other,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for BandwidthType<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "CT" => BandwidthType::Ct,
+            "AS" => BandwidthType::As,
+            "TIAS" => BandwidthType::Tias,
+            "RS" => BandwidthType::Rs,
+            "RR" => BandwidthType::Rr,
+            other => BandwidthType::Other(other),
+        }
+    }
+}
+
+impl<'a> fmt::Display for BandwidthType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// the bandwidth of a modifier, e.g. TIAS, among the b lines of a section
+pub(crate) fn find(bandwidth: &[Bandwidth], r#type: BandwidthType) -> Option<u64> {
+    bandwidth
+        .iter()
+        .find(|b| b.bandwidth_type() == r#type)
+        .map(|b| b.bandwidth)
+}
+
+// the TIAS bandwidth with `overhead` bytes of headers per packet at the packet
+// rate of a=maxprate, in bits per second (RFC 3890 section 6.4)
+pub(crate) fn transport(
+    bandwidth: &[Bandwidth],
+    maxprate: Option<f32>,
+    overhead: u32,
+) -> Option<u64> {
+    let tias = find(bandwidth, BandwidthType::Tias)?;
+    let headers = f64::from(maxprate?) * f64::from(overhead) * 8.0;
+
+    Some(tias + headers as u64)
+}
+
+// packets per second, fractional for e.g. one packet every 2 seconds
+pub(crate) fn parse_maxprate(value: &str) -> Result<f32> {
+    let maxprate = parse_number::<f32>(Some(value), "maxprate")?;
+
+    match maxprate.is_finite() && maxprate > 0.0 {
+        true => Ok(maxprate),
+        false => Err(Error::InvalidNumber {
+            field: "maxprate",
+            value: value.into(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdp::Sdp;
+
+    #[test]
+    fn it_parses_a_bandwidth() {
+        let parsed = Bandwidth::new("TIAS:64000").unwrap();
+        let expected = Bandwidth {
+            r#type: "TIAS",
+            bandwidth: 64000,
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.bandwidth_type(), BandwidthType::Tias);
+        assert_eq!(parsed.to_string(), "TIAS:64000");
+        assert_eq!(
+            Bandwidth::new("X-YZ:1").unwrap().bandwidth_type(),
+            BandwidthType::Other("X-YZ")
+        );
+        assert!(Bandwidth::new("AS").is_err());
+        assert!(Bandwidth::new(":512").is_err());
+        assert!(Bandwidth::new("AS:-1").is_err());
+    }
+
+    #[test]
+    fn it_parses_streaming_bandwidth() {
+        let sdp = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=-\r
+c=IN IP4 203.0.113.1\r
+b=AS:544\r
+b=TIAS:512000\r
+t=0 0\r
+a=maxprate:100\r
+m=video 5004 RTP/AVP 96\r
+b=AS:480\r
+b=TIAS:450000\r
+a=rtpmap:96 H264/90000\r
+a=maxprate:62.5\r
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let media = &parsed.media[0];
+
+        assert_eq!(parsed.bandwidth_for(BandwidthType::As), Some(544));
+        assert_eq!(parsed.maxprate, Some(100.0));
+        assert_eq!(media.bandwidth_for(BandwidthType::Tias), Some(450000));
+        assert_eq!(media.bandwidth_for(BandwidthType::Ct), None);
+        assert_eq!(media.maxprate, Some(62.5));
+        assert_eq!(media.transport_bandwidth(40), Some(470000));
+        assert_eq!(parsed.transport_bandwidth(40), Some(544000));
+        assert_eq!(parsed.to_string(), sdp);
+        assert!(Sdp::parse(&sdp.replace("62.5", "0")).is_err());
+    }
+}
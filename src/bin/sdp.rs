@@ -0,0 +1,175 @@
+//! Command line tool for inspecting SDP messages, e.g. ones captured from SIP or
+//! WebRTC signaling traffic.
+//!
+//! sdp-parse json [FILE]
+//! sdp-parse yaml [FILE], with the yaml feature
+//! sdp-parse validate [FILE]
+//! sdp-parse summarize [FILE]
+//! sdp-parse diff OLD NEW
+//!
+//! The SDP is read from stdin when FILE is omitted or is "-".
+
+use sdp_parser::{Diagnostic, Sdp, Severity};
+use std::io::{self, Read};
+use std::process;
+
+const USAGE: &str = "usage:
+    sdp-parse json [FILE]         print the SDP as JSON
+    sdp-parse yaml [FILE]         print the SDP as YAML, with the yaml feature
+    sdp-parse validate [FILE]     check the constraints between lines
+    sdp-parse summarize [FILE]    print an overview of the media sections
+    sdp-parse diff OLD NEW        print what changed between two SDPs
+
+FILE defaults to stdin, or can be \"-\"";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match run(&args) {
+        Ok(code) => process::exit(code),
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    }
+}
+
+// run a subcommand, returning the exit code
+fn run(args: &[&str]) -> Result<i32, String> {
+    match args {
+        ["json", rest @ ..] => {
+            let text = read(single(rest)?)?;
+            let json = parse(&text)?.to_json().map_err(|e| e.to_string())?;
+            println!("{}", json);
+            Ok(0)
+        }
+        #[cfg(feature = "yaml")]
+        ["yaml", rest @ ..] => {
+            let text = read(single(rest)?)?;
+            let yaml = parse(&text)?.to_yaml().map_err(|e| e.to_string())?;
+            print!("{}", yaml);
+            Ok(0)
+        }
+        ["validate", rest @ ..] => {
+            let text = read(single(rest)?)?;
+            let diagnostics = parse(&text)?.validate();
+
+            for diagnostic in &diagnostics {
+                println!("{}", describe(diagnostic));
+            }
+
+            let failed = diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.severity == Severity::Error);
+
+            Ok(if failed { 1 } else { 0 })
+        }
+        ["summarize", rest @ ..] => {
+            let text = read(single(rest)?)?;
+            summarize(&parse(&text)?);
+            Ok(0)
+        }
+        ["diff", old, new] => {
+            let (old, new) = (read(Some(old))?, read(Some(new))?);
+            let delta = parse(&old)?.diff(&parse(&new)?);
+            let json = serde_json::to_string_pretty(&delta).map_err(|e| e.to_string())?;
+            println!("{}", json);
+            Ok(if delta.is_empty() { 0 } else { 1 })
+        }
+        ["help"] | ["-h"] | ["--help"] => {
+            println!("{}", USAGE);
+            Ok(0)
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+// the optional file argument of a subcommand
+fn single<'a>(args: &[&'a str]) -> Result<Option<&'a str>, String> {
+    match args {
+        [] => Ok(None),
+        [path] => Ok(Some(path)),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn read(path: Option<&str>) -> Result<String, String> {
+    let mut text = String::new();
+
+    match path {
+        None | Some("-") => io::stdin()
+            .read_to_string(&mut text)
+            .map(|_| text)
+            .map_err(|e| format!("stdin: {}", e)),
+        Some(path) => std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e)),
+    }
+}
+
+fn parse(text: &str) -> Result<Sdp<'_>, String> {
+    Sdp::parse(text).map_err(|e| e.to_string())
+}
+
+fn describe(diagnostic: &Diagnostic) -> String {
+    let severity = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+
+    match diagnostic.line {
+        Some(line) => format!("{} (line {}): {}", severity, line, diagnostic.message),
+        None => format!("{}: {}", severity, diagnostic.message),
+    }
+}
+
+fn summarize(sdp: &Sdp) {
+    println!(
+        "origin {} {} {}, {} media section(s)",
+        sdp.origin.username,
+        sdp.origin.session_id,
+        sdp.origin.ip_address,
+        sdp.media.len()
+    );
+
+    for group in &sdp.groups {
+        println!("group {}", group);
+    }
+
+    for (index, media) in sdp.media.iter().enumerate() {
+        let codecs = media
+            .rtpmap
+            .iter()
+            .map(|rtpmap| rtpmap.to_string())
+            .collect::<Vec<_>>();
+
+        println!(
+            "#{} {} mid={} port={} {} {}",
+            index,
+            media.r#type,
+            media.mid.unwrap_or("-"),
+            media.port,
+            media.protocol,
+            media.effective_direction(sdp),
+        );
+
+        if !codecs.is_empty() {
+            println!("    codecs: {}", codecs.join(", "));
+        }
+
+        let mut ssrcs = media.ssrc.iter().map(|ssrc| ssrc.id).collect::<Vec<_>>();
+        ssrcs.sort_unstable();
+        ssrcs.dedup();
+
+        if !ssrcs.is_empty() {
+            println!("    ssrcs: {:?}", ssrcs);
+        }
+
+        if let Some(ufrag) = media.effective_ice_ufrag(sdp) {
+            println!(
+                "    ice: ufrag={} candidates={}",
+                ufrag,
+                media.candidates.len()
+            );
+        }
+    }
+}
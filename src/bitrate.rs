@@ -0,0 +1,154 @@
+use crate::media::{Fmtp, Format, Media};
+use crate::validate::SourceLine;
+
+/// Google Bitrate Hints
+///
+/// a=fmtp:96 x-google-min-bitrate=300;x-google-max-bitrate=2500;x-google-start-bitrate=1000
+///
+/// Chrome reads the bitrates its video encoder starts at and stays between
+/// from these fmtp parameters of the payload, in kilobits per second, which
+/// makes rewriting them the usual way for an application to cap or raise the
+/// bitrate of a call.  A missing or malformed parameter is None.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GoogleBitrateHints {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    pub start: Option<u32>,
+}
+
+const MIN: &str = "x-google-min-bitrate";
+const MAX: &str = "x-google-max-bitrate";
+const START: &str = "x-google-start-bitrate";
+
+impl GoogleBitrateHints {
+    pub(crate) fn new(fmtp: &Fmtp) -> Self {
+        let hint = |name| fmtp.parameter(name).and_then(|value| value.parse().ok());
+
+        Self {
+            min: hint(MIN),
+            max: hint(MAX),
+            start: hint(START),
+        }
+    }
+}
+
+// write the hints into the fmtp parameters of the payload, adding its fmtp
+// line if needed and dropping it if no parameter is left
+pub(crate) fn set_hints(media: &mut Media, payload: u8, hints: GoogleBitrateHints) {
+    let position = match media
+        .fmtp
+        .iter()
+        .position(|fmtp| fmtp.payload_type() == Some(payload))
+    {
+        Some(position) => position,
+        None => {
+            media.fmtp.push(Fmtp {
+                payload: Format::Payload(payload),
+                config: "".into(),
+                line: SourceLine::default(),
+            });
+            media.fmtp.len() - 1
+        }
+    };
+
+    let fmtp = &mut media.fmtp[position];
+    fmtp.set_parameter(MIN, hints.min);
+    fmtp.set_parameter(MAX, hints.max);
+    fmtp.set_parameter(START, hints.start);
+
+    if fmtp.config.trim().is_empty() {
+        media.fmtp.remove(position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseOptions;
+    use crate::sdp::Sdp;
+
+    #[test]
+    fn it_reads_and_writes_google_bitrate_hints() {
+        let mut media = Media::new("video 9 UDP/TLS/RTP/SAVPF 96 98").unwrap();
+        media
+            .parse_attribute(
+                "fmtp",
+                "96 x-google-min-bitrate=300;x-google-max-bitrate=abc",
+            )
+            .unwrap();
+
+        assert_eq!(
+            media.google_bitrate_hints(96),
+            GoogleBitrateHints {
+                min: Some(300),
+                ..Default::default()
+            }
+        );
+
+        let hints = GoogleBitrateHints {
+            max: Some(2500),
+            start: Some(1000),
+            ..Default::default()
+        };
+        media.set_google_bitrate_hints(96, hints);
+        media.set_google_bitrate_hints(98, hints);
+
+        assert_eq!(
+            media.fmtp[0].to_string(),
+            "96 x-google-max-bitrate=2500;x-google-start-bitrate=1000"
+        );
+        assert_eq!(media.google_bitrate_hints(98), hints);
+
+        media.set_google_bitrate_hints(98, GoogleBitrateHints::default());
+
+        assert_eq!(media.fmtp.len(), 1);
+        assert_eq!(
+            media.google_bitrate_hints(97),
+            GoogleBitrateHints::default()
+        );
+    }
+
+    #[test]
+    fn it_writes_hints_into_a_lossless_parse() {
+        let sdp = "v=0
+o=- 1 0 IN IP4 203.0.113.1
+s=
+t=0 0
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=rtpmap:96 VP8/90000
+a=fmtp:96 x-google-min-bitrate=300
+";
+        let mut options = ParseOptions::default();
+        options.lossless = true;
+        let mut parsed = Sdp::parse_with(sdp, &options).unwrap();
+        let hints = parsed.media[0].google_bitrate_hints(96);
+        parsed.media[0].set_google_bitrate_hints(96, hints);
+
+        assert_eq!(parsed.to_sdp_string_lossless(), sdp);
+
+        parsed.media[0].set_google_bitrate_hints(
+            96,
+            GoogleBitrateHints {
+                max: Some(2500),
+                ..hints
+            },
+        );
+
+        assert!(parsed
+            .to_sdp_string_lossless()
+            .contains("a=fmtp:96 x-google-min-bitrate=300;x-google-max-bitrate=2500\r\n"));
+    }
+
+    #[test]
+    fn it_sets_a_parameter() {
+        let mut fmtp = Fmtp::new("111 minptime=10; useinbandfec=1").unwrap();
+
+        assert_eq!(fmtp.parameter("useinbandfec"), Some("1"));
+        assert_eq!(fmtp.parameter("stereo"), None);
+
+        fmtp.set_parameter("minptime", Some(20));
+        fmtp.set_parameter("useinbandfec", None::<u8>);
+
+        assert_eq!(fmtp.config, "minptime=20");
+    }
+}
@@ -0,0 +1,145 @@
+//! Decoding of SDP messages received as bytes.
+//!
+//! The parser works on text, but the s= and i= lines may be in the character
+//! set named by a=charset (RFC 8866 section 6.10), e.g. ISO-8859-1 from older
+//! SIP equipment, rather than in UTF-8.
+//!
+//! A byte order mark is dropped, and NUL bytes are rejected, since they can
+//! only come from a corrupted body or a buffer read past its end.
+
+use crate::error::{Error, Result};
+use alloc::{borrow::Cow, string::String};
+use core::str;
+
+/// How the bytes of a message are turned into text
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Decoding {
+    /// Follow a=charset: ISO-8859-1 is transcoded to UTF-8, and any other
+    /// message must be valid UTF-8
+    #[default]
+    Charset,
+
+    /// Pass the bytes through, each as the character of the same value, so
+    /// names in an unknown character set survive unchanged
+    Raw,
+}
+
+// the names of ISO-8859-1 in a=charset, compared case-insensitively
+const LATIN_1: [&str; 3] = ["ISO-8859-1", "ISO_8859-1", "latin1"];
+
+// the UTF-8 byte order mark some editors and SIP stacks write
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+pub(crate) fn decode(message: &[u8], decoding: Decoding) -> Result<Cow<'_, str>> {
+    let message = message.strip_prefix(BOM).unwrap_or(message);
+
+    if let Some(position) = message.iter().position(|&byte| byte == 0) {
+        return Err(line_error(message, position, "embedded NUL byte"));
+    }
+
+    if decoding == Decoding::Raw || is_latin_1(message) {
+        return Ok(match str::from_utf8(message) {
+            Ok(text) if text.is_ascii() => Cow::Borrowed(text),
+            _ => Cow::Owned(message.iter().map(|&byte| char::from(byte)).collect()),
+        });
+    }
+
+    str::from_utf8(message).map(Cow::Borrowed).map_err(|error| {
+        line_error(
+            message,
+            error.valid_up_to(),
+            "invalid UTF-8, without an a=charset naming the character set",
+        )
+    })
+}
+
+// the text of a UTF-8 message, which the parsed SDP can borrow from
+pub(crate) fn utf8(message: &[u8]) -> Result<&str> {
+    match decode(message, Decoding::Charset)? {
+        Cow::Borrowed(text) => Ok(text),
+        Cow::Owned(_) => Err(Error::invalid_line(
+            "the message isn't UTF-8, decode it with Sdp::decode",
+        )),
+    }
+}
+
+// an error on the line of the byte at `position`
+fn line_error(message: &[u8], position: usize, reason: &str) -> Error {
+    let before = &message[..position];
+    let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+    let start = before
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |n| n + 1);
+    let raw = message[start..].split(|&byte| byte == b'\n').next();
+
+    Error::invalid_line(reason).at_line(line, &String::from_utf8_lossy(raw.unwrap_or_default()))
+}
+
+// a session-level a=charset naming ISO-8859-1
+fn is_latin_1(message: &[u8]) -> bool {
+    message
+        .split(|&byte| byte == b'\n')
+        .take_while(|line| !line.starts_with(b"m="))
+        .filter_map(|line| line.strip_prefix(b"a=charset:"))
+        .any(|charset| {
+            let charset = charset.trim_ascii();
+            LATIN_1
+                .iter()
+                .any(|name| charset.eq_ignore_ascii_case(name.as_bytes()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdp::Sdp;
+
+    #[test]
+    fn it_decodes_utf8() {
+        let message = "v=0\r\ns=Réunion\r\n".as_bytes();
+
+        assert_eq!(
+            decode(message, Decoding::Charset).unwrap(),
+            Cow::Borrowed("v=0\r\ns=Réunion\r\n")
+        );
+
+        let error = decode(b"v=0\r\ns=R\xe9union\r\n", Decoding::Charset).unwrap_err();
+
+        assert_eq!(error.line(), Some(2));
+    }
+
+    #[test]
+    fn it_parses_bytes() {
+        let message =
+            b"\xef\xbb\xbfv=0\r\no=- 1 0 IN IP4 203.0.113.1\r\ns=R\xc3\xa9union\r\nt=0 0\r\n";
+        let parsed = Sdp::parse_bytes(message).unwrap();
+
+        assert_eq!(parsed.session_name, "Réunion");
+
+        let nul = b"v=0\r\no=- 1 0 IN IP4 203.0.113.1\r\ns=\0\r\nt=0 0\r\n";
+        let error = Sdp::parse_bytes(nul).unwrap_err();
+
+        assert_eq!(error.line(), Some(3));
+        assert_eq!(error.kind(), &Error::invalid_line("embedded NUL byte"));
+        assert!(Sdp::parse_bytes(b"v=0\r\ns=R\xe9union\r\na=charset:ISO-8859-1\r\n").is_err());
+    }
+
+    #[test]
+    fn it_transcodes_latin_1() {
+        let message = b"v=0\r\ns=R\xe9union\r\na=charset:ISO-8859-1\r\n";
+
+        assert_eq!(
+            decode(message, Decoding::Charset).unwrap(),
+            "v=0\r\ns=Réunion\r\na=charset:ISO-8859-1\r\n"
+        );
+        assert_eq!(
+            decode(b"v=0\r\ns=R\xe9union\r\n", Decoding::Raw).unwrap(),
+            "v=0\r\ns=Réunion\r\n"
+        );
+        assert!(matches!(
+            decode(b"v=0\r\n", Decoding::Raw).unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::error::Result;
+use crate::utils::parse_number;
+use core::fmt;
+
+/// SDP Clip Rectangle
+///
+/// a=cliprect:0,0,480,640
+///
+/// The region of the video frame, as top, left, bottom and right pixel
+/// coordinates, an endpoint is expected to display.  3GPP and room system
+/// endpoints use it to crop the picture they send.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Cliprect {
+    pub top: u32,
+    pub left: u32,
+    pub bottom: u32,
+    pub right: u32,
+}
+
+impl Cliprect {
+    pub fn new(value: &str) -> Result<Self> {
+        let mut split = value.split(',');
+
+        Ok(Self {
+            top: parse_number::<u32>(split.next(), "cliprect top")?,
+            left: parse_number::<u32>(split.next(), "cliprect left")?,
+            bottom: parse_number::<u32>(split.next(), "cliprect bottom")?,
+            right: parse_number::<u32>(split.next(), "cliprect right")?,
+        })
+    }
+}
+
+impl fmt::Display for Cliprect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.top, self.left, self.bottom, self.right
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_cliprect() {
+        let cliprect = Cliprect::new("0,0,480,640").unwrap();
+
+        assert_eq!(cliprect.bottom, 480);
+        assert_eq!(cliprect.right, 640);
+        assert_eq!(cliprect.to_string(), "0,0,480,640");
+        assert!(Cliprect::new("0,0,480").is_err());
+    }
+}
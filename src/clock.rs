@@ -0,0 +1,312 @@
+use crate::error::{Error, Result};
+use crate::utils::parse_number;
+use alloc::format;
+use core::fmt;
+
+/// SDP Reference Clock
+///
+/// a=ts-refclk:ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:37
+/// a=ts-refclk:ntp=203.0.113.10
+/// a=ts-refclk:local
+///
+/// The clock the RTP timestamps of the stream are derived from (RFC 7273).
+/// AES67 and SMPTE ST 2110 streams reference a PTP grandmaster, given by its
+/// clock identity and domain, so that receivers can align streams of several
+/// senders.  Several reference clocks may be listed, the session-level ones
+/// applying to every media section without its own.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TsRefclk<'a> {
+    /// ntp, ptp, gps, gal, glonass, local or private
+    pub source: &'a str,
+
+    /// What follows the =, e.g. IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:37
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<&'a str>,
+}
+
+impl<'a> TsRefclk<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let (source, value) = match value.split_once('=') {
+            Some((source, value)) => (source, Some(value)),
+            None => (value, None),
+        };
+
+        if source.is_empty() {
+            return Err(Error::MissingField {
+                field: "clock source",
+            });
+        }
+
+        if source == "ptp" {
+            PtpClock::new(value.unwrap_or_default())?;
+        }
+
+        Ok(Self { source, value })
+    }
+
+    /// The PTP clock of a ptp source
+    pub fn ptp(&self) -> Option<PtpClock<'a>> {
+        match self.source {
+            "ptp" => PtpClock::new(self.value?).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> fmt::Display for TsRefclk<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{}={}", self.source, value),
+            None => write!(f, "{}", self.source),
+        }
+    }
+}
+
+/// A PTP reference clock, e.g. IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:37
+///
+/// The grandmaster is None for a=ts-refclk:ptp=IEEE1588-2008:traceable, any
+/// clock traceable to the same time source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PtpClock<'a> {
+    /// IEEE1588-2002, IEEE1588-2008, IEEE1588-2019 or IEEE802.1AS-2011
+    pub version: &'a str,
+    pub grandmaster: Option<&'a str>,
+    pub domain: Option<u8>,
+}
+
+impl<'a> PtpClock<'a> {
+    fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(':');
+        let version = split.next().filter(|version| !version.is_empty());
+        let version = version.ok_or(Error::MissingField {
+            field: "ptp version",
+        })?;
+        let grandmaster = match split.next() {
+            Some("traceable") => None,
+            Some(grandmaster) if is_clock_identity(grandmaster) => Some(grandmaster),
+            grandmaster => {
+                return Err(Error::invalid_value(
+                    "ptp grandmaster",
+                    format!(
+                        "'{}' is not a clock identity",
+                        grandmaster.unwrap_or_default()
+                    ),
+                ))
+            }
+        };
+        let domain = match split.next() {
+            Some(domain) => Some(parse_number::<u8>(Some(domain), "ptp domain")?),
+            None => None,
+        };
+
+        Ok(Self {
+            version,
+            grandmaster,
+            domain,
+        })
+    }
+}
+
+// eight hexadecimal bytes separated by dashes, e.g. 39-A7-94-FF-FE-07-CB-D0
+fn is_clock_identity(value: &str) -> bool {
+    value.split('-').count() == 8
+        && value
+            .split('-')
+            .all(|byte| byte.len() == 2 && byte.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// SDP Media Clock
+///
+/// a=mediaclk:direct=963214424
+/// a=mediaclk:direct=0 rate=48000/1
+/// a=mediaclk:sender
+///
+/// How the RTP clock relates to the reference clock (RFC 7273): direct
+/// streams start their timestamps at the offset from the reference clock's
+/// epoch, which ST 2110 receivers need to align streams, while sender clocks
+/// are only known to the sender.  An optional rate overrides the rate of the
+/// rtpmap line, and an id names the clock for several streams to share it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MediaClock<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+
+    /// direct, sender or IEEE1722
+    pub mode: &'a str,
+
+    /// What follows the =, e.g. the offset of a direct clock
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<&'a str>,
+
+    /// The numerator and denominator of the rate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<(u32, u32)>,
+}
+
+impl<'a> MediaClock<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ').filter(|part| !part.is_empty()).peekable();
+        let id = match split.peek().and_then(|part| part.strip_prefix("id=")) {
+            Some(id) => {
+                split.next();
+                Some(id)
+            }
+            None => None,
+        };
+        let clock = split.next().ok_or(Error::MissingField {
+            field: "media clock",
+        })?;
+        let (mode, value) = match clock.split_once('=') {
+            Some((mode, value)) => (mode, Some(value)),
+            None => (clock, None),
+        };
+        let rate = match split.next() {
+            Some(rate) => Some(parse_rate(rate)?),
+            None => None,
+        };
+
+        if let Some(extra) = split.next() {
+            return Err(Error::invalid_value(
+                "media clock",
+                format!("unexpected '{}'", extra),
+            ));
+        }
+
+        if mode == "direct" {
+            if let Some(offset) = value {
+                parse_number::<u64>(Some(offset), "media clock offset")?;
+            }
+        }
+
+        Ok(Self {
+            id,
+            mode,
+            value,
+            rate,
+        })
+    }
+
+    /// The offset of the RTP timestamps from the reference clock's epoch,
+    /// for a direct clock
+    pub fn direct_offset(&self) -> Option<u64> {
+        match self.mode {
+            "direct" => match self.value {
+                Some(offset) => offset.parse().ok(),
+                None => Some(0),
+            },
+            _ => None,
+        }
+    }
+}
+
+// rate=48000/1
+fn parse_rate(value: &str) -> Result<(u32, u32)> {
+    let rate = value
+        .strip_prefix("rate=")
+        .ok_or_else(|| Error::invalid_value("media clock", format!("unexpected '{}'", value)))?;
+    let (numerator, denominator) = rate.split_once('/').ok_or(Error::MissingField {
+        field: "media clock rate denominator",
+    })?;
+
+    Ok((
+        parse_number::<u32>(Some(numerator), "media clock rate")?,
+        parse_number::<u32>(Some(denominator), "media clock rate denominator")?,
+    ))
+}
+
+impl<'a> fmt::Display for MediaClock<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(id) = self.id {
+            write!(f, "id={} ", id)?;
+        }
+
+        write!(f, "{}", self.mode)?;
+
+        if let Some(value) = self.value {
+            write!(f, "={}", value)?;
+        }
+
+        if let Some((numerator, denominator)) = self.rate {
+            write!(f, " rate={}/{}", numerator, denominator)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdp::Sdp;
+
+    #[test]
+    fn it_parses_reference_clocks() {
+        let ptp = TsRefclk::new("ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:37").unwrap();
+
+        assert_eq!(
+            ptp.ptp(),
+            Some(PtpClock {
+                version: "IEEE1588-2008",
+                grandmaster: Some("39-A7-94-FF-FE-07-CB-D0"),
+                domain: Some(37),
+            })
+        );
+        assert_eq!(
+            TsRefclk::new("ptp=IEEE1588-2008:traceable")
+                .unwrap()
+                .ptp()
+                .unwrap()
+                .grandmaster,
+            None
+        );
+        assert_eq!(TsRefclk::new("local").unwrap().to_string(), "local");
+        assert_eq!(TsRefclk::new("ntp=203.0.113.10").unwrap().ptp(), None);
+        assert!(TsRefclk::new("ptp=IEEE1588-2008:39-A7-94").is_err());
+        assert!(TsRefclk::new("=203.0.113.10").is_err());
+    }
+
+    #[test]
+    fn it_parses_media_clocks() {
+        let clock =
+            MediaClock::new("id=MDA6NjA6MmI6MjA6MTI6MWY= direct=963214424 rate=1000/1001").unwrap();
+
+        assert_eq!(clock.id, Some("MDA6NjA6MmI6MjA6MTI6MWY="));
+        assert_eq!(clock.direct_offset(), Some(963214424));
+        assert_eq!(clock.rate, Some((1000, 1001)));
+        assert_eq!(
+            clock.to_string(),
+            "id=MDA6NjA6MmI6MjA6MTI6MWY= direct=963214424 rate=1000/1001"
+        );
+        assert_eq!(MediaClock::new("sender").unwrap().direct_offset(), None);
+        assert!(MediaClock::new("direct=now").is_err());
+        assert!(MediaClock::new("direct=0 rate=48000").is_err());
+    }
+
+    #[test]
+    fn it_parses_an_aes67_stream() {
+        let sdp = "v=0\r
+o=- 1311738121 1311738121 IN IP4 192.168.1.1\r
+s=Stage left I/O\r
+c=IN IP4 239.0.0.1/32\r
+t=0 0\r
+a=ts-refclk:ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:0\r
+m=audio 5004 RTP/AVP 96\r
+i=Channels 1-8\r
+a=recvonly\r
+a=rtpmap:96 L24/48000/8\r
+a=ptime:0.125\r
+a=mediaclk:direct=963214424\r
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let media = &parsed.media[0];
+        let ts_refclk = media.effective_ts_refclk(&parsed);
+
+        assert_eq!(ts_refclk[0].ptp().unwrap().domain, Some(0));
+        assert_eq!(media.ptime, Some(0.125));
+        assert_eq!(
+            media.mediaclk.as_ref().unwrap().direct_offset(),
+            Some(963214424)
+        );
+        assert_eq!(parsed.to_string(), sdp);
+    }
+}
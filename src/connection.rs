@@ -1,35 +1,88 @@
+use crate::address::parse_ip;
 use crate::error::Result;
-use crate::utils::parse_str;
+use crate::utils::{parse_number, parse_str};
+use core::fmt;
+use core::net::IpAddr;
 
 /// SDP Connection
 ///
 /// c=IN IP4 217.130.243.155
+/// c=IN IP4 224.2.1.1/127/3
 ///
 /// c is a connection line. This line gives the IP from where you expect to
 /// send and receive the real time traffic. As ICE is mandatory in WebRTC the
 /// IP in the c-line is not going to be used.
 ///
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// Multicast addresses can carry a TTL (IP4 only) and the number of contiguous
+/// addresses in use, appended with slashes.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Connection<'a> {
     pub network_type: &'a str,
     pub ip_type: &'a str,
     pub ip_address: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_of_addresses: Option<u32>,
 }
 
 impl<'a> Connection<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let network_type = parse_str(split.next(), 1)?;
-        let ip_type = parse_str(split.next(), 2)?;
-        let ip_address = parse_str(split.next(), 3)?;
-
-        Ok(Self {
+        let network_type = parse_str(split.next(), "network type")?;
+        let ip_type = parse_str(split.next(), "ip type")?;
+        let mut address = parse_str(split.next(), "ip address")?.split('/');
+        let ip_address = parse_str(address.next(), "ip address")?;
+        let mut connection = Self {
             network_type,
             ip_type,
             ip_address,
-        })
+            ..Default::default()
+        };
+
+        // IP6 multicast addresses have no TTL
+        if ip_type == "IP4" {
+            if let Some(ttl) = address.next() {
+                connection.ttl = Some(parse_number::<u8>(Some(ttl), "ttl")?);
+            }
+        }
+
+        if let Some(number) = address.next() {
+            connection.number_of_addresses =
+                Some(parse_number::<u32>(Some(number), "number of addresses")?);
+        }
+
+        Ok(connection)
+    }
+
+    /// The address as an IP, checked against the IP4 or IP6 address type
+    pub fn addr(&self) -> Result<IpAddr> {
+        parse_ip(self.ip_address, Some(self.ip_type))
     }
 }
+
+impl<'a> fmt::Display for Connection<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.network_type, self.ip_type, self.ip_address
+        )?;
+
+        if let Some(ttl) = self.ttl {
+            write!(f, "/{}", ttl)?;
+        }
+
+        if let Some(number_of_addresses) = self.number_of_addresses {
+            write!(f, "/{}", number_of_addresses)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,8 +95,37 @@ mod tests {
             network_type: "IN",
             ip_type: "IP4",
             ip_address: "203.0.113.1",
+            ..Default::default()
         };
 
         assert_eq!(parsed, expected);
     }
+
+    #[test]
+    fn it_parses_a_multicast_connection() {
+        let connection = "IN IP4 224.2.1.1/127/3";
+        let parsed = Connection::new(connection).unwrap();
+
+        assert_eq!(parsed.ip_address, "224.2.1.1");
+        assert_eq!(parsed.ttl, Some(127));
+        assert_eq!(parsed.number_of_addresses, Some(3));
+        assert_eq!(parsed.to_string(), connection);
+
+        let parsed = Connection::new("IN IP6 FF15::101/3").unwrap();
+
+        assert_eq!(parsed.ttl, None);
+        assert_eq!(parsed.number_of_addresses, Some(3));
+        assert!(Connection::new("IN IP4 224.2.1.1/300").is_err());
+    }
+
+    #[test]
+    fn it_parses_the_connection_address() {
+        let parsed = Connection::new("IN IP6 2001:db8::1").unwrap();
+
+        assert_eq!(parsed.addr().unwrap().to_string(), "2001:db8::1");
+        assert!(Connection::new("IN IP4 2001:db8::1")
+            .unwrap()
+            .addr()
+            .is_err());
+    }
 }
@@ -0,0 +1,182 @@
+use crate::error::{Error, Result};
+use crate::utils::{parse_number, parse_str};
+use alloc::{format, vec::Vec};
+use core::fmt;
+
+/// SDES Crypto
+///
+/// a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:32
+///
+/// SDP Security Descriptions (RFC 4568) carry the SRTP master key in the SDP
+/// itself instead of negotiating it with DTLS, which is still common in SIP.
+/// The tag identifies the line in the offer/answer exchange, followed by the
+/// crypto suite, one or more key parameters and optional session parameters
+/// such as UNENCRYPTED_SRTCP.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Crypto<'a> {
+    pub tag: u32,
+    pub suite: &'a str,
+    pub key_params: Vec<KeyParam<'a>>,
+    pub session_params: Vec<&'a str>,
+}
+
+/// A single key parameter, e.g. inline:base64key|2^20|1:32
+///
+/// The key is the concatenated master key and salt, the lifetime is the
+/// number of packets the key can protect and the MKI (master key identifier)
+/// lets multiple keys be used at the same time.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct KeyParam<'a> {
+    pub method: &'a str,
+    pub key: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifetime: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mki: Option<Mki>,
+}
+
+/// A master key identifier and its length in bytes
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Mki {
+    pub value: u64,
+    pub length: u8,
+}
+
+impl<'a> Crypto<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ').filter(|item| !item.is_empty());
+        let tag = parse_number::<u32>(split.next(), "tag")?;
+        let suite = parse_str(split.next(), "crypto suite")?;
+        let key_params = parse_str(split.next(), "key params")?
+            .split(';')
+            .map(KeyParam::new)
+            .collect::<Result<Vec<_>>>()?;
+        let session_params = split.collect();
+
+        Ok(Self {
+            tag,
+            suite,
+            key_params,
+            session_params,
+        })
+    }
+}
+
+impl<'a> KeyParam<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.splitn(2, ':');
+        let method = parse_str(split.next(), "key method")?;
+        let mut split = parse_str(split.next(), "key info")?.split('|');
+        let key = parse_str(split.next(), "key")?;
+        let mut key_param = Self {
+            method,
+            key,
+            ..Default::default()
+        };
+
+        // the lifetime and mki are both optional, only the mki contains a ':'
+        for item in split {
+            match item.split_once(':') {
+                Some((value, length)) => {
+                    key_param.mki = Some(Mki {
+                        value: parse_number::<u64>(Some(value), "mki")?,
+                        length: parse_number::<u8>(Some(length), "mki length")?,
+                    })
+                }
+                None if key_param.mki.is_none() => key_param.lifetime = Some(item),
+                None => {
+                    return Err(Error::invalid_value(
+                        "lifetime",
+                        format!("'{}' must precede the mki", item),
+                    ))
+                }
+            }
+        }
+
+        Ok(key_param)
+    }
+}
+
+impl<'a> fmt::Display for Crypto<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} ", self.tag, self.suite)?;
+
+        for (index, key_param) in self.key_params.iter().enumerate() {
+            if index > 0 {
+                write!(f, ";")?;
+            }
+
+            write!(f, "{}", key_param)?;
+        }
+
+        for session_param in &self.session_params {
+            write!(f, " {}", session_param)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for KeyParam<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.method, self.key)?;
+
+        if let Some(lifetime) = self.lifetime {
+            write!(f, "|{}", lifetime)?;
+        }
+
+        if let Some(mki) = &self.mki {
+            write!(f, "|{}:{}", mki.value, mki.length)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_crypto() {
+        let crypto = "1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:32 UNENCRYPTED_SRTCP";
+        let parsed = Crypto::new(crypto).unwrap();
+        let expected = Crypto {
+            tag: 1,
+            suite: "AES_CM_128_HMAC_SHA1_80",
+            key_params: vec![KeyParam {
+                method: "inline",
+                key: "PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR",
+                lifetime: Some("2^20"),
+                mki: Some(Mki {
+                    value: 1,
+                    length: 32,
+                }),
+            }],
+            session_params: vec!["UNENCRYPTED_SRTCP"],
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), crypto);
+    }
+
+    #[test]
+    fn it_parses_a_crypto_with_multiple_keys() {
+        let crypto = "2 AES_CM_128_HMAC_SHA1_32 inline:NzB4d1BINUAvLEw6UzF3WSJ+PSdFcGdUJShpX1Zj|1:4;inline:QUJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXow|2:4";
+        let parsed = Crypto::new(crypto).unwrap();
+
+        assert_eq!(parsed.key_params.len(), 2);
+        assert_eq!(parsed.key_params[1].lifetime, None);
+        assert_eq!(
+            parsed.key_params[1].mki,
+            Some(Mki {
+                value: 2,
+                length: 4
+            })
+        );
+        assert!(parsed.session_params.is_empty());
+        assert_eq!(parsed.to_string(), crypto);
+    }
+}
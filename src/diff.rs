@@ -0,0 +1,313 @@
+//! Differences between two versions of a SDP, e.g. before and after a
+//! renegotiation.
+//!
+//! Media sections are matched by mid when both have one, by position otherwise.
+//!
+//! Two SDPs can also be compared for what they negotiate, ignoring what a
+//! renegotiation changes without changing the session, e.g. to skip a re-offer
+//! that changes nothing.
+
+use crate::direction::Direction;
+use crate::media::Media;
+use crate::sdp::Sdp;
+use alloc::{string::ToString, vec, vec::Vec};
+use core::fmt::Display;
+
+/// What changed between two SDPs
+#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+pub struct SdpDelta<'a> {
+    /// Indexes, in the new SDP, of media sections that didn't exist before
+    pub added_media: Vec<usize>,
+
+    /// Indexes, in the old SDP, of media sections that no longer exist
+    pub removed_media: Vec<usize>,
+
+    /// Changes to the media sections present in both SDPs
+    pub media: Vec<MediaDelta<'a>>,
+}
+
+/// What changed in a media section
+#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+pub struct MediaDelta<'a> {
+    /// The index of the media section in the new SDP
+    pub index: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mid: Option<&'a str>,
+
+    /// The old and new direction, session-level directions included
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<(Direction, Direction)>,
+
+    /// The ICE credentials changed, so the transport must restart
+    pub ice_restart: bool,
+
+    pub added_ssrcs: Vec<u32>,
+    pub removed_ssrcs: Vec<u32>,
+    pub added_codecs: Vec<&'a str>,
+    pub removed_codecs: Vec<&'a str>,
+}
+
+impl<'a> SdpDelta<'a> {
+    /// Nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added_media.is_empty() && self.removed_media.is_empty() && self.media.is_empty()
+    }
+
+    /// An ICE restart happened in any of the media sections
+    pub fn requires_transport_restart(&self) -> bool {
+        self.media.iter().any(|media| media.ice_restart)
+    }
+}
+
+impl<'a> MediaDelta<'a> {
+    fn is_empty(&self) -> bool {
+        self.direction.is_none()
+            && !self.ice_restart
+            && self.added_ssrcs.is_empty()
+            && self.removed_ssrcs.is_empty()
+            && self.added_codecs.is_empty()
+            && self.removed_codecs.is_empty()
+    }
+}
+
+pub(crate) fn diff<'a>(old: &Sdp<'a>, new: &Sdp<'a>) -> SdpDelta<'a> {
+    let mut delta = SdpDelta::default();
+    let mut matched = vec![false; old.media.len()];
+
+    for (index, media) in new.media.iter().enumerate() {
+        match matching_media(old, media, index) {
+            Some(old_index) => {
+                matched[old_index] = true;
+
+                let media_delta = diff_media(old, &old.media[old_index], new, media, index);
+
+                if !media_delta.is_empty() {
+                    delta.media.push(media_delta);
+                }
+            }
+            None => delta.added_media.push(index),
+        }
+    }
+
+    delta.removed_media = matched
+        .iter()
+        .enumerate()
+        .filter(|(_, matched)| !**matched)
+        .map(|(index, _)| index)
+        .collect();
+
+    delta
+}
+
+// find the old version of a media section
+fn matching_media(old: &Sdp, media: &Media, index: usize) -> Option<usize> {
+    match media.mid {
+        Some(mid) => old.media.iter().position(|old| old.mid == Some(mid)),
+        None => old
+            .media
+            .get(index)
+            .filter(|old| old.mid.is_none() && old.r#type == media.r#type)
+            .map(|_| index),
+    }
+}
+
+fn diff_media<'a>(
+    old_sdp: &Sdp<'a>,
+    old: &Media<'a>,
+    new_sdp: &Sdp<'a>,
+    new: &Media<'a>,
+    index: usize,
+) -> MediaDelta<'a> {
+    let (old_ssrcs, new_ssrcs) = (ssrcs(old), ssrcs(new));
+    let (old_codecs, new_codecs) = (codecs(old), codecs(new));
+
+    MediaDelta {
+        index,
+        mid: new.mid,
+        direction: Some((
+            old.effective_direction(old_sdp),
+            new.effective_direction(new_sdp),
+        ))
+        .filter(|(old, new)| old != new),
+        ice_restart: old.effective_ice_ufrag(old_sdp) != new.effective_ice_ufrag(new_sdp)
+            || old.effective_ice_pwd(old_sdp) != new.effective_ice_pwd(new_sdp),
+        added_ssrcs: difference(&new_ssrcs, &old_ssrcs),
+        removed_ssrcs: difference(&old_ssrcs, &new_ssrcs),
+        added_codecs: difference(&new_codecs, &old_codecs),
+        removed_codecs: difference(&old_codecs, &new_codecs),
+    }
+}
+
+fn ssrcs(media: &Media) -> Vec<u32> {
+    // the lines of several ssrcs may be interleaved
+    let mut ssrcs = media.ssrc.iter().map(|ssrc| ssrc.id).collect::<Vec<_>>();
+    ssrcs.sort_unstable();
+    ssrcs.dedup();
+    ssrcs
+}
+
+fn codecs<'a>(media: &Media<'a>) -> Vec<&'a str> {
+    media.rtpmap.iter().map(|rtpmap| rtpmap.codec).collect()
+}
+
+// the items of `left` not in `right`
+fn difference<T: PartialEq + Copy>(left: &[T], right: &[T]) -> Vec<T> {
+    left.iter()
+        .filter(|item| !right.contains(item))
+        .cloned()
+        .collect()
+}
+
+// the SDPs are equal once their session id and version, the order of their
+// attribute lines and where their directions are given are left out
+pub(crate) fn semantically_equal(left: &Sdp, right: &Sdp) -> bool {
+    normalized(left) == normalized(right)
+}
+
+fn normalized<'a>(sdp: &Sdp<'a>) -> Sdp<'a> {
+    let mut normalized = sdp.clone();
+    normalized.origin.session_id = 0;
+    normalized.origin.session_version = 0;
+    normalized.attributes.clear();
+    normalized.direction = None;
+    sort(&mut normalized.bandwidth);
+    sort(&mut normalized.fingerprints);
+    sort(&mut normalized.groups);
+    sort(&mut normalized.ts_refclk);
+    sort(&mut normalized.extensions);
+
+    for media in &mut normalized.media {
+        media.direction = Some(media.effective_direction(sdp));
+        sort(&mut media.bandwidth);
+        sort(&mut media.candidates);
+        sort(&mut media.remote_candidates);
+        sort(&mut media.rtpmap);
+        sort(&mut media.fmtp);
+        sort(&mut media.rtc_fb);
+        sort(&mut media.imageattr);
+        sort(&mut media.ssrc);
+        sort(&mut media.ssrc_groups);
+        sort(&mut media.fingerprints);
+        sort(&mut media.crypto);
+        sort(&mut media.rid);
+        sort(&mut media.ts_refclk);
+        sort(&mut media.extensions);
+    }
+
+    normalized
+}
+
+// lines of the same attribute in the order of their values
+fn sort<T: Display>(lines: &mut [T]) {
+    lines.sort_by_cached_key(|line| line.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+m=audio 54400 RTP/SAVPF 0 96
+a=mid:0
+a=rtpmap:0 PCMU/8000
+a=rtpmap:96 opus/48000
+a=sendrecv
+a=ssrc:1399694169 cname:foo
+m=video 55400 RTP/SAVPF 97
+a=mid:1
+a=rtpmap:97 H264/90000";
+
+    #[test]
+    fn it_finds_no_changes() {
+        let old = Sdp::parse(OLD).unwrap();
+
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn it_compares_what_sdps_negotiate() {
+        let reoffer = "v=0
+o=- 20519 3 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+a=ice-ufrag:F7gI
+m=audio 54400 RTP/SAVPF 0 96
+a=rtpmap:96 opus/48000
+a=rtpmap:0 PCMU/8000
+a=mid:0
+a=ssrc:1399694169 cname:foo
+m=video 55400 RTP/SAVPF 97
+a=mid:1
+a=rtpmap:97 H264/90000";
+        let old = Sdp::parse(OLD).unwrap();
+        let reoffer = Sdp::parse(reoffer).unwrap();
+        let reordered = reoffer.to_string().replace("0 96\r\n", "96 0\r\n");
+
+        assert!(old.semantically_equal(&reoffer));
+        assert!(!old.semantically_equal(&Sdp::parse(&reordered).unwrap()));
+        assert!(!old.semantically_equal(&Sdp::parse(&OLD.replace("sendrecv", "recvonly")).unwrap()));
+        assert!(!old.semantically_equal(&Sdp::parse(&OLD.replace("F7gI", "Hl9k")).unwrap()));
+    }
+
+    #[test]
+    fn it_diffs_two_sdps() {
+        let new = "v=0
+o=- 20518 1 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-ufrag:Hl9k
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+m=audio 54400 RTP/SAVPF 96
+a=mid:0
+a=rtpmap:96 opus/48000
+a=sendonly
+a=ssrc:1399694169 cname:foo
+a=ssrc:2231627014 cname:foo
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel
+a=mid:2";
+        let old = Sdp::parse(OLD).unwrap();
+        let new = Sdp::parse(new).unwrap();
+        let delta = old.diff(&new);
+        let expected = SdpDelta {
+            added_media: vec![1],
+            removed_media: vec![1],
+            media: vec![MediaDelta {
+                index: 0,
+                mid: Some("0"),
+                direction: Some((Direction::SendRecv, Direction::SendOnly)),
+                ice_restart: true,
+                added_ssrcs: vec![2231627014],
+                removed_ssrcs: vec![],
+                added_codecs: vec![],
+                removed_codecs: vec!["PCMU"],
+            }],
+        };
+
+        assert_eq!(delta, expected);
+        assert!(delta.requires_transport_restart());
+    }
+
+    #[test]
+    fn it_reports_interleaved_ssrcs_once() {
+        let new = OLD.replace(
+            "a=ssrc:1399694169 cname:foo",
+            "a=ssrc:2231627014 cname:foo
+a=ssrc:3735928559 cname:foo
+a=ssrc:2231627014 msid:stream track
+a=ssrc:3735928559 msid:stream track",
+        );
+        let old = Sdp::parse(OLD).unwrap();
+        let new = Sdp::parse(&new).unwrap();
+        let delta = &old.diff(&new).media[0];
+
+        assert_eq!(delta.added_ssrcs, vec![2231627014, 3735928559]);
+        assert_eq!(delta.removed_ssrcs, vec![1399694169]);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::error::{Error, Result};
+use core::fmt;
+
+/// SDP Direction
+///
+/// a=sendrecv
+/// a=sendonly
+/// a=recvonly
+/// a=inactive
+///
+/// Whether an endpoint sends and/or receives the media of a section (RFC 3264).
+/// A direction given at the session level applies to every media section that
+/// doesn't have its own; without either, media is sendrecv.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    #[default]
+    SendRecv,
+    SendOnly,
+    RecvOnly,
+    Inactive,
+}
+
+impl Direction {
+    pub fn new(value: &str) -> Result<Self> {
+        match value {
+            "sendrecv" => Ok(Self::SendRecv),
+            "sendonly" => Ok(Self::SendOnly),
+            "recvonly" => Ok(Self::RecvOnly),
+            "inactive" => Ok(Self::Inactive),
+            _ => Err(Error::invalid_value("direction", "unknown direction")),
+        }
+    }
+
+    // the direction that sends and/or receives
+    pub(crate) fn from_capabilities(sends: bool, receives: bool) -> Self {
+        match (sends, receives) {
+            (true, true) => Self::SendRecv,
+            (true, false) => Self::SendOnly,
+            (false, true) => Self::RecvOnly,
+            (false, false) => Self::Inactive,
+        }
+    }
+
+    /// The attribute name of the direction, e.g. "sendonly"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SendRecv => "sendrecv",
+            Self::SendOnly => "sendonly",
+            Self::RecvOnly => "recvonly",
+            Self::Inactive => "inactive",
+        }
+    }
+
+    /// Whether media is sent in this direction
+    pub fn sends(&self) -> bool {
+        matches!(self, Self::SendRecv | Self::SendOnly)
+    }
+
+    /// Whether media is received in this direction
+    pub fn receives(&self) -> bool {
+        matches!(self, Self::SendRecv | Self::RecvOnly)
+    }
+
+    /// The direction that sends when this one receives and vice versa
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::SendOnly => Self::RecvOnly,
+            Self::RecvOnly => Self::SendOnly,
+            direction => *direction,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_directions() {
+        for value in ["sendrecv", "sendonly", "recvonly", "inactive"] {
+            assert_eq!(Direction::new(value).unwrap().to_string(), value);
+        }
+
+        assert!(Direction::new("sendbothways").is_err());
+    }
+
+    #[test]
+    fn it_reverses_directions() {
+        assert_eq!(Direction::SendOnly.reverse(), Direction::RecvOnly);
+        assert_eq!(Direction::RecvOnly.reverse(), Direction::SendOnly);
+        assert_eq!(Direction::Inactive.reverse(), Direction::Inactive);
+        assert!(Direction::SendOnly.sends() && !Direction::SendOnly.receives());
+    }
+}
@@ -1,31 +1,194 @@
 //! Custom errors for this applicatoin.
 //!
-//! Map errors from libraries to Error.
+//! Each variant is a kind of problem that callers can match on, and errors
+//! found while parsing a message are wrapped with the line they were found on.
 //!
 //! Define a reusable Result type.
 
-use log::error;
-use std::net::AddrParseError;
+use crate::validate::{Diagnostic, Severity};
+use alloc::{boxed::Box, string::String, string::ToString};
 
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
-#[derive(thiserror::Error, Debug, Serialize)]
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
 pub enum Error {
     #[error("Error converting SDP to JSON: {0}.")]
     ConvertToJson(String),
 
-    #[error("Parse error: {0}.")]
-    Parse(String),
+    #[error("Error converting JSON to SDP: {0}.")]
+    ConvertFromJson(String),
+
+    #[error("Error converting SDP to YAML: {0}.")]
+    ConvertToYaml(String),
+
+    #[error("Error converting SDP to TOML: {0}.")]
+    ConvertToToml(String),
+
+    /// A value missing from a line
+    #[error("missing {field}")]
+    MissingField { field: &'static str },
+
+    /// A value that isn't a number, or is out of range
+    #[error("invalid {field} '{value}'")]
+    InvalidNumber { field: &'static str, value: String },
+
+    /// A value breaking the rules of its field, e.g. a candidate component
+    /// other than 1 or 2
+    #[error("invalid {field}: {reason}")]
+    InvalidValue { field: &'static str, reason: String },
+
+    /// A fingerprint hash that isn't uppercase hex bytes separated by colons,
+    /// or isn't as long as the digests of its hash function (RFC 8122)
+    #[error("invalid fingerprint '{hash}': {reason}")]
+    InvalidFingerprint { hash: String, reason: String },
+
+    /// An attribute this parser doesn't support, at the session or media level
+    #[error("unsupported {level} attribute '{name}'")]
+    UnknownAttribute { level: &'static str, name: String },
+
+    /// A line that isn't well formed, or of an unsupported type
+    #[error("invalid line: {reason}")]
+    InvalidLine { reason: String },
+
+    /// A line every SDP must have, e.g. v= or o=
+    #[error("missing required line '{key}='")]
+    MissingRequiredLine { key: char },
+
+    /// A second line of a type its section has one of, when
+    /// `ParseOptions::duplicates` rejects them, with the name of an attribute,
+    /// e.g. 'a' and mid for a=mid
+    #[error(
+        "'{key}={}' may only appear once in its section",
+        .attribute.as_deref().unwrap_or_default()
+    )]
+    Duplicate {
+        key: char,
+        attribute: Option<String>,
+    },
+
+    /// A message over one of the `ParseOptions::limits`, e.g. with more media
+    /// sections than allowed
+    #[error("{limit} over the limit of {max}")]
+    LimitExceeded { limit: &'static str, max: usize },
+
+    /// A line out of the order of RFC 8866 section 5, when
+    /// `ParseOptions::enforce_ordering` is set
+    #[error("'{found}=' on line {line} is out of order, expected {expected}")]
+    OutOfOrder {
+        expected: String,
+        found: char,
+        line: usize,
+    },
+
+    /// An error on a line of the SDP message
+    #[error("Parse error on line {line}: {source}. Line: '{raw}'")]
+    Parse {
+        line: usize,
+        raw: String,
+        source: Box<Error>,
+    },
 }
 
-// Log out errors
-fn log_error(error: Error) -> Error {
-    error!("{:?}", error);
-    error
+impl Error {
+    pub(crate) fn invalid_value(field: &'static str, reason: impl Into<String>) -> Self {
+        Error::InvalidValue {
+            field,
+            reason: reason.into(),
+        }
+    }
+
+    pub(crate) fn invalid_line(reason: impl Into<String>) -> Self {
+        Error::InvalidLine {
+            reason: reason.into(),
+        }
+    }
+
+    pub(crate) fn unknown_attribute(level: &'static str, name: &str) -> Self {
+        Error::UnknownAttribute {
+            level,
+            name: name.into(),
+        }
+    }
+
+    // attach the position of the offending line
+    pub(crate) fn at_line(self, line: usize, raw: &str) -> Self {
+        match self {
+            Error::Parse { .. } | Error::OutOfOrder { .. } => self,
+            error => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(line, raw, error = %error, "invalid SDP line");
+
+                Error::Parse {
+                    line,
+                    raw: raw.into(),
+                    source: Box::new(error),
+                }
+            }
+        }
+    }
+
+    /// The kind of error, without the line it was found on
+    pub fn kind(&self) -> &Error {
+        match self {
+            Error::Parse { source, .. } => source.kind(),
+            error => error,
+        }
+    }
+
+    /// The line the error was found on, if it came from parsing a message
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Error::Parse { line, .. } | Error::OutOfOrder { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// The error as a diagnostic, e.g. to report it alongside validation results
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            line: self.line(),
+            message: self.kind().to_string(),
+        }
+    }
 }
 
-impl From<AddrParseError> for Error {
-    fn from(error: AddrParseError) -> Self {
-        log_error(Error::Parse(error.to_string()))
+// serialized as its diagnostic, e.g. in the json of a validation report
+#[cfg(feature = "serde")]
+impl serde::Serialize for Error {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        self.diagnostic().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_wraps_errors_with_their_line() {
+        let error = Error::MissingField { field: "port" }.at_line(3, "m=audio");
+
+        assert_eq!(error.line(), Some(3));
+        assert_eq!(error.kind(), &Error::MissingField { field: "port" });
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 3: missing port. Line: 'm=audio'"
+        );
+        assert_eq!(error.diagnostic().message, "missing port");
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "json"))]
+    fn it_serializes_as_a_diagnostic() {
+        let error = Error::MissingField { field: "port" }.at_line(3, "m=audio");
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::to_value(error.diagnostic()).unwrap()
+        );
     }
 }
@@ -0,0 +1,106 @@
+use crate::attribute::Attribute;
+use crate::error::Result;
+use alloc::{boxed::Box, sync::Arc};
+use core::any::Any;
+use core::fmt;
+
+/// A parser for an attribute the crate doesn't know, registered with
+/// `ParseOptions`
+///
+/// a=x-google-flag:conference
+/// a=source-filter: incl IN IP4 239.0.0.1 192.168.1.1
+///
+/// Vendor-specific attributes are turned into values of the caller's own type,
+/// kept as an `Extension` of the session or of the media section the line
+/// belongs to.
+pub trait AttributeParser: Send + Sync {
+    /// The name of the attribute, e.g. x-google-flag
+    fn name(&self) -> &str;
+
+    /// Parse the value of an a=name:value line, or of a value-less a=name line
+    fn parse(&self, value: Option<&str>) -> Result<Box<dyn Any + Send + Sync>>;
+}
+
+/// An attribute parsed by a registered `AttributeParser`
+///
+/// The line is kept as it appeared in the message, so the attribute is written
+/// back out unchanged.  The typed value isn't serialized and is missing from an
+/// SDP restored from JSON.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Extension<'a> {
+    #[serde(borrow)]
+    pub attribute: Attribute<'a>,
+
+    #[serde(skip)]
+    parsed: Parsed,
+}
+
+impl<'a> Extension<'a> {
+    pub fn new(attribute: Attribute<'a>, parser: &dyn AttributeParser) -> Result<Self> {
+        Ok(Self {
+            parsed: Parsed(Some(Arc::from(parser.parse(attribute.value)?))),
+            attribute,
+        })
+    }
+
+    /// The value of the registered parser, if it is of type `T`
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.parsed.0.as_deref()?.downcast_ref()
+    }
+}
+
+impl<'a> fmt::Display for Extension<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.attribute)
+    }
+}
+
+/// The type-erased value of an extension, compared through the line it was
+/// parsed from
+#[derive(Clone, Default)]
+struct Parsed(Option<Arc<dyn Any + Send + Sync>>);
+
+impl fmt::Debug for Parsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("Parsed(..)"),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+impl PartialEq for Parsed {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct GoogleFlag;
+
+    impl AttributeParser for GoogleFlag {
+        fn name(&self) -> &str {
+            "x-google-flag"
+        }
+
+        fn parse(&self, value: Option<&str>) -> Result<Box<dyn Any + Send + Sync>> {
+            Ok(Box::new(value == Some("conference")))
+        }
+    }
+
+    #[test]
+    fn it_parses_an_extension() {
+        let attribute = Attribute {
+            key: "x-google-flag",
+            value: Some("conference"),
+        };
+        let extension = Extension::new(attribute, &GoogleFlag).unwrap();
+
+        assert_eq!(extension.get::<bool>(), Some(&true));
+        assert_eq!(extension.get::<u32>(), None);
+        assert_eq!(extension.to_string(), "x-google-flag:conference");
+    }
+}
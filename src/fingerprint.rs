@@ -1,5 +1,7 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::utils::parse_str;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
 
 /// SDP Fingerprint
 ///
@@ -11,19 +13,101 @@ use crate::utils::parse_str;
 /// certificates used in DTLS, if the fingerprint doesn’t match, then the session
 /// should be rejected.
 ///
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// The hash is checked when parsing: pairs of uppercase hex digits separated
+/// by colons (RFC 8122 section 5), as many as the digest of a hash function in
+/// the IANA registry has bytes, e.g. 32 for sha-256.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Fingerprint<'a> {
     pub r#type: &'a str,
     pub hash: &'a str,
 }
 
 impl<'a> Fingerprint<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let r#type = parse_str(split.next(), 1)?;
-        let hash = parse_str(split.next(), 2)?;
+        let r#type = parse_str(split.next(), "hash function")?;
+        let hash = parse_str(split.next(), "hash")?;
 
-        Ok(Self { r#type, hash })
+        if !hash.split(':').all(is_uppercase_hex) {
+            return Err(invalid_fingerprint(
+                hash,
+                "expected uppercase hex bytes separated by colons",
+            ));
+        }
+
+        let fingerprint = Self { r#type, hash };
+        fingerprint.digest_bytes()?;
+
+        Ok(fingerprint)
+    }
+
+    /// The hash as bytes, e.g. to compare it against the digest of the DTLS
+    /// certificate. The number of bytes is checked against the hash function.
+    pub fn digest_bytes(&self) -> Result<Vec<u8>> {
+        let bytes = self
+            .hash
+            .split(':')
+            .map(|byte| match byte.len() {
+                2 => u8::from_str_radix(byte, 16).ok(),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| invalid_fingerprint(self.hash, "not hex"))?;
+
+        match digest_length(self.r#type) {
+            Some(length) if length != bytes.len() => Err(invalid_fingerprint(
+                self.hash,
+                format!(
+                    "{} hash must be {} bytes, found {}",
+                    self.r#type,
+                    length,
+                    bytes.len()
+                ),
+            )),
+            _ => Ok(bytes),
+        }
+    }
+
+    /// Whether this is the fingerprint of a certificate digest, comparing the
+    /// hash function case-insensitively and the hash as bytes, so sha-256 and
+    /// SHA-256 or 0d:1c and 0D:1C match
+    pub fn matches(&self, hash_function: &str, digest: &[u8]) -> bool {
+        self.r#type.eq_ignore_ascii_case(hash_function)
+            && self.digest_bytes().is_ok_and(|bytes| bytes == digest)
+    }
+}
+
+fn invalid_fingerprint(hash: &str, reason: impl Into<String>) -> Error {
+    Error::InvalidFingerprint {
+        hash: hash.into(),
+        reason: reason.into(),
+    }
+}
+
+// a byte of a hash as RFC 8122 writes it, e.g. 0D
+fn is_uppercase_hex(byte: &str) -> bool {
+    byte.len() == 2
+        && byte
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'A'..=b'F').contains(&b))
+}
+
+// the digest length in bytes of the hash functions in the IANA registry
+fn digest_length(hash_function: &str) -> Option<usize> {
+    match hash_function.to_ascii_lowercase().as_str() {
+        "md2" | "md5" => Some(16),
+        "sha-1" => Some(20),
+        "sha-224" => Some(28),
+        "sha-256" => Some(32),
+        "sha-384" => Some(48),
+        "sha-512" => Some(64),
+        _ => None,
+    }
+}
+
+impl<'a> fmt::Display for Fingerprint<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.r#type, self.hash)
     }
 }
 
@@ -42,4 +126,64 @@ mod tests {
 
         assert_eq!(parsed, expected);
     }
+
+    #[test]
+    fn it_validates_a_fingerprint_hash() {
+        let sha_384 = ["A5"; 48].join(":");
+
+        assert!(Fingerprint::new(&format!("sha-384 {}", sha_384)).is_ok());
+        assert!(Fingerprint::new("x-custom 0D:1C").is_ok());
+        assert!(matches!(
+            Fingerprint::new("sha-1 42:89:c5:c6:55:9d:6e:c8:e8:83:55:2a:39:f9:b6:eb:e9:a3:a9:e7"),
+            Err(Error::InvalidFingerprint { .. })
+        ));
+        assert!(matches!(
+            Fingerprint::new("sha-256 0D:1C"),
+            Err(Error::InvalidFingerprint { .. })
+        ));
+        assert!(Fingerprint::new("sha-512 0D1C").is_err());
+        assert!(Fingerprint::new("x-custom 0D::1C").is_err());
+    }
+
+    #[test]
+    fn it_converts_a_fingerprint_to_bytes() {
+        let fingerprint = Fingerprint {
+            r#type: "sha-1",
+            hash: "42:89:c5:c6:55:9d:6e:c8:e8:83:55:2a:39:f9:b6:eb:e9:a3:a9:e7",
+        };
+        let expected = vec![
+            0x42, 0x89, 0xc5, 0xc6, 0x55, 0x9d, 0x6e, 0xc8, 0xe8, 0x83, 0x55, 0x2a, 0x39, 0xf9,
+            0xb6, 0xeb, 0xe9, 0xa3, 0xa9, 0xe7,
+        ];
+
+        assert_eq!(fingerprint.digest_bytes().unwrap(), expected);
+    }
+
+    #[test]
+    fn it_matches_a_certificate_digest() {
+        let fingerprint = Fingerprint {
+            r#type: "SHA-1",
+            hash: "42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7",
+        };
+        let digest = fingerprint.digest_bytes().unwrap();
+
+        assert!(fingerprint.matches("sha-1", &digest));
+        assert!(!fingerprint.matches("sha-256", &digest));
+        assert!(!fingerprint.matches("sha-1", &digest[1..]));
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_digest() {
+        let short = Fingerprint {
+            r#type: "sha-256",
+            hash: "42:89:c5:c6",
+        };
+        let invalid = Fingerprint {
+            r#type: "sha-1",
+            hash: "42:89:zz",
+        };
+
+        assert!(short.digest_bytes().is_err());
+        assert!(invalid.digest_bytes().is_err());
+    }
 }
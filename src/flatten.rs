@@ -0,0 +1,139 @@
+//! The media sections of a SDP with the session-level values and the
+//! transport of their BUNDLE group applied, as a RTP stack configures them.
+//!
+//! The bundled sections of a group share the transport of its first mid, the
+//! tagged section (RFC 9143 section 7), which gives the port, connection, ICE
+//! credentials, DTLS fingerprints and setup role of every section of the
+//! group, bundle-only ones included.
+
+use crate::bandwidth::Bandwidth;
+use crate::connection::Connection;
+use crate::direction::Direction;
+use crate::fingerprint::Fingerprint;
+use crate::group::GroupSemantics;
+use crate::ice::IceCredentials;
+use crate::media::Media;
+use crate::sdp::Sdp;
+use alloc::vec::Vec;
+
+/// A media section with every value it inherits resolved, see `Sdp::flatten`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ResolvedMedia<'a> {
+    /// The index of the section in `Sdp::media`
+    pub index: usize,
+
+    /// The index of the section whose transport it uses, its own unless it is
+    /// bundled
+    pub transport: usize,
+
+    pub mid: Option<&'a str>,
+
+    /// The port of the transport, 0 for a rejected section
+    pub port: u16,
+    pub connection: Option<Connection<'a>>,
+    pub direction: Direction,
+    pub ice: Option<IceCredentials<'a>>,
+    pub fingerprints: Vec<Fingerprint<'a>>,
+    pub setup: Option<&'a str>,
+    pub rtcp_mux: bool,
+
+    /// The b lines of the section, or the session-level ones without any
+    pub bandwidth: Vec<Bandwidth<'a>>,
+}
+
+pub(crate) fn flatten<'a>(sdp: &Sdp<'a>) -> Vec<ResolvedMedia<'a>> {
+    sdp.media
+        .iter()
+        .enumerate()
+        .map(|(index, media)| {
+            let transport = bundle_tag(sdp, media).unwrap_or(index);
+            let tagged = &sdp.media[transport];
+            let bandwidth = match media.bandwidth.is_empty() {
+                true => &sdp.bandwidth,
+                false => &media.bandwidth,
+            };
+
+            ResolvedMedia {
+                index,
+                transport,
+                mid: media.mid,
+                port: tagged.port,
+                connection: tagged.effective_connection(sdp).cloned(),
+                direction: media.effective_direction(sdp),
+                ice: sdp.ice_credentials(transport),
+                fingerprints: tagged.effective_fingerprints(sdp).to_vec(),
+                setup: tagged.effective_setup(sdp),
+                rtcp_mux: tagged.rtcp_mux,
+                bandwidth: bandwidth.clone(),
+            }
+        })
+        .collect()
+}
+
+// the index of the tagged section of the BUNDLE group of a section that isn't
+// rejected
+fn bundle_tag(sdp: &Sdp, media: &Media) -> Option<usize> {
+    if media.is_rejected() {
+        return None;
+    }
+
+    let mid = media.mid?;
+    let group = sdp.groups.iter().find(|group| {
+        group.semantics_type() == GroupSemantics::Bundle && group.mids.contains(&mid)
+    })?;
+
+    group
+        .mids
+        .iter()
+        .find_map(|mid| sdp.media_id(mid))
+        .map(|id| id.index())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_flattens_a_bundled_sdp() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+c=IN IP4 203.0.113.1
+b=AS:512
+t=0 0
+a=group:BUNDLE 0 1
+a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+a=fingerprint:sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E
+a=recvonly
+m=audio 54400 UDP/TLS/RTP/SAVPF 111
+a=mid:0
+a=setup:actpass
+a=rtcp-mux
+m=video 0 UDP/TLS/RTP/SAVPF 96
+b=AS:256
+a=mid:1
+a=bundle-only
+a=sendonly
+m=video 0 UDP/TLS/RTP/SAVPF 96
+a=mid:2";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let flattened = parsed.flatten();
+        let video = &flattened[1];
+
+        assert_eq!(flattened.len(), 3);
+        assert_eq!((video.index, video.transport), (1, 0));
+        assert_eq!(video.port, 54400);
+        assert_eq!(video.connection, parsed.connection);
+        assert_eq!(video.direction, Direction::SendOnly);
+        assert_eq!(video.ice, parsed.ice_credentials(0));
+        assert_eq!(video.fingerprints, parsed.fingerprints);
+        assert_eq!(video.setup, Some("actpass"));
+        assert!(video.rtcp_mux);
+        assert_eq!(video.bandwidth[0].bandwidth, 256);
+        assert_eq!(flattened[0].direction, Direction::RecvOnly);
+        assert_eq!(flattened[0].bandwidth[0].bandwidth, 512);
+        assert_eq!((flattened[2].transport, flattened[2].port), (2, 0));
+        assert!(!flattened[2].rtcp_mux);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::error::{Error, Result};
+use crate::media::Candidate;
+use crate::reader::SdpLine;
+use crate::sdp::Sdp;
+use crate::utils::{lines, parse_str};
+use crate::{push_value, set_value};
+use alloc::{format, vec::Vec};
+
+/// SDP Fragment
+///
+/// a=ice-ufrag:F7gI
+/// a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+/// m=audio 9 RTP/AVP 0
+/// a=mid:0
+/// a=candidate:1 1 UDP 2113667327 203.0.113.1 54400 typ host
+/// a=end-of-candidates
+///
+/// Trickle ICE (RFC 8840) sends the candidates gathered after the offer or
+/// answer in partial bodies, e.g. in SIP INFO requests or signaling messages.
+/// A fragment only carries the ICE lines, grouped under the m line or a=mid of
+/// the media section they belong to.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SdpFragment<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_ufrag: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_pwd: Option<&'a str>,
+
+    /// Gathering has finished for every media section
+    #[serde(default)]
+    pub end_of_candidates: bool,
+    pub media: Vec<MediaFragment<'a>>,
+}
+
+/// The ICE lines of a fragment for one media section
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MediaFragment<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mid: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_ufrag: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_pwd: Option<&'a str>,
+    pub candidates: Vec<Candidate<'a>>,
+
+    #[serde(default)]
+    pub end_of_candidates: bool,
+}
+
+impl<'a> SdpFragment<'a> {
+    // parse each line of the fragment
+    pub fn parse(fragment: &'a str) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for (index, line) in lines(fragment.trim_end()).enumerate() {
+            SdpLine::new(line)
+                .and_then(|sdp_line| parsed.parse_line(sdp_line))
+                .map_err(|error| error.at_line(index + 1, line))?;
+        }
+
+        Ok(parsed)
+    }
+
+    fn parse_line(&mut self, line: SdpLine<'a>) -> Result<()> {
+        match line {
+            SdpLine::Media(_) => {
+                self.media.push(MediaFragment::default());
+                Ok(())
+            }
+            // a mid without an m line starts the lines of the next section
+            SdpLine::Attribute { name: "mid", value } => {
+                let starts_section = self.media.last().is_none_or(|media| media.mid.is_some());
+
+                if starts_section {
+                    self.media.push(MediaFragment::default());
+                }
+
+                self.media_mut().mid = Some(parse_str(value, "mid")?);
+                Ok(())
+            }
+            SdpLine::Attribute { name, value } => match self.media.last_mut() {
+                Some(media) => media.parse_attribute(name, value),
+                None => self.parse_session_attribute(name, value),
+            },
+            SdpLine::Field { key, .. } => Err(Error::invalid_line(format!(
+                "unsupported line type '{}' in a SDP fragment",
+                key
+            ))),
+        }
+    }
+
+    fn parse_session_attribute(&mut self, name: &'a str, value: Option<&'a str>) -> Result<()> {
+        match (name, value) {
+            ("ice-ufrag", Some(value)) => set_value!(self.ice_ufrag, Result::Ok(Some(value))),
+            ("ice-pwd", Some(value)) => set_value!(self.ice_pwd, Result::Ok(Some(value))),
+            ("end-of-candidates", None) => set_value!(self.end_of_candidates, Result::Ok(true)),
+            // candidates before any mid or m line
+            ("candidate", Some(_)) => self.media_mut().parse_attribute(name, value),
+            _ => Err(Error::unknown_attribute("fragment", name)),
+        }
+    }
+
+    fn media_mut(&mut self) -> &mut MediaFragment<'a> {
+        if self.media.is_empty() {
+            self.media.push(MediaFragment::default());
+        }
+
+        let last = self.media.len() - 1;
+        &mut self.media[last]
+    }
+}
+
+impl<'a> MediaFragment<'a> {
+    fn parse_attribute(&mut self, name: &'a str, value: Option<&'a str>) -> Result<()> {
+        match (name, value) {
+            ("ice-ufrag", Some(value)) => set_value!(self.ice_ufrag, Result::Ok(Some(value))),
+            ("ice-pwd", Some(value)) => set_value!(self.ice_pwd, Result::Ok(Some(value))),
+            ("candidate", Some(value)) => push_value!(self.candidates, Candidate::new(value)),
+            ("end-of-candidates", None) => set_value!(self.end_of_candidates, Result::Ok(true)),
+            _ => Err(Error::unknown_attribute("fragment", name)),
+        }
+    }
+}
+
+// merge the candidates of a fragment into the media sections of their mid
+// nothing is merged if any section of the fragment can't be
+pub(crate) fn apply<'a>(sdp: &mut Sdp<'a>, fragment: &SdpFragment<'a>) -> Result<()> {
+    let indexes = fragment
+        .media
+        .iter()
+        .map(|media_fragment| section_index(sdp, fragment, media_fragment))
+        .collect::<Result<Vec<_>>>()?;
+
+    for (media_fragment, index) in fragment.media.iter().zip(indexes) {
+        let media = &mut sdp.media[index];
+
+        for candidate in &media_fragment.candidates {
+            if !media.candidates.contains(candidate) {
+                media.candidates.push(candidate.clone());
+            }
+        }
+
+        media.end_of_candidates |= media_fragment.end_of_candidates;
+    }
+
+    if fragment.end_of_candidates {
+        for media in &mut sdp.media {
+            media.end_of_candidates = true;
+        }
+    }
+
+    Ok(())
+}
+
+// the media section with the mid of a fragment section, of the same ICE generation
+fn section_index(
+    sdp: &Sdp,
+    fragment: &SdpFragment,
+    media_fragment: &MediaFragment,
+) -> Result<usize> {
+    let mid = parse_str(media_fragment.mid, "mid")?;
+    let index = sdp.media_id(mid).map(|id| id.index()).ok_or_else(|| {
+        Error::invalid_value("mid", format!("no media section with mid '{}'", mid))
+    })?;
+
+    // candidates of another ufrag belong to an ICE restart, not to this SDP
+    let ufrag = media_fragment.ice_ufrag.or(fragment.ice_ufrag);
+
+    match (ufrag, sdp.media[index].effective_ice_ufrag(sdp)) {
+        (Some(ufrag), Some(current)) if ufrag != current => Err(Error::invalid_value(
+            "ice-ufrag",
+            format!(
+                "'{}' doesn't match the ufrag '{}' of mid '{}'",
+                ufrag, current, mid
+            ),
+        )),
+        _ => Ok(index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+m=audio 9 UDP/TLS/RTP/SAVPF 111
+a=mid:0
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=mid:1";
+
+    #[test]
+    fn it_parses_a_fragment() {
+        let fragment = "a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+m=audio 9 RTP/AVP 0
+a=mid:0
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54400 typ host
+a=end-of-candidates
+m=video 9 RTP/AVP 0
+a=mid:1
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54402 typ host
+";
+        let parsed = SdpFragment::parse(fragment).unwrap();
+
+        assert_eq!(parsed.ice_ufrag, Some("F7gI"));
+        assert_eq!(parsed.media.len(), 2);
+        assert_eq!(parsed.media[0].mid, Some("0"));
+        assert!(parsed.media[0].end_of_candidates);
+        assert_eq!(parsed.media[1].candidates[0].port, 54402);
+    }
+
+    #[test]
+    fn it_parses_candidate_only_fragments() {
+        let fragment = "a=mid:0
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54400 typ host
+a=mid:1
+a=candidate:2 1 UDP 1694498815 198.51.100.7 61000 typ srflx raddr 203.0.113.1 rport 54402
+a=end-of-candidates";
+        let parsed = SdpFragment::parse(fragment).unwrap();
+
+        assert_eq!(parsed.media.len(), 2);
+        assert_eq!(parsed.media[1].mid, Some("1"));
+        assert_eq!(parsed.media[1].candidates[0].r#type, "srflx");
+        assert!(parsed.media[1].end_of_candidates);
+        assert!(SdpFragment::parse("v=0").is_err());
+        assert!(SdpFragment::parse("a=rtpmap:0 PCMU/8000").is_err());
+    }
+
+    #[test]
+    fn it_applies_a_fragment() {
+        let mut sdp = Sdp::parse(SDP).unwrap();
+        let fragment = SdpFragment::parse(
+            "a=mid:1
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54402 typ host
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54402 typ host",
+        )
+        .unwrap();
+
+        sdp.apply_fragment(&fragment).unwrap();
+        sdp.apply_fragment(&fragment).unwrap();
+
+        assert!(sdp.media[0].candidates.is_empty());
+        assert_eq!(sdp.media[1].candidates.len(), 1);
+
+        sdp.apply_fragment(&SdpFragment::parse("a=end-of-candidates").unwrap())
+            .unwrap();
+
+        assert!(sdp.media.iter().all(|media| media.end_of_candidates));
+    }
+
+    #[test]
+    fn it_rejects_fragments_for_other_sections() {
+        let mut sdp = Sdp::parse(SDP).unwrap();
+        let candidate = "a=candidate:1 1 UDP 2113667327 203.0.113.1 54402 typ host";
+        let (unknown, restart) = (
+            format!("a=mid:2\n{}", candidate),
+            format!("a=ice-ufrag:Hx9b\na=mid:0\n{}", candidate),
+        );
+        let unknown = SdpFragment::parse(&unknown).unwrap();
+        let restart = SdpFragment::parse(&restart).unwrap();
+        let anonymous = SdpFragment::parse(candidate).unwrap();
+
+        assert!(sdp.apply_fragment(&unknown).is_err());
+        assert!(sdp.apply_fragment(&restart).is_err());
+        assert!(sdp.apply_fragment(&anonymous).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::error::Result;
+use crate::media::Media;
+use crate::sdp::Sdp;
+use crate::utils::parse_str;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// SDP Group
+///
+/// a=group:BUNDLE 0 1
+///
+/// a=group:LS 1 2
+///
+/// A group line (RFC 5888) ties media sections together by their a=mid
+/// identification tags. The semantics say what the group means: BUNDLE, the
+/// most common in WebRTC, asks for all the media sections to share a single
+/// transport, and LS (lip synchronization), used by telepresence systems, asks
+/// for the audio and video of a participant to be played out in sync.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Group<'a> {
+    pub semantics: &'a str,
+
+    #[serde(borrow)]
+    pub mids: Vec<&'a str>,
+}
+
+impl<'a> Group<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ');
+        let semantics = parse_str(split.next(), "semantics")?;
+        let mids = split.filter(|mid| !mid.is_empty()).collect();
+
+        Ok(Self { semantics, mids })
+    }
+
+    /// The typed semantics of this group
+    pub fn semantics_type(&self) -> GroupSemantics<'a> {
+        GroupSemantics::from(self.semantics)
+    }
+
+    /// The media sections of the mids of this group, in the order of the group,
+    /// without the mids no media section has
+    pub fn media<'b>(&self, sdp: &'b Sdp<'a>) -> Vec<&'b Media<'a>> {
+        self.mids
+            .iter()
+            .filter_map(|mid| sdp.media_by_mid(mid))
+            .collect()
+    }
+}
+
+/// The group semantics registered with IANA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupSemantics<'a> {
+    /// Lip synchronization (RFC 5888)
+    Ls,
+    /// Flow identification (RFC 5888)
+    Fid,
+    /// Single reservation flow (RFC 3524)
+    Srf,
+    /// Alternative network address types (RFC 4091)
+    Anat,
+    /// Forward error correction (RFC 5956)
+    FecFr,
+    /// A single transport for the media sections (RFC 9143)
+    Bundle,
+    Other(&'a str),
+}
+
+impl<'a> GroupSemantics<'a> {
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            GroupSemantics::Ls => "LS",
+            GroupSemantics::Fid => "FID",
+            GroupSemantics::Srf => "SRF",
+            GroupSemantics::Anat => "ANAT",
+            GroupSemantics::FecFr => "FEC-FR",
+            GroupSemantics::Bundle => "BUNDLE",
+            GroupSemantics::Other(other) => other,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for GroupSemantics<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "LS" => GroupSemantics::Ls,
+            "FID" => GroupSemantics::Fid,
+            "SRF" => GroupSemantics::Srf,
+            "ANAT" => GroupSemantics::Anat,
+            "FEC-FR" => GroupSemantics::FecFr,
+            "BUNDLE" => GroupSemantics::Bundle,
+            other => GroupSemantics::Other(other),
+        }
+    }
+}
+
+impl<'a> fmt::Display for GroupSemantics<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'a> fmt::Display for Group<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.semantics)?;
+
+        for mid in &self.mids {
+            write!(f, " {}", mid)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_group() {
+        let group = "BUNDLE 0 1";
+        let parsed = Group::new(group).unwrap();
+        let expected = Group {
+            semantics: "BUNDLE",
+            mids: vec!["0", "1"],
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), group);
+        assert_eq!(parsed.semantics_type(), GroupSemantics::Bundle);
+        assert_eq!(
+            Group::new("X-CUSTOM 1").unwrap().semantics_type(),
+            GroupSemantics::Other("X-CUSTOM")
+        );
+    }
+}
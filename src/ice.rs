@@ -0,0 +1,67 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+/// ICE Credentials
+///
+/// a=ice-ufrag:F7gI
+/// a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+///
+/// The username fragment and password an ICE agent uses to authenticate the
+/// connectivity checks of a media section.  They can be given per media
+/// section or once for the whole session.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct IceCredentials<'a> {
+    pub ufrag: &'a str,
+    pub pwd: &'a str,
+}
+
+/// The ICE options of a=ice-options registered with IANA
+///
+/// a=ice-options:trickle renomination
+///
+/// Trickle ICE sends candidates after the SDP (RFC 8840), renomination lets
+/// the controlling agent nominate another pair after one was selected, and
+/// ice2 marks an agent implementing RFC 8445 rather than RFC 5245.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IceOption<'a> {
+    Trickle,
+    Renomination,
+    Ice2,
+    Other(&'a str),
+}
+
+impl<'a> IceOption<'a> {
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            IceOption::Trickle => "trickle",
+            IceOption::Renomination => "renomination",
+            IceOption::Ice2 => "ice2",
+            IceOption::Other(other) => other,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for IceOption<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "trickle" => IceOption::Trickle,
+            "renomination" => IceOption::Renomination,
+            "ice2" => IceOption::Ice2,
+            other => IceOption::Other(other),
+        }
+    }
+}
+
+impl<'a> fmt::Display for IceOption<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// the space-separated options of an a=ice-options line
+pub(crate) fn parse_options(value: &str) -> Vec<&str> {
+    value
+        .split(' ')
+        .filter(|option| !option.is_empty())
+        .collect()
+}
@@ -0,0 +1,381 @@
+use crate::error::{Error, Result};
+use crate::media::Format;
+use crate::utils::{parse_number, parse_str};
+use alloc::{format, vec, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+
+/// Image Attributes
+///
+/// a=imageattr:97 send [x=800,y=640,sar=1.1,q=0.6] [x=480,y=320] recv [x=330,y=250]
+/// a=imageattr:* send [x=[320:16:640],y=[240:16:480],par=[1.2-1.3]] recv *
+///
+/// The image sizes an endpoint can send and receive for a payload type, or
+/// for all of them with "*" (RFC 6236).  Each set gives the accepted widths
+/// and heights, with the sample and picture aspect ratios and the preference
+/// of the set.  A direction with "*" accepts any size, and is kept as an
+/// empty list of sets.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImageAttr<'a> {
+    /// A payload type, or the "*" wildcard
+    #[serde(borrow)]
+    pub payload: Format<'a>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send: Option<Vec<ImageAttrSet>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recv: Option<Vec<ImageAttrSet>>,
+}
+
+/// A set of image sizes, e.g. [x=[320:16:640],y=[240:16:480],q=0.5]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImageAttrSet {
+    pub x: ImageAttrRange<u32>,
+    pub y: ImageAttrRange<u32>,
+
+    /// The sample aspect ratio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sar: Option<ImageAttrRange<f32>>,
+
+    /// The picture aspect ratio, a range of width over height
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub par: Option<ImageAttrRange<f32>>,
+
+    /// The preference of the set, from 0 to 1 (0.5 when missing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<f32>,
+}
+
+/// The values of an image attribute, e.g. 640, [320,640] or [320:16:640]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ImageAttrRange<T> {
+    Value(T),
+    Values(Vec<T>),
+
+    /// From min to max, every step values if given (x and y only)
+    Range {
+        min: T,
+        step: Option<T>,
+        max: T,
+    },
+}
+
+impl<'a> ImageAttr<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let (payload, sets) = value.split_once(' ').unwrap_or((value, ""));
+        let payload = match parse_str(Some(payload), "imageattr payload type")? {
+            "*" => Format::Token(payload),
+            _ => Format::Payload(parse_number::<u8>(Some(payload), "imageattr payload type")?),
+        };
+        let mut image_attr = Self {
+            payload,
+            send: None,
+            recv: None,
+        };
+
+        let mut split = sets.split_whitespace().peekable();
+
+        while let Some(direction) = split.next() {
+            let mut sets = vec![];
+
+            while let Some(set) = split.next_if(|set| set.starts_with('[')) {
+                sets.push(ImageAttrSet::new(set)?);
+            }
+
+            if sets.is_empty() {
+                parse_str(split.next().filter(|&any| any == "*"), "imageattr sets")?;
+            }
+
+            match direction {
+                "send" if image_attr.send.is_none() => image_attr.send = Some(sets),
+                "recv" if image_attr.recv.is_none() => image_attr.recv = Some(sets),
+                _ => {
+                    return Err(Error::invalid_value(
+                        "imageattr direction",
+                        format!("expected send or recv once, found '{}'", direction),
+                    ))
+                }
+            }
+        }
+
+        if image_attr.send.is_none() && image_attr.recv.is_none() {
+            return Err(Error::MissingField {
+                field: "imageattr direction",
+            });
+        }
+
+        Ok(image_attr)
+    }
+}
+
+impl<'a> fmt::Display for ImageAttr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.payload)?;
+
+        for (direction, sets) in [("send", &self.send), ("recv", &self.recv)].iter() {
+            if let Some(sets) = sets {
+                write!(f, " {}", direction)?;
+
+                if sets.is_empty() {
+                    f.write_str(" *")?;
+                }
+
+                for set in sets.iter() {
+                    write!(f, " {}", set)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ImageAttrSet {
+    pub fn new(value: &str) -> Result<Self> {
+        let inner = value
+            .strip_prefix('[')
+            .and_then(|value| value.strip_suffix(']'))
+            .ok_or_else(|| Error::invalid_value("imageattr set", "expected [...]"))?;
+        let (mut x, mut y, mut sar, mut par, mut q) = (None, None, None, None, None);
+
+        for parameter in split_parameters(inner) {
+            match parameter.split_once('=') {
+                Some(("x", value)) => x = Some(parse_range(value, ':', "imageattr x")?),
+                Some(("y", value)) => y = Some(parse_range(value, ':', "imageattr y")?),
+                Some(("sar", value)) => sar = Some(parse_range(value, '-', "imageattr sar")?),
+                Some(("par", value)) => par = Some(parse_par(value)?),
+                Some(("q", value)) => q = Some(parse_q(value)?),
+                // parameters defined after RFC 6236 are ignored
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            x: x.ok_or(Error::MissingField {
+                field: "imageattr x",
+            })?,
+            y: y.ok_or(Error::MissingField {
+                field: "imageattr y",
+            })?,
+            sar,
+            par,
+            q,
+        })
+    }
+}
+
+impl fmt::Display for ImageAttrSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[x=")?;
+        write_range(f, &self.x, ':')?;
+        f.write_str(",y=")?;
+        write_range(f, &self.y, ':')?;
+
+        if let Some(sar) = &self.sar {
+            f.write_str(",sar=")?;
+            write_range(f, sar, '-')?;
+        }
+
+        if let Some(par) = &self.par {
+            f.write_str(",par=")?;
+            write_range(f, par, '-')?;
+        }
+
+        if let Some(q) = self.q {
+            write!(f, ",q={}", q)?;
+        }
+
+        f.write_str("]")
+    }
+}
+
+// the comma separated parameters of a set, keeping the commas of [...] values
+fn split_parameters(value: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+
+    value
+        .split(move |c| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+
+            c == ',' && depth == 0
+        })
+        .filter(|parameter| !parameter.is_empty())
+}
+
+// a value, [a,b,...] or a range [min<separator>max], with a step for x and y
+fn parse_range<T: FromStr>(
+    value: &str,
+    separator: char,
+    field: &'static str,
+) -> Result<ImageAttrRange<T>> {
+    let inner = match value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+    {
+        Some(inner) => inner,
+        None => {
+            return Ok(ImageAttrRange::Value(parse_number::<T>(
+                Some(value),
+                field,
+            )?))
+        }
+    };
+
+    if inner.contains(separator) {
+        let bounds = inner
+            .split(separator)
+            .map(|bound| parse_number::<T>(Some(bound), field))
+            .collect::<Result<Vec<T>>>()?;
+
+        let mut bounds = bounds.into_iter();
+
+        return match (bounds.next(), bounds.next(), bounds.next(), bounds.next()) {
+            (Some(min), Some(max), None, None) => Ok(ImageAttrRange::Range {
+                min,
+                step: None,
+                max,
+            }),
+            (Some(min), Some(step), Some(max), None) if separator == ':' => {
+                Ok(ImageAttrRange::Range {
+                    min,
+                    step: Some(step),
+                    max,
+                })
+            }
+            _ => Err(Error::invalid_value(
+                field,
+                format!("invalid range '{}'", value),
+            )),
+        };
+    }
+
+    inner
+        .split(',')
+        .map(|value| parse_number::<T>(Some(value), field))
+        .collect::<Result<Vec<T>>>()
+        .map(ImageAttrRange::Values)
+}
+
+// the picture aspect ratio is always a range
+fn parse_par(value: &str) -> Result<ImageAttrRange<f32>> {
+    match parse_range::<f32>(value, '-', "imageattr par")? {
+        range @ ImageAttrRange::Range { .. } => Ok(range),
+        _ => Err(Error::invalid_value(
+            "imageattr par",
+            format!("expected a range, found '{}'", value),
+        )),
+    }
+}
+
+fn parse_q(value: &str) -> Result<f32> {
+    match parse_number::<f32>(Some(value), "imageattr q")? {
+        q if (0.0..=1.0).contains(&q) => Ok(q),
+        _ => Err(Error::invalid_value(
+            "imageattr q",
+            "must be between 0 and 1",
+        )),
+    }
+}
+
+fn write_range<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    range: &ImageAttrRange<T>,
+    separator: char,
+) -> fmt::Result {
+    match range {
+        ImageAttrRange::Value(value) => write!(f, "{}", value),
+        ImageAttrRange::Values(values) => {
+            f.write_str("[")?;
+
+            for (index, value) in values.iter().enumerate() {
+                let comma = if index == 0 { "" } else { "," };
+                write!(f, "{}{}", comma, value)?;
+            }
+
+            f.write_str("]")
+        }
+        ImageAttrRange::Range { min, step, max } => {
+            write!(f, "[{}{}", min, separator)?;
+
+            if let Some(step) = step {
+                write!(f, "{}{}", step, separator)?;
+            }
+
+            write!(f, "{}]", max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn it_parses_an_imageattr() {
+        let imageattr = "97 send [x=800,y=640,sar=1.1,q=0.6] [x=480,y=320] recv [x=330,y=250]";
+        let parsed = ImageAttr::new(imageattr).unwrap();
+        let send = parsed.send.as_ref().unwrap();
+
+        assert_eq!(parsed.payload, Format::Payload(97));
+        assert_eq!(send.len(), 2);
+        assert_eq!(send[0].x, ImageAttrRange::Value(800));
+        assert_eq!(send[0].sar, Some(ImageAttrRange::Value(1.1)));
+        assert_eq!(send[0].q, Some(0.6));
+        assert_eq!(
+            parsed.recv.as_ref().unwrap()[0].y,
+            ImageAttrRange::Value(250)
+        );
+        assert_eq!(parsed.to_string(), imageattr);
+    }
+
+    #[test]
+    fn it_parses_imageattr_ranges() {
+        let imageattr =
+            "* send [x=[320:16:640],y=[240:16:480],par=[1.2-1.3]] recv [x=[176,352],y=[144,288],sar=[0.9-1.1]]";
+        let parsed = ImageAttr::new(imageattr).unwrap();
+        let send = &parsed.send.as_ref().unwrap()[0];
+        let recv = &parsed.recv.as_ref().unwrap()[0];
+
+        assert_eq!(
+            send.x,
+            ImageAttrRange::Range {
+                min: 320,
+                step: Some(16),
+                max: 640
+            }
+        );
+        assert_eq!(
+            send.par,
+            Some(ImageAttrRange::Range {
+                min: 1.2,
+                step: None,
+                max: 1.3
+            })
+        );
+        assert_eq!(recv.x, ImageAttrRange::Values(vec![176, 352]));
+        assert_eq!(parsed.to_string(), imageattr);
+
+        let any = ImageAttr::new("100 recv *").unwrap();
+
+        assert_eq!(any.recv, Some(vec![]));
+        assert_eq!(any.send, None);
+        assert_eq!(any.to_string(), "100 recv *");
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_imageattr() {
+        assert!(ImageAttr::new("97").is_err());
+        assert!(ImageAttr::new("97 send").is_err());
+        assert!(ImageAttr::new("97 send [y=640]").is_err());
+        assert!(ImageAttr::new("97 send [x=800,y=640,q=2]").is_err());
+        assert!(ImageAttr::new("97 send [x=800,y=640,par=1.2]").is_err());
+        assert!(ImageAttr::new("97 both [x=800,y=640]").is_err());
+        assert!(ImageAttr::new("vp8 send *").is_err());
+    }
+}
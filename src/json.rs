@@ -0,0 +1,75 @@
+//! SDPs restored from the JSON of `Sdp::to_json()`, e.g. one stored in Redis
+//! or Postgres.
+//!
+//! `Sdp::from_json` borrows its strings from the JSON, so it can't restore a
+//! string written with escapes, e.g. a session name with a quote.  `SdpJson`
+//! owns the unescaped strings, and the restored SDP borrows from it instead.
+
+use crate::error::{Error, Result};
+use crate::sdp::Sdp;
+use alloc::string::ToString;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The JSON of a SDP, with its strings unescaped
+///
+/// let stored = SdpJson::from_json(&json)?;
+/// let sdp = stored.sdp();
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdpJson(Value);
+
+impl SdpJson {
+    // load the json, failing if it isn't the json of a SDP
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value =
+            serde_json::from_str(json).map_err(|e| Error::ConvertFromJson(e.to_string()))?;
+        let stored = SdpJson(value);
+        stored.restore()?;
+
+        Ok(stored)
+    }
+
+    // the SDP, borrowing its strings from the json
+    pub fn sdp(&self) -> Sdp<'_> {
+        self.restore()
+            .expect("the json was restored when it was loaded")
+    }
+
+    fn restore(&self) -> Result<Sdp<'_>> {
+        Sdp::deserialize(&self.0).map_err(|e| Error::ConvertFromJson(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=The \"quoted\" \\ session
+t=0 0
+a=tool:tab\tseparated
+m=audio 54400 RTP/SAVPF 0
+a=rtpmap:0 PCMU/8000";
+
+    #[test]
+    fn it_restores_escaped_strings() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let json = parsed.to_json().unwrap();
+
+        assert!(Sdp::from_json(&json).is_err());
+
+        let stored = SdpJson::from_json(&json).unwrap();
+        let restored = stored.sdp();
+
+        assert_eq!(restored, parsed);
+        assert_eq!(restored.session_name, "The \"quoted\" \\ session");
+        assert_eq!(restored.to_sdp_string(), parsed.to_sdp_string());
+    }
+
+    #[test]
+    fn it_fails_on_json_of_something_else() {
+        assert!(SdpJson::from_json("{\"v\": 0}").is_err());
+        assert!(SdpJson::from_json("not json").is_err());
+    }
+}
@@ -1,11 +1,112 @@
+//! A zero-copy Session Description Protocol parser.
+//!
+//! The parser only needs `alloc`, so the crate builds without `std` when the
+//! default `std` and `json` features are turned off.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 #[macro_use]
 extern crate serde_derive;
 
+mod address;
+mod anonymize;
+mod application;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod arbitrary;
+mod attribute;
+mod bandwidth;
+mod bitrate;
+mod charset;
+mod cliprect;
+mod clock;
 mod connection;
+mod crypto;
+mod diff;
+mod direction;
 mod error;
+mod extension;
 mod fingerprint;
+mod flatten;
+mod fragment;
+mod group;
+mod ice;
+mod imageattr;
+#[cfg(feature = "json")]
+mod json;
+mod lossless;
 mod media;
+mod media_id;
+mod msid;
+pub mod negotiate;
 mod origin;
+mod parser;
+mod payload;
+mod plan;
+mod reader;
+mod rtcp;
+mod rtsp;
 pub mod sdp;
+#[cfg(feature = "json")]
+mod sdp_transform;
+mod serialize;
+mod silence;
+mod simulcast;
+mod stats;
+mod t38;
+mod template;
 mod time;
 mod utils;
+mod validate;
+mod visit;
+mod warning;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use address::Address;
+pub use anonymize::AnonymizeOptions;
+pub use application::{Application, Sctpmap};
+pub use attribute::Attribute;
+pub use bandwidth::{Bandwidth, BandwidthType};
+pub use bitrate::GoogleBitrateHints;
+pub use charset::Decoding;
+pub use cliprect::Cliprect;
+pub use clock::{MediaClock, PtpClock, TsRefclk};
+pub use connection::Connection;
+pub use crypto::{Crypto, KeyParam, Mki};
+pub use diff::{MediaDelta, SdpDelta};
+pub use direction::Direction;
+pub use error::{Error, Result};
+pub use extension::{AttributeParser, Extension};
+pub use fingerprint::Fingerprint;
+pub use flatten::ResolvedMedia;
+pub use fragment::{MediaFragment, SdpFragment};
+pub use group::{Group, GroupSemantics};
+pub use ice::{IceCredentials, IceOption};
+pub use imageattr::{ImageAttr, ImageAttrRange, ImageAttrSet};
+#[cfg(feature = "json")]
+pub use json::SdpJson;
+pub use lossless::RawLines;
+pub use media::{
+    Candidate, Fmtp, Format, Media, MediaType, RemoteCandidate, RtcpFb, RtcpFbType, Rtpmap, Ssrc,
+    SsrcGroup, SsrcInfo, TransportProtocol,
+};
+pub use media_id::{MediaId, MidIndex};
+pub use msid::MsidSemantic;
+pub use origin::Origin;
+pub use parser::{DuplicatePolicy, Limits, ParseOptions, SdpParser};
+pub use payload::PayloadConflict;
+pub use reader::{SdpLine, SdpReader};
+pub use rtcp::Rtcp;
+pub use sdp::{LineEnding, Sdp};
+pub use serialize::{Profile, SerializeOptions};
+pub use silence::SilenceSupp;
+pub use simulcast::{Rid, RidRestriction, Simulcast, SimulcastStream};
+pub use stats::SdpStats;
+pub use t38::T38;
+pub use template::WebrtcOfferConfig;
+pub use time::{Repeat, Time, TimeDescription, TypedTime, ZoneAdjustment};
+pub use validate::{Diagnostic, Severity, SourceLine};
+pub use visit::Visitor;
+pub use warning::{Warning, WarningKind};
@@ -0,0 +1,264 @@
+//! Byte-for-byte re-emission of a parsed SDP.
+//!
+//! With `ParseOptions::lossless` set, every line is kept as it appeared in the
+//! message, with its line ending, in the session or media section it belongs
+//! to.  `Sdp::to_sdp_string_lossless()` writes those lines back, so a SIP
+//! B2BUA can drop or reorder media sections without normalizing the others.
+//!
+//! A section edited since it was parsed, e.g. by `Media::remove_codec` or by
+//! assigning one of its fields, no longer writes the lines it was parsed from
+//! but its typed fields.  The parser keeps each section as its typed fields
+//! wrote it then, and the writer compares it to what they write now.
+
+use crate::sdp::Sdp;
+use alloc::{string::String, string::ToString, vec::Vec};
+use core::fmt;
+
+/// The lines of a section as they appeared in the message
+///
+/// Like source positions, the lines don't take part in equality.
+#[derive(Debug, Default, Clone)]
+pub struct RawLines<'a> {
+    pub(crate) lines: Vec<&'a str>,
+
+    /// The section as its typed fields wrote it once parsed
+    pub(crate) parsed: String,
+}
+
+impl<'a> RawLines<'a> {
+    /// The lines, with their line endings
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Forget the lines, so the section is written from its typed fields
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.parsed.clear();
+    }
+
+    // the lines still describe a section its typed fields write as `typed`
+    fn is_current(&self, typed: &str) -> bool {
+        !self.lines.is_empty() && self.parsed == typed
+    }
+}
+
+impl<'a> PartialEq for RawLines<'a> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+// keep each section of a lossless parse as its typed fields write it
+pub(crate) fn snapshot(sdp: &mut Sdp) {
+    sdp.raw.parsed = Session(sdp).to_string();
+
+    for media in &mut sdp.media {
+        media.raw.parsed = media.to_string();
+    }
+}
+
+/// Writes the raw lines of each section, or its typed fields if it has none
+/// or was edited since it was parsed
+pub(crate) struct Lossless<'b, 'a>(pub(crate) &'b Sdp<'a>);
+
+impl<'b, 'a> fmt::Display for Lossless<'b, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sdp = self.0;
+        let session = Session(sdp).to_string();
+
+        match sdp.raw.is_current(&session) {
+            true => write_raw(f, &sdp.raw, sdp.media.is_empty())?,
+            false => f.write_str(&session)?,
+        }
+
+        for (index, media) in sdp.media.iter().enumerate() {
+            let typed = media.to_string();
+
+            match media.raw.is_current(&typed) {
+                true => write_raw(f, &media.raw, index + 1 == sdp.media.len())?,
+                false => f.write_str(&typed)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// the lines before the first m line, from the typed fields
+struct Session<'b, 'a>(&'b Sdp<'a>);
+
+impl<'b, 'a> fmt::Display for Session<'b, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.write_session(f)
+    }
+}
+
+// the last line of the message may have no line ending, unless sections follow
+// it now
+fn write_raw(f: &mut fmt::Formatter, raw: &RawLines, last: bool) -> fmt::Result {
+    raw.lines().iter().try_for_each(|line| f.write_str(line))?;
+
+    match raw.lines().last() {
+        Some(line) if !last && !line.ends_with('\n') => f.write_str("\r\n"),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fragment::SdpFragment;
+    use crate::parser::ParseOptions;
+    use crate::sdp::Sdp;
+
+    const SDP: &str = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=\r
+t=0 0\r
+a=ice-options:trickle  renomination\r
+m=audio 54400 RTP/SAVPF 0\r
+a=rtpmap:0 pcmu/8000\r
+a=ptime:20.0\r
+m=video 55400 RTP/SAVPF 96\n\
+a=rtpmap:96 VP8/90000\n\
+\r
+";
+
+    fn lossless() -> ParseOptions {
+        let mut options = ParseOptions::default();
+        options.lossless = true;
+        options
+    }
+
+    const PLAN_B: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE 0 1
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0
+a=mid:0
+a=rtpmap:111 opus/48000/2
+a=fmtp:111 minptime=10
+a=rtpmap:0 PCMU/8000
+m=video 9 UDP/TLS/RTP/SAVPF 96 98
+a=mid:1
+a=rtpmap:96 VP8/90000
+a=rtpmap:98 H264/90000
+a=ssrc:2001 msid:stream camera
+a=ssrc:3001 msid:stream screen
+";
+
+    // the lossless output is the edited SDP, not the message it was parsed from
+    fn edit(edit: impl FnOnce(&mut Sdp)) -> String {
+        let mut parsed = Sdp::parse_with(PLAN_B, &lossless()).unwrap();
+        edit(&mut parsed);
+        let written = parsed.to_sdp_string_lossless();
+
+        assert_eq!(
+            Sdp::parse(&written).unwrap().to_sdp_string(),
+            parsed.to_sdp_string()
+        );
+        assert_ne!(written, PLAN_B);
+
+        written
+    }
+
+    #[test]
+    fn it_reproduces_the_message() {
+        let parsed = Sdp::parse_with(SDP, &lossless()).unwrap();
+
+        assert_eq!(parsed.to_sdp_string_lossless(), SDP);
+        assert_ne!(parsed.to_sdp_string(), SDP);
+        assert_eq!(parsed, Sdp::parse(SDP).unwrap());
+        assert_eq!(parsed.media[0].raw.lines()[1], "a=rtpmap:0 pcmu/8000\r\n");
+    }
+
+    #[test]
+    fn it_writes_edited_sections_from_the_model() {
+        let mut parsed = Sdp::parse_with(SDP.trim_end(), &lossless()).unwrap();
+        parsed.reject_media(0).unwrap();
+
+        assert_eq!(
+            parsed.to_sdp_string_lossless(),
+            SDP.replace(
+                "m=audio 54400 RTP/SAVPF 0\r\na=rtpmap:0 pcmu/8000\r\na=ptime:20.0\r\n",
+                "m=audio 0 RTP/SAVPF 0\r\n"
+            )
+            .trim_end()
+        );
+
+        parsed.media.push(parsed.media[0].clone());
+
+        assert!(parsed
+            .to_sdp_string_lossless()
+            .ends_with("a=rtpmap:96 VP8/90000\r\nm=audio 0 RTP/SAVPF 0\r\n"));
+        assert_eq!(
+            Sdp::parse(SDP).unwrap().to_sdp_string_lossless(),
+            Sdp::parse(SDP).unwrap().to_sdp_string()
+        );
+    }
+
+    #[test]
+    fn it_writes_removed_codecs() {
+        let written = edit(|sdp| sdp.remove_codec("PCMU"));
+
+        assert!(written.contains("m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n"));
+        assert!(!written.contains("PCMU"));
+        assert!(written.contains("a=rtpmap:98 H264/90000\n"));
+    }
+
+    #[test]
+    fn it_writes_the_codecs_kept() {
+        let written = edit(|sdp| sdp.keep_only_codecs(&["opus", "VP8"]));
+
+        assert!(written.contains("m=video 9 UDP/TLS/RTP/SAVPF 96\r\n"));
+        assert!(!written.contains("H264"));
+    }
+
+    #[test]
+    fn it_writes_opus_params() {
+        let written = edit(|sdp| sdp.set_opus_params("stereo=1"));
+
+        assert!(written.contains("a=fmtp:111 stereo=1\r\n"));
+        assert!(!written.contains("minptime"));
+    }
+
+    #[test]
+    fn it_writes_reordered_payloads() {
+        let written = edit(|sdp| sdp.reorder_payloads(&["H264"]));
+
+        assert!(written.contains("m=video 9 UDP/TLS/RTP/SAVPF 98 96\r\n"));
+    }
+
+    #[test]
+    fn it_writes_applied_fragments() {
+        let fragment =
+            SdpFragment::parse("a=mid:1\na=candidate:1 1 UDP 2113667327 203.0.113.1 9 typ host")
+                .unwrap();
+        let written = edit(|sdp| sdp.apply_fragment(&fragment).unwrap());
+
+        assert!(written.contains("a=candidate:1 1 UDP 2113667327 203.0.113.1 9 typ host\r\n"));
+    }
+
+    #[test]
+    fn it_writes_converted_plans() {
+        let written = edit(|sdp| *sdp = sdp.to_unified_plan());
+
+        assert!(written.contains("a=group:BUNDLE 0 1 screen\r\n"));
+        assert_eq!(written.matches("m=video").count(), 2);
+        assert_eq!(written.matches("a=ssrc:2001").count(), 1);
+
+        let unified = Sdp::parse_with(PLAN_B, &lossless())
+            .unwrap()
+            .to_unified_plan();
+        let written = unified.to_plan_b().to_sdp_string_lossless();
+
+        assert!(written.contains("a=group:BUNDLE 0 1\nm=audio"));
+        assert!(written.contains("a=mid:1\r\na=sendrecv\r\n"));
+        assert_eq!(written.matches("m=video").count(), 1);
+    }
+}
@@ -1,6 +1,34 @@
+use crate::address::Address;
+use crate::application::{Application, Sctpmap};
+use crate::bandwidth::{self, parse_maxprate, Bandwidth, BandwidthType};
+use crate::bitrate::{self, GoogleBitrateHints};
+use crate::cliprect::Cliprect;
+use crate::clock::{MediaClock, TsRefclk};
+use crate::connection::Connection;
+use crate::crypto::Crypto;
+use crate::direction::Direction;
 use crate::error::{Error, Result};
-use crate::utils::{parse_number, parse_str};
+use crate::extension::Extension;
+use crate::fingerprint::Fingerprint;
+use crate::ice::{self, IceOption};
+use crate::imageattr::ImageAttr;
+use crate::lossless::RawLines;
+use crate::payload;
+use crate::rtcp::Rtcp;
+use crate::rtsp;
+use crate::sdp::Sdp;
+use crate::serialize::{MediaLines, Profile};
+use crate::silence::SilenceSupp;
+use crate::simulcast::{Rid, Simulcast};
+use crate::t38::T38;
+use crate::utils::{parse_number, parse_str, write_attribute, write_flag, write_line};
+use crate::validate::SourceLine;
 use crate::{push_value, set_value};
+use alloc::{borrow::Cow, format, string::String, string::ToString, vec, vec::Vec};
+use core::cmp::Reverse;
+use core::convert::TryFrom;
+use core::fmt;
+use core::net::SocketAddr;
 
 /// SDP Media
 ///
@@ -22,52 +50,1177 @@ use crate::{push_value, set_value};
 /// In our SDP 100 maps to VP8 and 101 to VP9. Format numbers larger than 95
 /// are dynamic and there are a=rtpmap: attribute to map from the RTP payload
 /// type numbers to media encoding names.  There are also a=fmtp: attributes
-#[derive(Debug, Default, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Media<'a> {
     pub r#type: &'a str,
-    pub port: u64,
+    pub port: u16,
+
+    /// The number of consecutive ports of a hierarchically encoded stream,
+    /// e.g. 2 in m=video 49170/2 RTP/AVP 31
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_count: Option<u16>,
     pub protocol: &'a str,
-    pub payloads: &'a str,
+    pub payloads: Vec<u8>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formats: Vec<&'a str>,
     pub candidates: Vec<Candidate<'a>>,
-    pub direction: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
     pub fmtp: Vec<Fmtp<'a>>,
-    pub ptime: u64,
+
+    /// Milliseconds of media per packet, fractional for e.g. the 0.125 of
+    /// AES67 streams
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ptime: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxptime: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framerate: Option<f32>,
+
+    /// The highest packet rate, in packets per second (RFC 3890)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxprate: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orient: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cliprect: Option<Cliprect>,
+
+    /// The reference clocks of the RTP timestamps (RFC 7273)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ts_refclk: Vec<TsRefclk<'a>>,
+
+    /// How the RTP clock relates to the reference clock (RFC 7273)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mediaclk: Option<MediaClock<'a>>,
+
+    /// The silence suppression of a PSTN gateway (RFC 3108)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence_supp: Option<SilenceSupp<'a>>,
+
     pub rtpmap: Vec<Rtpmap<'a>>,
     pub rtc_fb: Vec<RtcpFb<'a>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imageattr: Vec<ImageAttr<'a>>,
     pub ssrc: Vec<Ssrc<'a>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ssrc_groups: Vec<SsrcGroup<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_ufrag: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_pwd: Option<&'a str>,
+
+    /// The ICE options of this media section, see `effective_ice_options`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ice_options: Vec<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fingerprints: Vec<Fingerprint<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<Connection<'a>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bandwidth: Vec<Bandwidth<'a>>,
+
+    /// The k= line of the section, obsolete (RFC 8866 section 5.12)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_key: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub application: Option<Application<'a>>,
+
+    /// The T.38 attributes of a fax image section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub t38: Option<T38<'a>>,
+
+    pub rid: Vec<Rid<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulcast: Option<Simulcast<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mid: Option<&'a str>,
+
+    /// The i= line describing the section, e.g. Main camera
+    #[serde(default, alias = "title", skip_serializing_if = "Option::is_none")]
+    pub information: Option<&'a str>,
+
+    /// What the media shows, e.g. slides or main (RFC 4796)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<&'a str>,
+
+    /// An identifier for the media section (RFC 4574)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<&'a str>,
+
+    /// The URL of the RTSP requests for the section, e.g. trackID=1, relative
+    /// to the session's control URL (RFC 2326)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control: Option<&'a str>,
+
+    /// The time range the RTSP media is available in, e.g. npt=0-
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crypto: Vec<Crypto<'a>>,
+
+    #[serde(default)]
+    pub end_of_candidates: bool,
+
+    /// RTP and RTCP share the port of the m line (RFC 5761)
+    #[serde(default)]
+    pub rtcp_mux: bool,
+
+    /// The section is only offered on the transport of its BUNDLE group, with
+    /// port 0 in an initial offer (RFC 8843 section 6)
+    #[serde(default)]
+    pub bundle_only: bool,
+
+    /// One and two-byte RTP header extensions may be mixed in a stream
+    /// (RFC 8285 section 6)
+    #[serde(default)]
+    pub extmap_allow_mixed: bool,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_candidates: Vec<RemoteCandidate<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtcp: Option<Rtcp<'a>>,
+
+    /// Attributes parsed by a registered `AttributeParser`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<Extension<'a>>,
+
+    #[serde(skip)]
+    pub line: SourceLine,
+
+    /// The lines of the section, when parsed with `ParseOptions::lossless`
+    #[serde(skip)]
+    pub raw: RawLines<'a>,
+}
+
+/// The media types registered for m lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaType<'a> {
+    Audio,
+    Video,
+    Application,
+    Text,
+    Message,
+    Image,
+    Other(&'a str),
+}
+
+impl<'a> MediaType<'a> {
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            MediaType::Audio => "audio",
+            MediaType::Video => "video",
+            MediaType::Application => "application",
+            MediaType::Text => "text",
+            MediaType::Message => "message",
+            MediaType::Image => "image",
+            MediaType::Other(other) => other,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for MediaType<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "audio" => MediaType::Audio,
+            "video" => MediaType::Video,
+            "application" => MediaType::Application,
+            "text" => MediaType::Text,
+            "message" => MediaType::Message,
+            "image" => MediaType::Image,
+            other => MediaType::Other(other),
+        }
+    }
+}
+
+impl<'a> fmt::Display for MediaType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The well-known transport protocols of m lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransportProtocol<'a> {
+    Udp,
+    RtpAvp,
+    RtpAvpf,
+    RtpSavp,
+    RtpSavpf,
+    UdpTlsRtpSavp,
+    UdpTlsRtpSavpf,
+    TcpDtlsRtpSavpf,
+    UdpDtlsSctp,
+    TcpDtlsSctp,
+    DtlsSctp,
+    Udptl,
+    Other(&'a str),
+}
+
+impl<'a> TransportProtocol<'a> {
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            TransportProtocol::Udp => "udp",
+            TransportProtocol::RtpAvp => "RTP/AVP",
+            TransportProtocol::RtpAvpf => "RTP/AVPF",
+            TransportProtocol::RtpSavp => "RTP/SAVP",
+            TransportProtocol::RtpSavpf => "RTP/SAVPF",
+            TransportProtocol::UdpTlsRtpSavp => "UDP/TLS/RTP/SAVP",
+            TransportProtocol::UdpTlsRtpSavpf => "UDP/TLS/RTP/SAVPF",
+            TransportProtocol::TcpDtlsRtpSavpf => "TCP/DTLS/RTP/SAVPF",
+            TransportProtocol::UdpDtlsSctp => "UDP/DTLS/SCTP",
+            TransportProtocol::TcpDtlsSctp => "TCP/DTLS/SCTP",
+            TransportProtocol::DtlsSctp => "DTLS/SCTP",
+            TransportProtocol::Udptl => "udptl",
+            TransportProtocol::Other(other) => other,
+        }
+    }
+
+    /// The m line lists RTP payload types rather than format tokens
+    pub fn is_rtp(&self) -> bool {
+        self.as_str().contains("RTP")
+    }
+
+    /// The media is secured with DTLS, so it needs a fingerprint
+    pub fn is_dtls(&self) -> bool {
+        self.as_str().contains("TLS")
+    }
+}
+
+impl<'a> From<&'a str> for TransportProtocol<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "udp" => TransportProtocol::Udp,
+            "RTP/AVP" => TransportProtocol::RtpAvp,
+            "RTP/AVPF" => TransportProtocol::RtpAvpf,
+            "RTP/SAVP" => TransportProtocol::RtpSavp,
+            "RTP/SAVPF" => TransportProtocol::RtpSavpf,
+            "UDP/TLS/RTP/SAVP" => TransportProtocol::UdpTlsRtpSavp,
+            "UDP/TLS/RTP/SAVPF" => TransportProtocol::UdpTlsRtpSavpf,
+            "TCP/DTLS/RTP/SAVPF" => TransportProtocol::TcpDtlsRtpSavpf,
+            "UDP/DTLS/SCTP" => TransportProtocol::UdpDtlsSctp,
+            "TCP/DTLS/SCTP" => TransportProtocol::TcpDtlsSctp,
+            "DTLS/SCTP" => TransportProtocol::DtlsSctp,
+            "udptl" => TransportProtocol::Udptl,
+            other => TransportProtocol::Other(other),
+        }
+    }
+}
+
+impl<'a> fmt::Display for TransportProtocol<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl<'a> Media<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let r#type = parse_str(split.next(), 1)?;
-        let port = parse_number::<u64>(split.next(), 2)?;
-        let protocol = parse_str(split.next(), 3)?;
-        let payloads = parse_str(split.next(), 4)?;
-
-        Ok(Self {
+        let r#type = parse_str(split.next(), "media type")?;
+        let mut ports = parse_str(split.next(), "port")?.splitn(2, '/');
+        let port = parse_number::<u16>(ports.next(), "port")?;
+        let port_count = match ports.next() {
+            Some(count) => Some(parse_number::<u16>(Some(count), "port count")?),
+            None => None,
+        };
+        let protocol = parse_str(split.next(), "protocol")?;
+        let mut media = Self {
             r#type,
             port,
+            port_count,
             protocol,
-            payloads,
             ..Default::default()
-        })
+        };
+
+        // RTP based protocols list payload types, others (e.g. data channels)
+        // list protocol specific format tokens
+        let first = parse_str(split.next(), "format")?;
+        let is_rtp = media.transport_protocol().is_rtp();
+
+        for format in core::iter::once(first).chain(split) {
+            if is_rtp {
+                media.payloads.push(parse_payload_type(format)?);
+            } else {
+                media.formats.push(format);
+            }
+        }
+
+        Ok(media)
     }
 
     pub(crate) fn parse_attribute(&mut self, attribute: &'a str, value: &'a str) -> Result<()> {
         match attribute {
-            "ptime" => set_value!(self.ptime, parse_number::<u64>(Some(value), 1)),
-            "rtpmap" => push_value!(self.rtpmap, Rtpmap::new(value)),
+            "ptime" => set_value!(self.ptime, parse_ptime(value, "ptime").map(Some)),
+            "maxptime" => set_value!(self.maxptime, parse_ptime(value, "maxptime").map(Some)),
+            "framerate" => set_value!(self.framerate, parse_framerate(value).map(Some)),
+            "ts-refclk" => push_value!(self.ts_refclk, TsRefclk::new(value)),
+            "mediaclk" => set_value!(self.mediaclk, MediaClock::new(value).map(Some)),
+            "maxprate" => set_value!(self.maxprate, parse_maxprate(value).map(Some)),
+            "quality" => set_value!(self.quality, parse_quality(value).map(Some)),
+            "orient" => set_value!(self.orient, Result::Ok(Some(value))),
+            "cliprect" => set_value!(self.cliprect, Cliprect::new(value).map(Some)),
+            "silenceSupp" => set_value!(self.silence_supp, SilenceSupp::new(value).map(Some)),
+            "rtpmap" => self.parse_rtpmap(value),
             "candidate" => push_value!(self.candidates, Candidate::new(value)),
+            "remote-candidates" => {
+                for remote_candidate in RemoteCandidate::parse_list(value) {
+                    self.remote_candidates.push(remote_candidate?);
+                }
+
+                Ok(())
+            }
             "fmtp" => push_value!(self.fmtp, Fmtp::new(value)),
             "rtcp-fb" => push_value!(self.rtc_fb, RtcpFb::new(value)),
+            "imageattr" => push_value!(self.imageattr, ImageAttr::new(value)),
             "ssrc" => push_value!(self.ssrc, Ssrc::new(value)),
-            "direction" => set_value!(self.direction, Result::Ok(value)),
-            _ => Err(Error::Parse(format!(
-                "Unsupported media attribute: {}",
-                attribute
-            ))),
+            "ssrc-group" => push_value!(self.ssrc_groups, SsrcGroup::new(value)),
+            "ice-ufrag" => set_value!(self.ice_ufrag, Result::Ok(Some(value))),
+            "ice-options" => set_value!(self.ice_options, Result::Ok(ice::parse_options(value))),
+            "ice-pwd" => set_value!(self.ice_pwd, Result::Ok(Some(value))),
+            "fingerprint" => push_value!(self.fingerprints, Fingerprint::new(value)),
+            "setup" => set_value!(self.setup, Result::Ok(Some(value))),
+            "crypto" => push_value!(self.crypto, Crypto::new(value)),
+            "mid" => set_value!(self.mid, Result::Ok(Some(value))),
+            "content" => set_value!(self.content, Result::Ok(Some(value))),
+            "label" => set_value!(self.label, Result::Ok(Some(value))),
+            "control" => set_value!(self.control, Result::Ok(Some(value))),
+            "range" => set_value!(self.range, Result::Ok(Some(value))),
+            "rid" => push_value!(self.rid, Rid::new(value)),
+            "simulcast" => set_value!(self.simulcast, Simulcast::new(value).map(Some)),
+            "rtcp" => set_value!(self.rtcp, Rtcp::new(value).map(Some)),
+            "sctp-port" => set_value!(
+                self.application_mut().sctp_port,
+                parse_number::<u16>(Some(value), "sctp port").map(Some)
+            ),
+            "max-message-size" => set_value!(
+                self.application_mut().max_message_size,
+                parse_number::<u64>(Some(value), "max message size").map(Some)
+            ),
+            "sctpmap" => set_value!(
+                self.application_mut().sctpmap,
+                Sctpmap::new(value).map(Some)
+            ),
+            _ if attribute.starts_with("T38") => {
+                self.t38_mut().parse_attribute(attribute, Some(value))
+            }
+            _ => Err(Error::unknown_attribute("media", attribute)),
+        }
+    }
+
+    // a later rtpmap of a payload type replaces the earlier one, see
+    // DuplicatePolicy
+    fn parse_rtpmap(&mut self, value: &'a str) -> Result<()> {
+        let rtpmap = Rtpmap::new(value)?;
+        self.rtpmap
+            .retain(|earlier| earlier.payload != rtpmap.payload);
+        self.rtpmap.push(rtpmap);
+
+        Ok(())
+    }
+
+    // value-less attributes, e.g. a=sendrecv
+    pub(crate) fn parse_flag(&mut self, flag: &'a str) -> Result<()> {
+        match flag {
+            "sendrecv" | "sendonly" | "recvonly" | "inactive" => {
+                set_value!(self.direction, Direction::new(flag).map(Some))
+            }
+            "end-of-candidates" => set_value!(self.end_of_candidates, Result::Ok(true)),
+            "rtcp-mux" => set_value!(self.rtcp_mux, Result::Ok(true)),
+            "bundle-only" => set_value!(self.bundle_only, Result::Ok(true)),
+            "extmap-allow-mixed" => set_value!(self.extmap_allow_mixed, Result::Ok(true)),
+            _ if flag.starts_with("T38") => self.t38_mut().parse_attribute(flag, None),
+            _ => Err(Error::unknown_attribute("media", flag)),
+        }
+    }
+
+    // the line of the attribute just parsed, for the diagnostics about it
+    pub(crate) fn set_attribute_line(&mut self, attribute: &str, number: usize) {
+        let line = SourceLine(Some(number));
+
+        match attribute {
+            "rtpmap" => self.rtpmap.last_mut().map(|rtpmap| rtpmap.line = line),
+            "fmtp" => self.fmtp.last_mut().map(|fmtp| fmtp.line = line),
+            "ssrc-group" => self.ssrc_groups.last_mut().map(|group| group.line = line),
+            _ => None,
+        };
+    }
+
+    /// The m line was rejected, or the media section removed, by setting its
+    /// port to 0 (RFC 3264 section 6), unless it is a=bundle-only, whose port
+    /// 0 only means it shares the transport of its BUNDLE group
+    pub fn is_rejected(&self) -> bool {
+        self.port == 0 && !self.bundle_only
+    }
+
+    /// The rejected form of this media section: the m line with port 0, its
+    /// formats and its mid, without any other line
+    pub fn rejected(&self) -> Media<'a> {
+        Media {
+            r#type: self.r#type,
+            port: 0,
+            protocol: self.protocol,
+            payloads: self.payloads.clone(),
+            formats: self.formats.clone(),
+            mid: self.mid,
+            line: self.line,
+            ..Default::default()
+        }
+    }
+
+    /// The typed media type of the m line
+    pub fn media_type(&self) -> MediaType<'a> {
+        MediaType::from(self.r#type)
+    }
+
+    /// The typed transport protocol of the m line
+    pub fn transport_protocol(&self) -> TransportProtocol<'a> {
+        TransportProtocol::from(self.protocol)
+    }
+
+    /// The candidates, highest priority first
+    pub fn candidates_sorted(&self) -> Vec<&Candidate<'a>> {
+        let mut candidates = self.candidates.iter().collect::<Vec<_>>();
+        candidates.sort_by_key(|candidate| Reverse(candidate.priority));
+        candidates
+    }
+
+    /// The highest priority candidate of each component, by component
+    pub fn best_candidate_per_component(&self) -> Vec<&Candidate<'a>> {
+        let mut best: Vec<&Candidate<'a>> = vec![];
+
+        for candidate in self.candidates_sorted() {
+            if !best.iter().any(|b| b.component == candidate.component) {
+                best.push(candidate);
+            }
+        }
+
+        best.sort_by_key(|candidate| candidate.component);
+        best
+    }
+
+    /// The rtpmap describing the codec of a payload type listed on the m line
+    pub fn codec_for_payload(&self, payload: u8) -> Option<&Rtpmap<'a>> {
+        self.rtpmap.iter().find(|rtpmap| rtpmap.payload == payload)
+    }
+
+    /// The rtpmap of a payload type, like `codec_for_payload`
+    pub fn rtpmap_for(&self, payload: u8) -> Option<&Rtpmap<'a>> {
+        self.codec_for_payload(payload)
+    }
+
+    /// The format parameters of a payload type listed on the m line
+    pub fn fmtp_for_payload(&self, payload: u8) -> Option<&Fmtp<'a>> {
+        self.fmtp
+            .iter()
+            .find(|fmtp| fmtp.payload_type() == Some(payload))
+    }
+
+    /// The format parameters of a non-RTP format token listed on the m line,
+    /// e.g. webrtc-datachannel
+    pub fn fmtp_for_format(&self, format: &str) -> Option<&Fmtp<'a>> {
+        self.fmtp
+            .iter()
+            .find(|fmtp| fmtp.payload == Format::Token(format))
+    }
+
+    /// The rtcp-fb lines that apply to a payload type, the "*" wildcard ones
+    /// included, in the order of the message.  A feedback given both for all
+    /// payloads and for this one is only listed once.
+    pub fn rtcp_fb_for_payload(&self, payload: u8) -> Vec<&RtcpFb<'a>> {
+        let mut rtc_fbs: Vec<&RtcpFb<'a>> = vec![];
+
+        for rtc_fb in self
+            .rtc_fb
+            .iter()
+            .filter(|rtc_fb| rtc_fb.applies_to(payload))
+        {
+            if !rtc_fbs
+                .iter()
+                .any(|other| other.r#type == rtc_fb.r#type && other.parameter == rtc_fb.parameter)
+            {
+                rtc_fbs.push(rtc_fb);
+            }
         }
+
+        rtc_fbs
+    }
+
+    /// Whether the receiver of a payload type sends transport-wide congestion
+    /// control feedback, a=rtcp-fb:96 transport-cc
+    pub fn supports_transport_cc(&self, payload: u8) -> bool {
+        self.supports_feedback(payload, RtcpFbType::TransportCc)
+    }
+
+    /// Whether the receiver of a payload type sends receiver estimated maximum
+    /// bitrate messages, a=rtcp-fb:96 goog-remb
+    pub fn supports_remb(&self, payload: u8) -> bool {
+        self.supports_feedback(payload, RtcpFbType::GoogRemb)
+    }
+
+    fn supports_feedback(&self, payload: u8, feedback_type: RtcpFbType) -> bool {
+        self.rtc_fb
+            .iter()
+            .any(|rtc_fb| rtc_fb.applies_to(payload) && rtc_fb.feedback_type() == feedback_type)
+    }
+
+    /// Removes a codec, and its retransmission payloads, from the m line along
+    /// with their rtpmap, fmtp and rtcp-fb lines.  Codec names are case-insensitive.
+    pub fn remove_codec(&mut self, codec: &str) {
+        self.retain_codecs(|name| !name.eq_ignore_ascii_case(codec));
+    }
+
+    /// Removes every codec but the given ones, keeping their retransmission
+    /// payloads
+    pub fn keep_only_codecs(&mut self, codecs: &[&str]) {
+        self.retain_codecs(|name| codecs.iter().any(|codec| codec.eq_ignore_ascii_case(name)));
+    }
+
+    /// Replaces the format parameters of the opus payloads, e.g.
+    /// "minptime=10;useinbandfec=1;stereo=1"
+    pub fn set_opus_params(&mut self, config: &'a str) {
+        let opus = self
+            .rtpmap
+            .iter()
+            .filter(|rtpmap| rtpmap.codec.eq_ignore_ascii_case("opus"))
+            .map(|rtpmap| rtpmap.payload)
+            .collect::<Vec<_>>();
+
+        for payload in opus {
+            match self
+                .fmtp
+                .iter_mut()
+                .find(|fmtp| fmtp.payload_type() == Some(payload))
+            {
+                Some(fmtp) => fmtp.config = config.into(),
+                None => self.fmtp.push(Fmtp {
+                    config: config.into(),
+                    payload: Format::Payload(payload),
+                    line: SourceLine::default(),
+                }),
+            }
+        }
+    }
+
+    /// The bitrates Chrome's encoder keeps to for a payload type, from the
+    /// x-google parameters of its fmtp line
+    pub fn google_bitrate_hints(&self, payload: u8) -> GoogleBitrateHints {
+        self.fmtp_for_payload(payload)
+            .map(GoogleBitrateHints::new)
+            .unwrap_or_default()
+    }
+
+    /// Writes the x-google bitrate parameters of a payload type, adding its
+    /// fmtp line if needed, and removing the parameters that are None
+    pub fn set_google_bitrate_hints(&mut self, payload: u8, hints: GoogleBitrateHints) {
+        bitrate::set_hints(self, payload, hints)
+    }
+
+    /// Renames a payload type on the m line and in the rtpmap, fmtp, rtcp-fb and
+    /// imageattr lines, in the apt parameter of its retransmission payload and
+    /// in the pt= restriction of the rids.  The new payload type must be free in
+    /// this section.
+    pub fn remap_payload(&mut self, old: u8, new: u8) -> Result<()> {
+        payload::check_remap(self, old, new)?;
+        payload::remap_media(self, old, new);
+        Ok(())
+    }
+
+    /// Moves the payloads of the given codecs, in order of preference, to the
+    /// front of the m line.  Retransmission payloads follow their codec.
+    pub fn reorder_payloads(&mut self, codecs: &[&str]) {
+        let rank = |media: &Media, payload: u8| {
+            let payload = media.rtx_target(payload).unwrap_or(payload);
+            let codec = media
+                .codec_for_payload(payload)
+                .map_or("", |rtpmap| rtpmap.codec);
+
+            codecs
+                .iter()
+                .position(|preferred| preferred.eq_ignore_ascii_case(codec))
+                .unwrap_or(codecs.len())
+        };
+
+        let mut payloads = self.payloads.clone();
+        payloads.sort_by_key(|payload| rank(self, *payload));
+        self.payloads = payloads;
+
+        // keep the lines in the same order as the m line
+        let position = |payloads: &[u8], payload: u8| {
+            payloads
+                .iter()
+                .position(|listed| *listed == payload)
+                .unwrap_or(payloads.len())
+        };
+        let payloads = &self.payloads;
+
+        self.rtpmap
+            .sort_by_key(|rtpmap| position(payloads, rtpmap.payload));
+        self.fmtp.sort_by_key(|fmtp| match fmtp.payload_type() {
+            Some(payload) => position(payloads, payload),
+            None => 0,
+        });
+        self.rtc_fb
+            .sort_by_key(|rtc_fb| match rtc_fb.payload_type() {
+                Some(payload) => position(payloads, payload),
+                None => 0,
+            });
+    }
+
+    /// The ssrc lines grouped by source, in order of first appearance
+    pub fn ssrc_info(&self) -> Vec<SsrcInfo<'a>> {
+        let mut sources: Vec<SsrcInfo<'a>> = vec![];
+
+        for ssrc in &self.ssrc {
+            let index = match sources.iter().position(|info| info.id == ssrc.id) {
+                Some(index) => index,
+                None => {
+                    sources.push(SsrcInfo {
+                        id: ssrc.id,
+                        ..Default::default()
+                    });
+                    sources.len() - 1
+                }
+            };
+            let info = &mut sources[index];
+
+            match (ssrc.attribute, ssrc.value) {
+                ("cname", Some(value)) => info.cname = Some(value),
+                ("msid", Some(value)) => info.msid = Some(value),
+                ("mslabel", Some(value)) => info.mslabel = Some(value),
+                ("label", Some(value)) => info.label = Some(value),
+                (attribute, value) => info.other.push((attribute, value)),
+            }
+        }
+
+        sources
+    }
+
+    /// The (primary, retransmission) ssrc pairs of the FID ssrc groups
+    pub fn rtx_pairs(&self) -> Vec<(u32, u32)> {
+        self.ssrc_pairs("FID")
+    }
+
+    /// The (primary, forward error correction) ssrc pairs of the FEC-FR ssrc
+    /// groups
+    pub fn fec_pairs(&self) -> Vec<(u32, u32)> {
+        self.ssrc_pairs("FEC-FR")
+    }
+
+    /// The (primary, retransmission) payload type pairs, from the apt
+    /// parameter of the rtx payloads
+    pub fn rtx_payloads(&self) -> Vec<(u8, u8)> {
+        self.payloads
+            .iter()
+            .filter_map(|&payload| Some((self.rtx_target(payload)?, payload)))
+            .collect()
+    }
+
+    fn ssrc_pairs(&self, semantics: &str) -> Vec<(u32, u32)> {
+        self.ssrc_groups
+            .iter()
+            .filter(|group| group.semantics == semantics)
+            .filter_map(|group| match group.ssrcs.as_slice() {
+                [primary, secondary] => Some((*primary, *secondary)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // keep the payloads whose codec matches, plus the retransmission payloads of
+    // the ones kept
+    fn retain_codecs<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        let codec = |payload: u8| {
+            self.codec_for_payload(payload)
+                .map_or("", |rtpmap| rtpmap.codec)
+        };
+        let primary = self
+            .payloads
+            .iter()
+            .cloned()
+            .filter(|payload| self.rtx_target(*payload).is_none() && keep(codec(*payload)))
+            .collect::<Vec<_>>();
+        let kept = self
+            .payloads
+            .iter()
+            .cloned()
+            .filter(|payload| match self.rtx_target(*payload) {
+                Some(target) => primary.contains(&target),
+                None => primary.contains(payload),
+            })
+            .collect::<Vec<_>>();
+        let is_kept = |payload: u8| kept.contains(&payload);
+
+        self.payloads.retain(|payload| kept.contains(payload));
+        self.rtpmap.retain(|rtpmap| is_kept(rtpmap.payload));
+        self.fmtp
+            .retain(|fmtp| fmtp.payload_type().is_none_or(is_kept));
+        self.rtc_fb
+            .retain(|rtc_fb| rtc_fb.payload_type().is_none_or(is_kept));
+    }
+
+    // the payload type a retransmission payload repairs, from its apt parameter
+    fn rtx_target(&self, payload: u8) -> Option<u8> {
+        self.codec_for_payload(payload)
+            .filter(|rtpmap| rtpmap.codec.eq_ignore_ascii_case("rtx"))?;
+
+        self.fmtp_for_payload(payload)?.apt()
+    }
+
+    // data channel attributes are collected on demand
+    fn application_mut(&mut self) -> &mut Application<'a> {
+        self.application.get_or_insert_with(Application::default)
+    }
+
+    fn t38_mut(&mut self) -> &mut T38<'a> {
+        self.t38.get_or_insert_with(T38::default)
+    }
+
+    /// The bandwidth of a b line of this media section, e.g. b=TIAS
+    pub fn bandwidth_for(&self, r#type: BandwidthType) -> Option<u64> {
+        bandwidth::find(&self.bandwidth, r#type)
+    }
+
+    /// The TIAS bandwidth of this media section with the headers of its
+    /// a=maxprate packets of `overhead` bytes, e.g. 40 for IPv4, UDP and RTP,
+    /// in bits per second (RFC 3890 section 6.4)
+    pub fn transport_bandwidth(&self, overhead: u32) -> Option<u64> {
+        bandwidth::transport(&self.bandwidth, self.maxprate, overhead)
+    }
+
+    /// The URL of the RTSP requests for this media section: its a=control
+    /// made absolute against the session-level one, or against `base`, the
+    /// Content-Base of the DESCRIBE response, when that is * or missing
+    pub fn control_url(&self, sdp: &Sdp<'a>, base: &str) -> Option<String> {
+        rtsp::control_url(base, sdp.control, self.control?)
+    }
+
+    /// The ICE username fragment of this media section, falling back to the
+    /// session-level value
+    pub fn effective_ice_ufrag(&self, sdp: &Sdp<'a>) -> Option<&'a str> {
+        self.ice_ufrag.or(sdp.ice_ufrag)
+    }
+
+    /// The ICE password of this media section, falling back to the
+    /// session-level value
+    pub fn effective_ice_pwd(&self, sdp: &Sdp<'a>) -> Option<&'a str> {
+        self.ice_pwd.or(sdp.ice_pwd)
+    }
+
+    /// The ICE options of this media section, falling back to the
+    /// session-level ones
+    pub fn effective_ice_options(&self, sdp: &Sdp<'a>) -> Vec<IceOption<'a>> {
+        let options = match self.ice_options.is_empty() {
+            true => &sdp.ice_options,
+            false => &self.ice_options,
+        };
+
+        options
+            .iter()
+            .map(|&option| IceOption::from(option))
+            .collect()
+    }
+
+    /// The direction of this media section, falling back to the session-level
+    /// value and then to sendrecv
+    pub fn effective_direction(&self, sdp: &Sdp<'a>) -> Direction {
+        self.direction.or(sdp.direction).unwrap_or_default()
+    }
+
+    /// The reference clocks of this media section, falling back to the
+    /// session-level ones
+    pub fn effective_ts_refclk<'b>(&'b self, sdp: &'b Sdp<'a>) -> &'b [TsRefclk<'a>] {
+        if self.ts_refclk.is_empty() {
+            &sdp.ts_refclk
+        } else {
+            &self.ts_refclk
+        }
+    }
+
+    /// The DTLS fingerprints of this media section, falling back to the
+    /// session-level values
+    pub fn effective_fingerprints<'b>(&'b self, sdp: &'b Sdp<'a>) -> &'b [Fingerprint<'a>] {
+        if self.fingerprints.is_empty() {
+            &sdp.fingerprints
+        } else {
+            &self.fingerprints
+        }
+    }
+
+    /// The DTLS setup role of this media section, falling back to the
+    /// session-level value
+    pub fn effective_setup(&self, sdp: &Sdp<'a>) -> Option<&'a str> {
+        self.setup.or(sdp.setup)
+    }
+
+    /// The connection of this media section, falling back to the
+    /// session-level value
+    pub fn effective_connection<'b>(&'b self, sdp: &'b Sdp<'a>) -> Option<&'b Connection<'a>> {
+        self.connection.as_ref().or(sdp.connection.as_ref())
+    }
+}
+
+// RTP payload types must fit in 0..=127 (RFC 3551)
+fn parse_payload_type(value: &str) -> Result<u8> {
+    parse_number::<u8>(Some(value), "payload type")
+        .ok()
+        .filter(|payload| *payload <= 127)
+        .ok_or_else(|| Error::InvalidNumber {
+            field: "payload type",
+            value: value.into(),
+        })
+}
+
+// milliseconds, fractional for e.g. the 0.125 of AES67 streams, or written
+// 20.0 by some gateways
+fn parse_ptime(value: &str, field: &'static str) -> Result<f32> {
+    let invalid = || Error::InvalidNumber {
+        field,
+        value: value.into(),
+    };
+
+    if value.ends_with('.') {
+        return Err(invalid());
+    }
+
+    match parse_number::<f32>(Some(value), field)? {
+        ptime if ptime.is_finite() && ptime >= 0.0 => Ok(ptime),
+        _ => Err(invalid()),
+    }
+}
+
+// frames per second, fractional for e.g. NTSC 29.97
+fn parse_framerate(value: &str) -> Result<f32> {
+    let framerate = parse_number::<f32>(Some(value), "framerate")?;
+
+    match framerate.is_finite() && framerate > 0.0 {
+        true => Ok(framerate),
+        false => Err(Error::InvalidNumber {
+            field: "framerate",
+            value: value.into(),
+        }),
+    }
+}
+
+// 0 (worst) to 10 (best)
+fn parse_quality(value: &str) -> Result<u8> {
+    match parse_number::<u8>(Some(value), "quality")? {
+        quality @ 0..=10 => Ok(quality),
+        _ => Err(Error::invalid_value("quality", "must be between 0 and 10")),
+    }
+}
+
+/// Writes the whole media section: the m line followed by its own lines
+impl<'a> fmt::Display for Media<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_with(f, Profile::default())
+    }
+}
+
+impl<'a> Media<'a> {
+    // the m line, then the other lines in the order of the profile
+    pub(crate) fn write_with(&self, f: &mut fmt::Formatter, profile: Profile) -> fmt::Result {
+        write!(f, "m={} {}", self.r#type, self.port)?;
+
+        if let Some(port_count) = self.port_count {
+            write!(f, "/{}", port_count)?;
+        }
+
+        write!(f, " {}", self.protocol)?;
+
+        for payload in &self.payloads {
+            write!(f, " {}", payload)?;
+        }
+
+        for format in &self.formats {
+            write!(f, " {}", format)?;
+        }
+
+        f.write_str("\r\n")?;
+
+        for lines in profile.media_order() {
+            self.write_lines(f, *lines)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_lines(&self, f: &mut fmt::Formatter, lines: MediaLines) -> fmt::Result {
+        match lines {
+            MediaLines::Info => {
+                if let Some(information) = self.information {
+                    write_line(f, 'i', information)?;
+                }
+
+                if let Some(connection) = &self.connection {
+                    write_line(f, 'c', connection)?;
+                }
+
+                for bandwidth in &self.bandwidth {
+                    write_line(f, 'b', bandwidth)?;
+                }
+
+                if let Some(encryption_key) = self.encryption_key {
+                    write_line(f, 'k', encryption_key)?;
+                }
+            }
+            MediaLines::Ice => {
+                if let Some(ice_ufrag) = self.ice_ufrag {
+                    write_attribute(f, "ice-ufrag", ice_ufrag)?;
+                }
+
+                if let Some(ice_pwd) = self.ice_pwd {
+                    write_attribute(f, "ice-pwd", ice_pwd)?;
+                }
+
+                if !self.ice_options.is_empty() {
+                    write_attribute(f, "ice-options", self.ice_options.join(" "))?;
+                }
+            }
+            MediaLines::Dtls => {
+                for fingerprint in &self.fingerprints {
+                    write_attribute(f, "fingerprint", fingerprint)?;
+                }
+
+                if let Some(setup) = self.setup {
+                    write_attribute(f, "setup", setup)?;
+                }
+            }
+            MediaLines::Identification => {
+                if let Some(mid) = self.mid {
+                    write_attribute(f, "mid", mid)?;
+                }
+
+                if self.bundle_only {
+                    write_flag(f, "bundle-only")?;
+                }
+
+                if let Some(content) = self.content {
+                    write_attribute(f, "content", content)?;
+                }
+
+                if let Some(label) = self.label {
+                    write_attribute(f, "label", label)?;
+                }
+
+                if let Some(control) = self.control {
+                    write_attribute(f, "control", control)?;
+                }
+
+                if let Some(range) = self.range {
+                    write_attribute(f, "range", range)?;
+                }
+            }
+            MediaLines::Crypto => {
+                for crypto in &self.crypto {
+                    write_attribute(f, "crypto", crypto)?;
+                }
+            }
+            MediaLines::Direction => {
+                if self.extmap_allow_mixed {
+                    write_flag(f, "extmap-allow-mixed")?;
+                }
+
+                if let Some(direction) = self.direction {
+                    write_flag(f, direction.as_str())?;
+                }
+            }
+            MediaLines::Rtcp => {
+                if let Some(rtcp) = &self.rtcp {
+                    write_attribute(f, "rtcp", rtcp)?;
+                }
+            }
+            MediaLines::RtcpMux => {
+                if self.rtcp_mux {
+                    write_flag(f, "rtcp-mux")?;
+                }
+            }
+            MediaLines::Codecs => {
+                for rtpmap in &self.rtpmap {
+                    write_attribute(f, "rtpmap", rtpmap)?;
+                }
+
+                for fmtp in &self.fmtp {
+                    write_attribute(f, "fmtp", fmtp)?;
+                }
+
+                for rtc_fb in &self.rtc_fb {
+                    write_attribute(f, "rtcp-fb", rtc_fb)?;
+                }
+            }
+            MediaLines::CodecsByPayload => self.write_codecs_by_payload(f)?,
+            MediaLines::Format => {
+                for imageattr in &self.imageattr {
+                    write_attribute(f, "imageattr", imageattr)?;
+                }
+
+                if let Some(ptime) = self.ptime {
+                    write_attribute(f, "ptime", ptime)?;
+                }
+
+                if let Some(maxptime) = self.maxptime {
+                    write_attribute(f, "maxptime", maxptime)?;
+                }
+
+                if let Some(framerate) = self.framerate {
+                    write_attribute(f, "framerate", framerate)?;
+                }
+
+                for ts_refclk in &self.ts_refclk {
+                    write_attribute(f, "ts-refclk", ts_refclk)?;
+                }
+
+                if let Some(mediaclk) = &self.mediaclk {
+                    write_attribute(f, "mediaclk", mediaclk)?;
+                }
+
+                if let Some(maxprate) = self.maxprate {
+                    write_attribute(f, "maxprate", maxprate)?;
+                }
+
+                if let Some(quality) = self.quality {
+                    write_attribute(f, "quality", quality)?;
+                }
+
+                if let Some(orient) = self.orient {
+                    write_attribute(f, "orient", orient)?;
+                }
+
+                if let Some(cliprect) = &self.cliprect {
+                    write_attribute(f, "cliprect", cliprect)?;
+                }
+
+                if let Some(silence_supp) = &self.silence_supp {
+                    write_attribute(f, "silenceSupp", silence_supp)?;
+                }
+            }
+            MediaLines::Candidates => {
+                for candidate in &self.candidates {
+                    write_attribute(f, "candidate", candidate)?;
+                }
+
+                if self.end_of_candidates {
+                    write_flag(f, "end-of-candidates")?;
+                }
+
+                if !self.remote_candidates.is_empty() {
+                    let remote_candidates = self.remote_candidates.iter().map(|c| c.to_string());
+                    write_attribute(
+                        f,
+                        "remote-candidates",
+                        remote_candidates.collect::<Vec<_>>().join(" "),
+                    )?;
+                }
+            }
+            MediaLines::Ssrc => {
+                for ssrc_group in &self.ssrc_groups {
+                    write_attribute(f, "ssrc-group", ssrc_group)?;
+                }
+
+                for ssrc in &self.ssrc {
+                    write_attribute(f, "ssrc", ssrc)?;
+                }
+            }
+            MediaLines::Simulcast => {
+                for rid in &self.rid {
+                    write_attribute(f, "rid", rid)?;
+                }
+
+                if let Some(simulcast) = &self.simulcast {
+                    write_attribute(f, "simulcast", simulcast)?;
+                }
+            }
+            MediaLines::Application => {
+                if let Some(application) = &self.application {
+                    if let Some(sctp_port) = application.sctp_port {
+                        write_attribute(f, "sctp-port", sctp_port)?;
+                    }
+
+                    if let Some(max_message_size) = application.max_message_size {
+                        write_attribute(f, "max-message-size", max_message_size)?;
+                    }
+
+                    if let Some(sctpmap) = &application.sctpmap {
+                        write_attribute(f, "sctpmap", sctpmap)?;
+                    }
+                }
+
+                if let Some(t38) = &self.t38 {
+                    write!(f, "{}", t38)?;
+                }
+            }
+            MediaLines::Extensions => {
+                for extension in &self.extensions {
+                    write_line(f, 'a', extension)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // the rtpmap, rtcp-fb and fmtp lines of each payload type of the m line,
+    // then those of no payload type listed on it, e.g. a=rtcp-fb:* nack
+    fn write_codecs_by_payload(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let listed = |payload: Option<u8>| matches!(payload, Some(p) if self.payloads.contains(&p));
+
+        for &payload in &self.payloads {
+            for rtpmap in self.rtpmap.iter().filter(|r| r.payload == payload) {
+                write_attribute(f, "rtpmap", rtpmap)?;
+            }
+
+            for rtc_fb in self
+                .rtc_fb
+                .iter()
+                .filter(|r| r.payload_type() == Some(payload))
+            {
+                write_attribute(f, "rtcp-fb", rtc_fb)?;
+            }
+
+            for fmtp in self
+                .fmtp
+                .iter()
+                .filter(|r| r.payload_type() == Some(payload))
+            {
+                write_attribute(f, "fmtp", fmtp)?;
+            }
+        }
+
+        for rtpmap in self.rtpmap.iter().filter(|r| !listed(Some(r.payload))) {
+            write_attribute(f, "rtpmap", rtpmap)?;
+        }
+
+        for rtc_fb in self.rtc_fb.iter().filter(|r| !listed(r.payload_type())) {
+            write_attribute(f, "rtcp-fb", rtc_fb)?;
+        }
+
+        for fmtp in self.fmtp.iter().filter(|r| !listed(r.payload_type())) {
+            write_attribute(f, "fmtp", fmtp)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -89,42 +1242,233 @@ impl<'a> Media<'a> {
 /// that priority of host candidates is the higher than other candidates as using host
 /// candidates are more efficient in terms of use of resources. The first lines
 /// (component= 1) is for RTP and second line (component = 2) is for RTCP.
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// Other media may have up to 256 components (RFC 8445 section 5.1.2.1).
+///
+/// The pairs after the type, e.g. raddr and rport for the base of a srflx or
+/// relay candidate, or the generation and network-id of browsers, are kept in
+/// order.  Parse the `candidate` string of a RTCIceCandidate with
+/// `Candidate::try_from`, and write one with `to_attribute_string`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Candidate<'a> {
-    pub component: u64,
     pub foundation: &'a str,
+    pub component: u16,
     pub transport: &'a str,
-    pub priority: u64,
+    pub priority: u32,
     pub ip: &'a str,
-    pub port: u64,
+    pub port: u16,
     pub r#type: &'a str,
+
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<(&'a str, &'a str)>,
 }
 
 impl<'a> Candidate<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let component = parse_number::<u64>(split.next(), 1)?;
-        let foundation = parse_str(split.next(), 2)?;
-        let transport = parse_str(split.next(), 3)?;
-        let priority = parse_number::<u64>(split.next(), 4)?;
-        let ip = parse_str(split.next(), 5)?;
-        let port = parse_number::<u64>(split.next(), 6)?;
+        let foundation = parse_str(split.next(), "foundation")?;
+        let component = parse_number::<u16>(split.next(), "component")?;
+        let transport = parse_str(split.next(), "transport")?;
+        let priority = parse_number::<u32>(split.next(), "priority")?;
+        let ip = parse_str(split.next(), "ip")?;
+        let port = parse_number::<u16>(split.next(), "port")?;
+        let typ = parse_str(split.next(), "typ")?;
+        let r#type = parse_str(split.next(), "candidate type")?;
+        let mut extensions = vec![];
 
-        // skip typ
-        split.next();
+        while let Some(name) = split.next().filter(|name| !name.is_empty()) {
+            let value = parse_str(split.next(), "candidate extension value")?;
+            extensions.push((name, value));
+        }
+
+        check_component(component)?;
 
-        let r#type = parse_str(split.next(), 7)?;
+        if typ != "typ" {
+            return Err(Error::invalid_value(
+                "typ",
+                format!("expected 'typ', found '{}'", typ),
+            ));
+        }
 
         Ok(Self {
-            component,
             foundation,
+            component,
             transport,
             priority,
             ip,
             port,
             r#type,
+            extensions,
         })
     }
+
+    /// The value of an extension, e.g. raddr
+    pub fn extension(&self, name: &str) -> Option<&'a str> {
+        self.extensions
+            .iter()
+            .find(|(extension, _)| *extension == name)
+            .map(|&(_, value)| value)
+    }
+
+    /// The candidate as a RTCIceCandidate writes it, e.g.
+    /// candidate:1 1 udp 2122260223 192.0.2.1 46243 typ host
+    pub fn to_attribute_string(&self) -> String {
+        format!("candidate:{}", self)
+    }
+
+    /// The priority of a candidate (RFC 8445 section 5.1.2.1), from the
+    /// preference for its type (0-126, e.g. 126 for host), the local
+    /// preference among candidates of that type and its component (1-256)
+    pub fn compute_priority(type_pref: u8, local_pref: u16, component: u16) -> Result<u32> {
+        check_component(component)?;
+
+        if type_pref > 126 {
+            return Err(Error::invalid_value(
+                "type preference",
+                format!("'{}', expected 0 to 126", type_pref),
+            ));
+        }
+
+        Ok((u32::from(type_pref) << 24)
+            + (u32::from(local_pref) << 8)
+            + (256 - u32::from(component)))
+    }
+
+    /// The address of the candidate, an IP or an mDNS hostname
+    pub fn addr(&self) -> Result<Address<'a>> {
+        Address::new(self.ip)
+    }
+
+    /// The socket the candidate receives on, for an IP address; an mDNS
+    /// hostname has to be resolved first
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        match self.addr()? {
+            Address::Ip(ip) => Ok(SocketAddr::new(ip, self.port)),
+            Address::Mdns(hostname) => Err(Error::invalid_value(
+                "ip address",
+                format!("'{}' is an mDNS hostname", hostname),
+            )),
+        }
+    }
+
+    /// Whether the address of the candidate is an IPv6 address
+    pub fn is_ipv6(&self) -> bool {
+        matches!(self.addr(), Ok(Address::Ip(ip)) if ip.is_ipv6())
+    }
+
+    /// Whether the address of the candidate is an mDNS hostname
+    pub fn is_mdns(&self) -> bool {
+        matches!(self.addr(), Ok(Address::Mdns(_)))
+    }
+
+    /// Whether the candidate is an address of a local interface
+    pub fn is_host(&self) -> bool {
+        self.r#type == "host"
+    }
+
+    /// Whether the candidate is the address a STUN server saw the agent at
+    pub fn is_srflx(&self) -> bool {
+        self.r#type == "srflx"
+    }
+
+    /// Whether the candidate is the address the peer saw a check come from
+    pub fn is_prflx(&self) -> bool {
+        self.r#type == "prflx"
+    }
+
+    /// Whether the candidate is an address allocated on a TURN server
+    pub fn is_relay(&self) -> bool {
+        self.r#type == "relay"
+    }
+}
+
+// the components of a candidate are numbered from 1 to 256
+fn check_component(component: u16) -> Result<()> {
+    match component {
+        1..=256 => Ok(()),
+        _ => Err(Error::invalid_value(
+            "component",
+            format!("'{}', expected 1 to 256", component),
+        )),
+    }
+}
+
+impl<'a> fmt::Display for Candidate<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} typ {}",
+            self.foundation,
+            self.component,
+            self.transport,
+            self.priority,
+            self.ip,
+            self.port,
+            self.r#type
+        )?;
+
+        for (name, value) in &self.extensions {
+            write!(f, " {} {}", name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+// a candidate gathered by an ICE agent or trickled by a browser, with or
+// without the a= and candidate: prefixes; FromStr can't borrow the string
+impl<'a> TryFrom<&'a str> for Candidate<'a> {
+    type Error = Error;
+
+    fn try_from(value: &'a str) -> Result<Self> {
+        let value = value.trim();
+        let value = value.strip_prefix("a=").unwrap_or(value);
+
+        Candidate::new(value.strip_prefix("candidate:").unwrap_or(value))
+    }
+}
+
+/// Remote Candidate
+///
+/// a=remote-candidates:1 192.0.2.3 45664 2 192.0.2.3 45665
+///
+/// The controlling agent lists, for each component, the remote candidate ICE
+/// selected once the checks completed (RFC 5245), so that the answerer uses the
+/// same pair even if its own checks picked another one.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RemoteCandidate<'a> {
+    pub component: u8,
+    pub address: &'a str,
+    pub port: u16,
+}
+
+impl<'a> RemoteCandidate<'a> {
+    // one line lists a (component, address, port) triple per component
+    pub fn parse_list(value: &'a str) -> impl Iterator<Item = Result<Self>> + 'a {
+        let mut split = value
+            .split(' ')
+            .filter(|token| !token.is_empty())
+            .peekable();
+
+        core::iter::from_fn(move || {
+            split.peek()?;
+
+            Some(Self::parse(&mut split))
+        })
+    }
+
+    fn parse(split: &mut impl Iterator<Item = &'a str>) -> Result<Self> {
+        Ok(Self {
+            component: parse_number::<u8>(split.next(), "remote candidate component")?,
+            address: parse_str(split.next(), "remote candidate address")?,
+            port: parse_number::<u16>(split.next(), "remote candidate port")?,
+        })
+    }
+}
+
+impl<'a> fmt::Display for RemoteCandidate<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.component, self.address, self.port)
+    }
 }
 
 /// FMTP
@@ -136,19 +1480,109 @@ impl<'a> Candidate<'a> {
 /// time (ptime: the number of miliseconds of audio transported by a single packet).
 /// useinbandfec=1 specifies that the decoder has the capability to take advantage of
 /// the Opus in-band FEC (Forward Error Correction). For more info check RFC7587.
-#[derive(Debug, Default, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Fmtp<'a> {
-    pub config: &'a str,
-    pub payload: u64,
+    /// The parameters, owned once rewritten, e.g. by `Sdp::remap_payload`
+    #[serde(borrow)]
+    pub config: Cow<'a, str>,
+
+    #[serde(borrow)]
+    pub payload: Format<'a>,
+
+    #[serde(skip)]
+    pub line: SourceLine,
+}
+
+/// The format of the m line an fmtp, rtcp-fb or imageattr line applies to: a
+/// RTP payload type, or a token, e.g. the format of another protocol such as
+/// webrtc-datachannel, or the "*" wildcard of the rtcp-fb and imageattr lines
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum Format<'a> {
+    Payload(u8),
+    Token(&'a str),
 }
 
 impl<'a> Fmtp<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.splitn(2, ' ');
-        let payload = parse_number::<u64>(split.next(), 1)?;
-        let config = parse_str(split.next(), 2)?;
+        let payload = Format::new(parse_str(split.next(), "payload type")?)?;
+        let config = parse_str(split.next(), "config")?.into();
 
-        Ok(Self { payload, config })
+        Ok(Self {
+            payload,
+            config,
+            line: SourceLine::default(),
+        })
+    }
+
+    /// The payload type this line applies to, None for a non-RTP format
+    pub fn payload_type(&self) -> Option<u8> {
+        match self.payload {
+            Format::Payload(payload) => Some(payload),
+            Format::Token(_) => None,
+        }
+    }
+
+    /// The value of a parameter, e.g. 1 for useinbandfec in
+    /// "minptime=10;useinbandfec=1"
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.config
+            .split(';')
+            .filter_map(|parameter| parameter.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Sets a parameter, in its place if the line already has it, or removes
+    /// it when the value is None
+    pub fn set_parameter(&mut self, name: &str, value: Option<impl fmt::Display>) {
+        let mut value = value.map(|value| format!("{}={}", name, value));
+        let mut parameters = vec![];
+
+        for parameter in self.config.split(';').filter(|p| !p.trim().is_empty()) {
+            match parameter.trim().split('=').next() == Some(name) {
+                true => parameters.extend(value.take()),
+                false => parameters.push(parameter.to_string()),
+            }
+        }
+
+        parameters.extend(value);
+        self.config = parameters.join(";").into();
+    }
+
+    /// The payload type a retransmission (rtx) payload repairs, from its
+    /// apt parameter, e.g. 96 in "apt=96"
+    pub fn apt(&self) -> Option<u8> {
+        self.config
+            .split(';')
+            .filter_map(|parameter| parameter.trim().strip_prefix("apt="))
+            .find_map(|apt| apt.parse().ok())
+    }
+}
+
+impl<'a> Format<'a> {
+    // numeric formats are payload types
+    pub fn new(value: &'a str) -> Result<Self> {
+        match value.bytes().all(|b| b.is_ascii_digit()) {
+            true => parse_payload_type(value).map(Format::Payload),
+            false => Ok(Format::Token(value)),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Fmtp<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.payload, self.config)
+    }
+}
+
+impl<'a> fmt::Display for Format<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Payload(payload) => write!(f, "{}", payload),
+            Format::Token(token) => write!(f, "{}", token),
+        }
     }
 }
 
@@ -160,50 +1594,185 @@ impl<'a> Fmtp<'a> {
 /// bit rate (6kbps-510kbps) and is not under any royalty so it can be freely
 /// implemented in any browser (unlike other codecs like as G.729). Opus
 /// support is starting to become common and it has become critical for most
-/// WebRTC applications.
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// WebRTC applications. The optional trailing parameter is the number of
+/// audio channels.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Rtpmap<'a> {
     pub codec: &'a str,
-    pub payload: &'a str,
-    pub rate: u64,
+    pub payload: u8,
+    pub rate: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channels: Option<u8>,
+
+    #[serde(skip)]
+    pub line: SourceLine,
 }
 
 impl<'a> Rtpmap<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let payload = parse_str(split.next(), 1)?;
+        let payload = parse_payload_type(parse_str(split.next(), "payload type")?)?;
+
+        let mut split = parse_str(split.next(), "encoding")?.split('/');
+        let codec = parse_str(split.next(), "codec")?;
+        let rate = match split.next() {
+            Some(rate) => Some(parse_number::<u32>(Some(rate), "clock rate")?),
+            None => None,
+        };
+        let channels = match split.next() {
+            Some(channels) => Some(parse_number::<u8>(Some(channels), "channels")?),
+            None => None,
+        };
+
+        Ok(Self {
+            codec,
+            payload,
+            rate,
+            channels,
+            line: SourceLine::default(),
+        })
+    }
+}
+
+impl<'a> fmt::Display for Rtpmap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.payload, self.codec)?;
+
+        if let Some(rate) = self.rate {
+            write!(f, "/{}", rate)?;
 
-        let mut split = parse_str(split.next(), 2)?.split('/');
-        let codec = parse_str(split.next(), 2)?;
-        let rate = parse_number::<u64>(split.next(), 3)?;
+            if let Some(channels) = self.channels {
+                write!(f, "/{}", channels)?;
+            }
+        }
 
-        Ok(Self {
-            codec,
-            payload,
-            rate,
-        })
+        Ok(())
     }
 }
 
 /// RTCP FB
 ///
 /// a=rtcp-fb:100 nack
+/// a=rtcp-fb:100 nack pli
+///
+/// a=rtcp-fb:100 trr-int 100
+/// a=rtcp-fb:100 ack app foo
 ///
 /// This line requests the use of Negative ACKs (nack) as indicated in RFC 4585.
-/// This allows to make the other end aware about packet losses.
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// This allows to make the other end aware about packet losses. Anything after
+/// the feedback type is kept as its parameter, e.g. pli in "nack pli" or fir in
+/// "ccm fir".  A trr-int line must give its interval in milliseconds and an ack
+/// line its subtype, rpsi or app, which app may follow with its parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct RtcpFb<'a> {
-    pub payload: &'a str,
+    /// A payload type, or the "*" wildcard
+    #[serde(borrow)]
+    pub payload: Format<'a>,
+
     pub r#type: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<&'a str>,
+}
+
+/// The well-known RTCP feedback types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RtcpFbType<'a> {
+    Ack,
+    Nack,
+    TrrInt,
+    Ccm,
+    GoogRemb,
+    TransportCc,
+    Other(&'a str),
 }
 
 impl<'a> RtcpFb<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
-        let mut split = value.split(' ');
-        let payload = parse_str(split.next(), 1)?;
-        let r#type = parse_str(split.next(), 2)?;
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.splitn(3, ' ');
+        let payload = Format::new(parse_str(split.next(), "payload type")?)?;
+        let r#type = parse_str(split.next(), "feedback type")?;
+        let parameter = split.next();
+
+        match r#type {
+            "trr-int" => {
+                parse_number::<u32>(parameter, "trr-int interval")?;
+            }
+            "ack" => {
+                parse_str(parameter, "ack subtype")?;
+            }
+            _ => (),
+        }
+
+        Ok(Self {
+            payload,
+            r#type,
+            parameter,
+        })
+    }
+
+    /// The minimum interval between regular RTCP reports of a trr-int line, in
+    /// milliseconds
+    pub fn trr_int(&self) -> Option<u32> {
+        match self.feedback_type() {
+            RtcpFbType::TrrInt => self.parameter?.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The subtype of an ack line and its parameters, e.g. ("app", Some("foo"))
+    /// for "ack app foo"
+    pub fn ack(&self) -> Option<(&'a str, Option<&'a str>)> {
+        match self.feedback_type() {
+            RtcpFbType::Ack => match self.parameter?.split_once(' ') {
+                Some((subtype, parameters)) => Some((subtype, Some(parameters))),
+                None => Some((self.parameter?, None)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether this line applies to a payload type, either by its number or
+    /// through the "*" wildcard
+    pub fn applies_to(&self, payload: u8) -> bool {
+        match self.payload {
+            Format::Payload(own) => own == payload,
+            Format::Token(token) => token == "*",
+        }
+    }
+
+    /// The payload type of this line, None for the "*" wildcard
+    pub fn payload_type(&self) -> Option<u8> {
+        match self.payload {
+            Format::Payload(payload) => Some(payload),
+            Format::Token(_) => None,
+        }
+    }
 
-        Ok(Self { payload, r#type })
+    /// The typed feedback type of this line
+    pub fn feedback_type(&self) -> RtcpFbType<'a> {
+        match self.r#type {
+            "ack" => RtcpFbType::Ack,
+            "nack" => RtcpFbType::Nack,
+            "trr-int" => RtcpFbType::TrrInt,
+            "ccm" => RtcpFbType::Ccm,
+            "goog-remb" => RtcpFbType::GoogRemb,
+            "transport-cc" => RtcpFbType::TransportCc,
+            other => RtcpFbType::Other(other),
+        }
+    }
+}
+
+impl<'a> fmt::Display for RtcpFb<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.payload, self.r#type)?;
+
+        if let Some(parameter) = self.parameter {
+            write!(f, " {}", parameter)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -215,27 +1784,24 @@ impl<'a> RtcpFb<'a> {
 /// Identifier which will remain constant for the RTP media stream even when the ssrc
 /// identifier changes if a conflict is found. This is the value that the media sender
 /// will place in its RTCP SDES packets.
-#[derive(Debug, Default, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Ssrc<'a> {
-    pub id: u64,
+    pub id: u32,
     pub attribute: &'a str,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub value: Option<&'a str>,
 }
 
 impl<'a> Ssrc<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
-        let mut split = value.split(' ');
-        let id = parse_number::<u64>(split.next(), 1)?;
-
-        let mut split = parse_str(split.next(), 2)?.split(':');
-        let attribute = parse_str(split.next(), 2)?;
-        let mut value = None;
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.splitn(2, ' ');
+        let id = parse_number::<u32>(split.next(), "ssrc")?;
 
-        if let Some(split) = split.next() {
-            value = Some(parse_str(Some(split), 3)?);
-        }
+        // values can contain spaces and colons, e.g. msid:stream track
+        let mut split = parse_str(split.next(), "attribute")?.splitn(2, ':');
+        let attribute = parse_str(split.next(), "attribute")?;
+        let value = split.next();
 
         Ok(Self {
             id,
@@ -243,6 +1809,104 @@ impl<'a> Ssrc<'a> {
             value,
         })
     }
+
+    /// The stream id and the track id (the appdata) of a msid attribute
+    ///
+    /// a=ssrc:3570614608 msid:stream track
+    ///
+    /// The track id is None for a source that only names its stream.
+    pub fn msid(&self) -> Option<(&'a str, Option<&'a str>)> {
+        if self.attribute != "msid" {
+            return None;
+        }
+
+        let mut split = self.value?.split(' ').filter(|id| !id.is_empty());
+
+        Some((split.next()?, split.next()))
+    }
+}
+
+/// The ssrc lines of a single source, grouped by id
+///
+/// a=ssrc:3570614608 cname:4TOk42mSjXCkVIa6
+/// a=ssrc:3570614608 msid:stream track
+///
+/// mslabel and label are the legacy, pre-msid way of naming the stream and track.
+#[derive(Debug, Default, Clone, Serialize, PartialEq, Eq, Hash)]
+pub struct SsrcInfo<'a> {
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cname: Option<&'a str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msid: Option<&'a str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mslabel: Option<&'a str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<&'a str>,
+
+    /// Any other attributes, with their values
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other: Vec<(&'a str, Option<&'a str>)>,
+}
+
+/// SSRC Group
+///
+/// a=ssrc-group:FID 3570614608 2231627014
+///
+/// Ties sources of a media section together (RFC 5576).  FID pairs a source with
+/// its retransmission (RTX) source and FEC-FR with its forward error correction
+/// source, the primary source coming first.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SsrcGroup<'a> {
+    pub semantics: &'a str,
+    pub ssrcs: Vec<u32>,
+
+    #[serde(skip)]
+    pub line: SourceLine,
+}
+
+impl<'a> SsrcGroup<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ').filter(|token| !token.is_empty());
+        let semantics = parse_str(split.next(), "semantics")?;
+        let ssrcs = split
+            .map(|ssrc| parse_number::<u32>(Some(ssrc), "ssrc"))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            semantics,
+            ssrcs,
+            line: SourceLine::default(),
+        })
+    }
+}
+
+impl<'a> fmt::Display for SsrcGroup<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.semantics)?;
+
+        for ssrc in &self.ssrcs {
+            write!(f, " {}", ssrc)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Ssrc<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.attribute)?;
+
+        if let Some(value) = self.value {
+            write!(f, ":{}", value)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -257,34 +1921,524 @@ mod tests {
             r#type: "audio",
             port: 58779,
             protocol: "UDP/TLS/RTP/SAVPF",
-            payloads: "111",
+            payloads: vec![111, 103, 104, 9, 0, 8, 106, 105, 13, 126],
             candidates: vec![],
-            direction: "",
+            direction: None,
             fmtp: vec![],
-            ptime: 0,
             rtpmap: vec![],
             rtc_fb: vec![],
             ssrc: vec![],
+            ..Default::default()
         };
 
         assert_eq!(parsed, expected);
     }
 
+    fn video() -> Media<'static> {
+        let mut media = Media::new("video 9 UDP/TLS/RTP/SAVPF 96 97 98 99 100").unwrap();
+
+        for rtpmap in [
+            "96 VP8/90000",
+            "97 rtx/90000",
+            "98 H264/90000",
+            "99 rtx/90000",
+        ] {
+            media.parse_attribute("rtpmap", rtpmap).unwrap();
+        }
+
+        for fmtp in ["97 apt=96", "98 profile-level-id=42e01f", "99 apt=98"] {
+            media.parse_attribute("fmtp", fmtp).unwrap();
+        }
+
+        for rtc_fb in ["96 nack", "98 nack", "* transport-cc"] {
+            media.parse_attribute("rtcp-fb", rtc_fb).unwrap();
+        }
+
+        media
+    }
+
+    #[test]
+    fn it_removes_codecs() {
+        let mut media = video();
+        media.remove_codec("vp8");
+
+        assert_eq!(media.payloads, vec![98, 99, 100]);
+        assert_eq!(media.rtpmap.len(), 2);
+        assert_eq!(media.fmtp.len(), 2);
+        assert_eq!(media.rtc_fb.len(), 2);
+
+        let mut media = video();
+        media.keep_only_codecs(&["H264"]);
+
+        assert_eq!(media.payloads, vec![98, 99]);
+        assert_eq!(media.codec_for_payload(96), None);
+        assert_eq!(media.fmtp_for_payload(97), None);
+        assert_eq!(media.rtc_fb[0].payload, Format::Payload(98));
+    }
+
+    #[test]
+    fn it_reorders_payloads() {
+        let mut media = video();
+        media.reorder_payloads(&["H264"]);
+
+        assert_eq!(media.payloads, vec![98, 99, 96, 97, 100]);
+        assert_eq!(media.rtpmap[0].codec, "H264");
+        assert_eq!(media.fmtp[0].payload_type(), Some(98));
+        assert_eq!(media.rtc_fb[0].payload, Format::Payload(98));
+        assert_eq!(media.rtc_fb[2].payload, Format::Payload(96));
+    }
+
+    #[test]
+    fn it_sets_opus_params() {
+        let mut media = Media::new("audio 9 UDP/TLS/RTP/SAVPF 111 0").unwrap();
+        media.parse_attribute("rtpmap", "111 opus/48000/2").unwrap();
+        media.set_opus_params("stereo=1");
+
+        assert_eq!(media.fmtp_for_payload(111).unwrap().config, "stereo=1");
+
+        media.set_opus_params("useinbandfec=1");
+
+        assert_eq!(media.fmtp.len(), 1);
+        assert_eq!(
+            media.fmtp_for_payload(111).unwrap().config,
+            "useinbandfec=1"
+        );
+    }
+
+    #[test]
+    fn it_groups_ssrc_lines() {
+        let mut media = Media::new("video 9 UDP/TLS/RTP/SAVPF 96").unwrap();
+
+        for ssrc in [
+            "1 cname:foo",
+            "1 msid:stream track",
+            "2 cname:foo",
+            "1 x-custom",
+        ] {
+            media.parse_attribute("ssrc", ssrc).unwrap();
+        }
+
+        let expected = vec![
+            SsrcInfo {
+                id: 1,
+                cname: Some("foo"),
+                msid: Some("stream track"),
+                other: vec![("x-custom", None)],
+                ..Default::default()
+            },
+            SsrcInfo {
+                id: 2,
+                cname: Some("foo"),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(media.ssrc_info(), expected);
+        assert_eq!(media.ssrc[1].to_string(), "1 msid:stream track");
+    }
+
+    #[test]
+    fn it_pairs_rtx_sources() {
+        let mut media = video();
+
+        for ssrc_group in ["FID 1 2", "FEC-FR 1 3", "SIM 1 4 5"] {
+            media.parse_attribute("ssrc-group", ssrc_group).unwrap();
+        }
+
+        assert_eq!(media.rtx_pairs(), vec![(1, 2)]);
+        assert_eq!(media.fec_pairs(), vec![(1, 3)]);
+        assert_eq!(media.rtx_payloads(), vec![(96, 97), (98, 99)]);
+        assert_eq!(media.ssrc_groups[2].to_string(), "SIM 1 4 5");
+        assert!(SsrcGroup::new("FID 1 x").is_err());
+    }
+
+    #[test]
+    fn it_computes_candidate_priorities() {
+        assert_eq!(
+            Candidate::compute_priority(126, 65535, 1).unwrap(),
+            2130706431
+        );
+        assert_eq!(Candidate::compute_priority(0, 0, 2).unwrap(), 254);
+        assert_eq!(
+            Candidate::compute_priority(126, 65535, 256).unwrap(),
+            2130706176
+        );
+        assert!(Candidate::compute_priority(126, 65535, 0).is_err());
+        assert!(Candidate::compute_priority(126, 65535, 257).is_err());
+        assert!(matches!(
+            Candidate::compute_priority(127, 65535, 1),
+            Err(Error::InvalidValue {
+                field: "type preference",
+                ..
+            })
+        ));
+
+        let mut media = Media::new("audio 9 UDP/TLS/RTP/SAVPF 0").unwrap();
+
+        for candidate in [
+            "1 1 UDP 1686052607 203.0.113.1 54400 typ srflx",
+            "2 2 UDP 2130706430 192.168.0.1 54401 typ host",
+            "3 1 UDP 2130706431 192.168.0.1 54400 typ host",
+            "4 2 UDP 16777214 198.51.100.1 3478 typ relay",
+        ] {
+            media.parse_attribute("candidate", candidate).unwrap();
+        }
+
+        fn foundations<'a>(candidates: Vec<&Candidate<'a>>) -> Vec<&'a str> {
+            candidates
+                .iter()
+                .map(|candidate| candidate.foundation)
+                .collect()
+        }
+
+        assert_eq!(
+            foundations(media.candidates_sorted()),
+            vec!["3", "2", "1", "4"]
+        );
+        assert_eq!(
+            foundations(media.best_candidate_per_component()),
+            vec!["3", "2"]
+        );
+    }
+
+    #[test]
+    fn it_parses_packet_times() {
+        let mut media = Media::new("audio 9 RTP/AVP 0").unwrap();
+
+        assert_eq!(media.ptime, None);
+
+        media.parse_attribute("ptime", "20.0").unwrap();
+        media.parse_attribute("maxptime", "40").unwrap();
+
+        assert_eq!(media.ptime, Some(20.0));
+        assert_eq!(media.maxptime, Some(40.0));
+        assert!(media.parse_attribute("ptime", "20.").is_err());
+        assert!(media.parse_attribute("ptime", "-20").is_err());
+    }
+
+    #[test]
+    fn it_parses_room_system_attributes() {
+        let mut media = Media::new("video 9 RTP/AVP 97").unwrap();
+
+        media.parse_attribute("framerate", "29.97").unwrap();
+        media.parse_attribute("quality", "10").unwrap();
+        media.parse_attribute("orient", "landscape").unwrap();
+
+        assert_eq!(media.framerate, Some(29.97));
+        assert_eq!(media.quality, Some(10));
+        assert_eq!(media.orient, Some("landscape"));
+        assert!(media
+            .to_string()
+            .contains("a=framerate:29.97\r\na=quality:10\r\na=orient:landscape\r\n"));
+        assert!(media.parse_attribute("framerate", "-30").is_err());
+        assert!(media.parse_attribute("framerate", "NaN").is_err());
+        assert!(media.parse_attribute("quality", "11").is_err());
+    }
+
+    #[test]
+    fn it_resolves_wildcard_feedback() {
+        let mut media = Media::new("video 9 RTP/AVPF 96 97").unwrap();
+
+        for rtc_fb in [
+            "* nack",
+            "96 nack",
+            "96 nack pli",
+            "97 ccm fir",
+            "* transport-cc",
+        ] {
+            media.parse_attribute("rtcp-fb", rtc_fb).unwrap();
+        }
+
+        let feedback = |payload| {
+            media
+                .rtcp_fb_for_payload(payload)
+                .iter()
+                .map(|rtc_fb| rtc_fb.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            feedback(96),
+            vec!["* nack", "96 nack pli", "* transport-cc"]
+        );
+        assert_eq!(feedback(97), vec!["* nack", "97 ccm fir", "* transport-cc"]);
+    }
+
+    #[test]
+    fn it_detects_congestion_control_feedback() {
+        let mut media = Media::new("video 9 RTP/AVPF 96 97 98").unwrap();
+
+        for rtc_fb in ["96 goog-remb", "96 transport-cc", "* goog-remb"] {
+            media.parse_attribute("rtcp-fb", rtc_fb).unwrap();
+        }
+
+        assert!(media.supports_transport_cc(96));
+        assert!(!media.supports_transport_cc(97));
+        assert!(media.supports_remb(96));
+        assert!(media.supports_remb(98));
+    }
+
+    #[test]
+    fn it_parses_a_port_count() {
+        let media = Media::new("video 49170/2 RTP/AVP 31").unwrap();
+
+        assert_eq!(media.port, 49170);
+        assert_eq!(media.port_count, Some(2));
+        assert_eq!(media.to_string(), "m=video 49170/2 RTP/AVP 31\r\n");
+        assert_eq!(
+            Media::new("audio 49170 RTP/AVP 0").unwrap().port_count,
+            None
+        );
+        assert!(Media::new("video 49170/two RTP/AVP 31").is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_media_section() {
+        let mut media = Media::new("video 9 UDP/TLS/RTP/SAVPF 96").unwrap();
+        media.parse_attribute("mid", "1").unwrap();
+        media.parse_attribute("rtpmap", "96 VP8/90000").unwrap();
+        media.parse_flag("rtcp-mux").unwrap();
+
+        let rejected = media.rejected();
+
+        assert!(!media.is_rejected());
+        assert!(rejected.is_rejected());
+        assert_eq!(
+            rejected.to_string(),
+            "m=video 0 UDP/TLS/RTP/SAVPF 96\r\na=mid:1\r\n"
+        );
+        assert!(Media::new("video 0 UDP/TLS/RTP/SAVPF 0")
+            .unwrap()
+            .is_rejected());
+    }
+
+    #[test]
+    fn it_parses_imageattr_lines() {
+        let mut media = Media::new("video 9 UDP/TLS/RTP/SAVPF 96 97").unwrap();
+
+        media
+            .parse_attribute("imageattr", "96 send [x=1280,y=720] recv *")
+            .unwrap();
+        media
+            .parse_attribute("imageattr", "* recv [x=[320:16:640],y=[240:16:480]]")
+            .unwrap();
+
+        assert_eq!(media.imageattr.len(), 2);
+        assert_eq!(media.imageattr[1].payload, Format::Token("*"));
+        assert!(media
+            .to_string()
+            .contains("a=imageattr:96 send [x=1280,y=720] recv *\r\n"));
+    }
+
+    #[test]
+    fn it_parses_remote_candidates() {
+        let mut media = Media::new("audio 45664 RTP/AVP 0").unwrap();
+
+        media
+            .parse_attribute("remote-candidates", "1 192.0.2.3 45664 2 192.0.2.3 45665")
+            .unwrap();
+
+        assert_eq!(
+            media.remote_candidates[1],
+            RemoteCandidate {
+                component: 2,
+                address: "192.0.2.3",
+                port: 45665,
+            }
+        );
+        assert!(media
+            .to_string()
+            .contains("a=remote-candidates:1 192.0.2.3 45664 2 192.0.2.3 45665\r\n"));
+        assert!(media
+            .parse_attribute("remote-candidates", "1 192.0.2.3")
+            .is_err());
+    }
+
+    #[test]
+    fn it_writes_the_information_after_the_media_line() {
+        let sdp = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=-\r
+t=0 0\r
+m=video 51372 RTP/AVP 99\r
+i=Main camera\r
+c=IN IP4 203.0.113.1\r
+b=AS:512\r
+k=prompt\r
+a=rtpmap:99 h263-1998/90000\r
+";
+        let mut parsed = Sdp::parse(sdp).unwrap();
+
+        assert_eq!(parsed.media[0].information, Some("Main camera"));
+        assert_eq!(parsed.media[0].encryption_key, Some("prompt"));
+        assert_eq!(parsed.encryption_key, None);
+        assert_eq!(parsed.to_string(), sdp);
+
+        parsed.media[0].information = Some("Slides");
+
+        assert!(parsed.media[0]
+            .to_string()
+            .starts_with("m=video 51372 RTP/AVP 99\r\ni=Slides\r\nc=IN IP4"));
+    }
+
+    #[test]
+    fn it_keys_sets_on_model_types() {
+        use std::collections::HashSet;
+
+        let candidate = "1 1 udp 2122260223 192.168.0.196 46243 typ host";
+        let candidates: HashSet<_> = [candidate, candidate, "2 1 tcp 1 192.168.0.196 9 typ host"]
+            .iter()
+            .map(|candidate| Candidate::new(candidate).unwrap())
+            .collect();
+        let rtpmaps: HashSet<_> = ["96 VP8/90000", "97 rtx/90000", "96 VP8/90000"]
+            .iter()
+            .map(|rtpmap| Rtpmap::new(rtpmap).unwrap())
+            .collect();
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(rtpmaps.len(), 2);
+        assert!(rtpmaps.contains(&Rtpmap::new("97 rtx/90000").unwrap()));
+    }
+
+    #[test]
+    fn it_types_the_media_line() {
+        let media = Media::new("application 9 UDP/DTLS/SCTP webrtc-datachannel").unwrap();
+
+        assert_eq!(media.media_type(), MediaType::Application);
+        assert_eq!(media.transport_protocol(), TransportProtocol::UdpDtlsSctp);
+        assert!(media.transport_protocol().is_dtls());
+        assert!(!media.transport_protocol().is_rtp());
+        assert_eq!(MediaType::from("image"), MediaType::Image);
+        assert_eq!(MediaType::from("control"), MediaType::Other("control"));
+        assert_eq!(TransportProtocol::RtpSavpf.to_string(), "RTP/SAVPF");
+    }
+
+    #[test]
+    fn it_looks_up_codecs_by_payload() {
+        let mut media = Media::new("audio 58779 UDP/TLS/RTP/SAVPF 111 0").unwrap();
+        media.parse_attribute("rtpmap", "111 opus/48000/2").unwrap();
+        media.parse_attribute("rtpmap", "0 PCMU/8000").unwrap();
+        media.parse_attribute("fmtp", "111 minptime=10").unwrap();
+
+        assert_eq!(media.codec_for_payload(111).unwrap().codec, "opus");
+        assert_eq!(media.codec_for_payload(0).unwrap().codec, "PCMU");
+        assert_eq!(media.codec_for_payload(8), None);
+        assert_eq!(media.fmtp_for_payload(111).unwrap().config, "minptime=10");
+        assert_eq!(media.fmtp_for_payload(0), None);
+    }
+
+    #[test]
+    fn it_rejects_non_numeric_rtp_payloads() {
+        assert!(Media::new("audio 58779 UDP/TLS/RTP/SAVPF opus").is_err());
+    }
+
+    #[test]
+    fn it_rejects_out_of_range_numbers() {
+        assert_eq!(
+            Media::new("audio 99999999 RTP/AVP 0").unwrap_err(),
+            Error::InvalidNumber {
+                field: "port",
+                value: "99999999".into(),
+            }
+        );
+        assert_eq!(
+            Media::new("audio 9 RTP/AVP 0 128").unwrap_err(),
+            Error::InvalidNumber {
+                field: "payload type",
+                value: "128".into(),
+            }
+        );
+        assert!(Rtpmap::new("200 PCMU/8000").is_err());
+        assert!(Candidate::new("1 1 udp 4294967296 203.0.113.1 9 typ host").is_err());
+        assert_eq!(
+            Ssrc::new("99999999999 cname:foo").unwrap_err(),
+            Error::InvalidNumber {
+                field: "ssrc",
+                value: "99999999999".into(),
+            }
+        );
+        assert!(SsrcGroup::new("FID 3570614608 4294967296").is_err());
+    }
+
     #[test]
     fn it_parses_a_candidate() {
         let candidate = "1467250027 1 udp 2122260223 192.168.0.196 46243 typ host generation 0";
         let parsed = Candidate::new(candidate).unwrap();
         let expected = Candidate {
-            component: 1467250027,
-            foundation: "1",
+            foundation: "1467250027",
+            component: 1,
             transport: "udp",
             priority: 2122260223,
             ip: "192.168.0.196",
             port: 46243,
             r#type: "host",
+            extensions: vec![("generation", "0")],
         };
 
         assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), candidate);
+    }
+
+    #[test]
+    fn it_converts_an_ice_candidate_string() {
+        let ice = "candidate:2 1 udp 1694498815 198.51.100.7 61000 typ srflx raddr 203.0.113.1 rport 54402 generation 0";
+        let parsed = Candidate::try_from(ice).unwrap();
+
+        assert_eq!(parsed.extension("raddr"), Some("203.0.113.1"));
+        assert_eq!(parsed.extension("rport"), Some("54402"));
+        assert_eq!(parsed.to_attribute_string(), ice);
+        assert_eq!(
+            Candidate::try_from(&format!("a={}\r\n", ice)[..]),
+            Ok(parsed)
+        );
+        assert!(
+            Candidate::try_from("candidate:2 1 udp 1 198.51.100.7 61000 typ srflx raddr").is_err()
+        );
+    }
+
+    #[test]
+    fn it_parses_candidate_addresses() {
+        let ipv6 = "1 1 udp 2122262783 2001:db8::1 46243 typ host";
+        let mdns = "1 1 udp 2122262783 0d1b5c4f-7b2e-4c1d-9f3a-6e8b2d4a1c7f.local 46243 typ host";
+        let address = Candidate::new(ipv6).unwrap().addr().unwrap();
+
+        assert!(matches!(address, Address::Ip(ip) if ip.is_ipv6()));
+        assert_eq!(
+            Candidate::new(mdns).unwrap().addr(),
+            Ok(Address::Mdns("0d1b5c4f-7b2e-4c1d-9f3a-6e8b2d4a1c7f.local"))
+        );
+    }
+
+    #[test]
+    fn it_queries_a_candidate() {
+        let relay = "2 1 udp 41885439 [2001:db8::2] 3478 typ relay";
+        let mdns = "1 1 udp 2122262783 0d1b5c4f-7b2e-4c1d-9f3a-6e8b2d4a1c7f.local 46243 typ host";
+        let relay = Candidate::new(relay).unwrap();
+        let mdns = Candidate::new(mdns).unwrap();
+
+        assert_eq!(
+            relay.socket_addr().unwrap().to_string(),
+            "[2001:db8::2]:3478"
+        );
+        assert!(relay.is_ipv6() && relay.is_relay() && !relay.is_mdns());
+        assert!(mdns.is_mdns() && mdns.is_host() && !mdns.is_ipv6());
+        assert!(!mdns.is_srflx() && !mdns.is_prflx());
+        assert!(mdns.socket_addr().is_err());
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_candidate() {
+        let component = "1467250027 257 udp 2122260223 192.168.0.196 46243 typ host";
+        let zero = "1467250027 0 udp 2122260223 192.168.0.196 46243 typ host";
+        let third = "1467250027 3 udp 2122260223 192.168.0.196 46243 typ host";
+        let priority = "1467250027 1 udp 4294967296 192.168.0.196 46243 typ host";
+        let port = "1467250027 1 udp 2122260223 192.168.0.196 65536 typ host";
+
+        assert!(Candidate::new(component).is_err());
+        assert!(Candidate::new(zero).is_err());
+        assert_eq!(Candidate::new(third).unwrap().component, 3);
+        assert!(Candidate::new(priority).is_err());
+        assert!(Candidate::new(port).is_err());
     }
 
     #[test]
@@ -292,21 +2446,67 @@ mod tests {
         let fmtp = "111 minptime=10; useinbandfec=1";
         let parsed = Fmtp::new(fmtp).unwrap();
         let expected = Fmtp {
-            config: "minptime=10; useinbandfec=1",
-            payload: 111,
+            config: "minptime=10; useinbandfec=1".into(),
+            payload: Format::Payload(111),
+            line: SourceLine::default(),
         };
 
         assert_eq!(parsed, expected);
     }
 
+    #[test]
+    fn it_parses_a_fmtp_for_other_formats() {
+        let mut media = Media::new("application 9 UDP/DTLS/SCTP webrtc-datachannel").unwrap();
+        media
+            .parse_attribute("fmtp", "webrtc-datachannel max-message-size=262144")
+            .unwrap();
+
+        let fmtp = media.fmtp_for_format("webrtc-datachannel").unwrap();
+
+        assert_eq!(fmtp.payload, Format::Token("webrtc-datachannel"));
+        assert_eq!(fmtp.payload_type(), None);
+        assert_eq!(
+            fmtp.to_string(),
+            "webrtc-datachannel max-message-size=262144"
+        );
+        assert!(Fmtp::new("300 apt=96").is_err());
+        assert!(Fmtp::new("128 apt=96").is_err());
+    }
+
+    #[test]
+    fn it_resolves_rtx_associations() {
+        let fmtp = Fmtp::new("97 apt=96;rtx-time=3000").unwrap();
+
+        assert_eq!(fmtp.payload_type(), Some(97));
+        assert_eq!(fmtp.apt(), Some(96));
+        assert_eq!(Fmtp::new("111 minptime=10").unwrap().apt(), None);
+    }
+
     #[test]
     fn it_parses_a_rtpmap() {
         let rtpmap = "111 opus/48000/2";
         let parsed = Rtpmap::new(rtpmap).unwrap();
         let expected = Rtpmap {
             codec: "opus",
-            payload: "111",
-            rate: 48000,
+            payload: 111,
+            rate: Some(48000),
+            channels: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn it_parses_a_rtpmap_without_a_rate() {
+        let rtpmap = "101 telephone-event";
+        let parsed = Rtpmap::new(rtpmap).unwrap();
+        let expected = Rtpmap {
+            codec: "telephone-event",
+            payload: 101,
+            rate: None,
+            channels: None,
+            ..Default::default()
         };
 
         assert_eq!(parsed, expected);
@@ -317,13 +2517,55 @@ mod tests {
         let rtcpfb = "100 nack";
         let parsed = RtcpFb::new(rtcpfb).unwrap();
         let expected = RtcpFb {
-            payload: "100",
+            payload: Format::Payload(100),
             r#type: "nack",
+            parameter: None,
         };
 
         assert_eq!(parsed, expected);
     }
 
+    #[test]
+    fn it_parses_a_rtcpfb_with_a_parameter() {
+        let rtcpfb = "100 ccm fir";
+        let parsed = RtcpFb::new(rtcpfb).unwrap();
+        let expected = RtcpFb {
+            payload: Format::Payload(100),
+            r#type: "ccm",
+            parameter: Some("fir"),
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.feedback_type(), RtcpFbType::Ccm);
+        assert_eq!(
+            RtcpFb::new("100 goog-remb").unwrap().feedback_type(),
+            RtcpFbType::GoogRemb
+        );
+        assert_eq!(
+            RtcpFb::new("100 foo").unwrap().feedback_type(),
+            RtcpFbType::Other("foo")
+        );
+    }
+
+    #[test]
+    fn it_parses_trr_int_and_ack_feedback() {
+        let trr_int = RtcpFb::new("100 trr-int 100").unwrap();
+        let ack = RtcpFb::new("100 ack app foo bar").unwrap();
+
+        assert_eq!(trr_int.trr_int(), Some(100));
+        assert_eq!(trr_int.to_string(), "100 trr-int 100");
+        assert_eq!(ack.ack(), Some(("app", Some("foo bar"))));
+        assert_eq!(ack.to_string(), "100 ack app foo bar");
+        assert_eq!(
+            RtcpFb::new("* ack rpsi").unwrap().ack(),
+            Some(("rpsi", None))
+        );
+        assert_eq!(RtcpFb::new("100 nack pli").unwrap().ack(), None);
+        assert!(RtcpFb::new("100 trr-int").is_err());
+        assert!(RtcpFb::new("100 trr-int often").is_err());
+        assert!(RtcpFb::new("100 ack").is_err());
+    }
+
     #[test]
     fn it_parses_a_ssrc() {
         let ssrc = "3570614608 cname:4TOk42mSjXCkVIa6";
@@ -335,5 +2577,20 @@ mod tests {
         };
 
         assert_eq!(parsed, expected);
+        assert_eq!(parsed.msid(), None);
+    }
+
+    #[test]
+    fn it_parses_a_ssrc_msid() {
+        let parsed = Ssrc::new("3570614608 msid:stream track").unwrap();
+
+        assert_eq!(parsed.value, Some("stream track"));
+        assert_eq!(parsed.msid(), Some(("stream", Some("track"))));
+        assert_eq!(parsed.to_string(), "3570614608 msid:stream track");
+        assert_eq!(
+            Ssrc::new("3570614608 msid:stream").unwrap().msid(),
+            Some(("stream", None))
+        );
+        assert_eq!(Ssrc::new("3570614608 msid:").unwrap().msid(), None);
     }
 }
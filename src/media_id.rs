@@ -0,0 +1,120 @@
+use crate::media::Media;
+use crate::sdp::Sdp;
+use alloc::collections::BTreeMap;
+
+/// A handle to a media section of a Sdp
+///
+/// let id = sdp.media_id("1")?;
+/// sdp.media_by_id_mut(id)?.direction = Some(Direction::Inactive);
+///
+/// The position of the section in the message, so a handle taken after
+/// parsing keeps pointing at the same section as long as sections are only
+/// added or rejected, see `Sdp::reject_media`, and never removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MediaId(usize);
+
+impl MediaId {
+    /// The index of the section in `Sdp::media`
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// The media sections of a parsed Sdp by mid
+///
+/// Built when parsing, so bundled SDPs with many mids don't need a scan per
+/// lookup.  A section added or given a mid later is still found, by a scan,
+/// and the index doesn't take part in equality.
+#[derive(Debug, Default, Clone)]
+pub struct MidIndex<'a>(BTreeMap<&'a str, usize>);
+
+impl<'a> MidIndex<'a> {
+    pub(crate) fn new(media: &[Media<'a>]) -> Self {
+        let mut index = BTreeMap::new();
+
+        for (position, media) in media.iter().enumerate() {
+            if let Some(mid) = media.mid {
+                index.entry(mid).or_insert(position);
+            }
+        }
+
+        Self(index)
+    }
+}
+
+impl<'a> PartialEq for MidIndex<'a> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+// the section with a mid, from the index if it is still right about it
+pub(crate) fn find(sdp: &Sdp, mid: &str) -> Option<MediaId> {
+    let indexed = sdp
+        .mid_index
+        .0
+        .get(mid)
+        .filter(|&&position| sdp.media.get(position).and_then(|media| media.mid) == Some(mid));
+
+    match indexed {
+        Some(&position) => Some(MediaId(position)),
+        None => sdp
+            .media
+            .iter()
+            .position(|media| media.mid == Some(mid))
+            .map(MediaId),
+    }
+}
+
+pub(crate) fn ids(sdp: &Sdp) -> impl Iterator<Item = MediaId> {
+    (0..sdp.media.len()).map(MediaId)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE 0 1
+m=audio 9 UDP/TLS/RTP/SAVPF 111
+a=mid:0
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=mid:1";
+
+    #[test]
+    fn it_looks_up_media_by_mid() {
+        let mut sdp = Sdp::parse(SDP).unwrap();
+        let id = sdp.media_id("1").unwrap();
+
+        assert_eq!(id.index(), 1);
+        assert_eq!(sdp.media_by_mid("1").unwrap().r#type, "video");
+        assert!(sdp.media_by_mid("2").is_none());
+
+        sdp.media_by_id_mut(id).unwrap().direction = Some(Direction::Inactive);
+        sdp.reject_media(0).unwrap();
+
+        assert_eq!(
+            sdp.media_by_id(id).unwrap().direction,
+            Some(Direction::Inactive)
+        );
+        assert_eq!(sdp.media_ids().collect::<Vec<_>>().len(), 2);
+    }
+
+    #[test]
+    fn it_finds_sections_the_index_misses() {
+        let mut sdp = Sdp::parse(SDP).unwrap();
+        sdp.media.swap(0, 1);
+
+        assert_eq!(sdp.media_by_mid("0").unwrap().r#type, "audio");
+
+        let mut media = sdp.media[0].clone();
+        media.mid = Some("2");
+        sdp.media.push(media);
+
+        assert_eq!(sdp.media_id("2").unwrap().index(), 2);
+    }
+}
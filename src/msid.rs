@@ -0,0 +1,58 @@
+use crate::error::Result;
+use crate::utils::parse_str;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// SDP MSID Semantic
+///
+/// a=msid-semantic: WMS *
+///
+/// A session-level line announcing the media streams (WMS, WebRTC Media
+/// Streams) the msid lines refer to, either by id or with * for all of them.
+/// Plan B era browsers, Jitsi and Janus still send it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MsidSemantic<'a> {
+    pub semantic: &'a str,
+
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<&'a str>,
+}
+
+impl<'a> MsidSemantic<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ').filter(|token| !token.is_empty());
+        let semantic = parse_str(split.next(), "semantic")?;
+        let ids = split.collect();
+
+        Ok(Self { semantic, ids })
+    }
+}
+
+impl<'a> fmt::Display for MsidSemantic<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.semantic)?;
+
+        for id in &self.ids {
+            write!(f, " {}", id)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_msid_semantic() {
+        let parsed = MsidSemantic::new(" WMS *").unwrap();
+        let expected = MsidSemantic {
+            semantic: "WMS",
+            ids: vec!["*"],
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), "WMS *");
+    }
+}
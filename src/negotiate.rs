@@ -0,0 +1,321 @@
+//! SDP offer/answer negotiation (RFC 3264).
+//!
+//! Build an answer from the local capabilities, themselves described as a SDP,
+//! and a remote offer.
+
+use crate::direction::Direction;
+use crate::group::Group;
+use crate::media::{Fmtp, Format, Media};
+use crate::media_id::MidIndex;
+use crate::sdp::Sdp;
+use crate::validate::SourceLine;
+use alloc::vec::Vec;
+
+/// Build the answer to `offer` from the `local` capabilities
+///
+/// * every offered m line is answered, in the same order
+/// * codecs are the intersection of both sides, in the order and with the
+///   payload types of the offer
+/// * directions are mirrored (sendonly is answered with recvonly) and limited
+///   to what the local side supports
+/// * m lines without a local counterpart or a common codec are rejected by
+///   setting their port to 0
+/// * the groups of the offer, e.g. BUNDLE, keep the mids of the accepted m
+///   lines
+pub fn answer<'a>(local: &Sdp<'a>, offer: &Sdp<'a>) -> Sdp<'a> {
+    let mut answer = Sdp {
+        version: local.version,
+        session_name: local.session_name,
+        origin: local.origin.clone(),
+        times: local.times.clone(),
+        connection: local.connection.clone(),
+        ice_ufrag: local.ice_ufrag,
+        ice_pwd: local.ice_pwd,
+        fingerprints: local.fingerprints.clone(),
+        setup: offer.setup.map(|setup| answer_setup(local.setup, setup)),
+        media: offer
+            .media
+            .iter()
+            .map(|offered| {
+                local
+                    .media
+                    .iter()
+                    .filter(|supported| {
+                        supported.r#type == offered.r#type && supported.protocol == offered.protocol
+                    })
+                    .find_map(|supported| accept(local, offer, supported, offered))
+                    .unwrap_or_else(|| offered.rejected())
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    answer.groups = answer_groups(offer, &answer.media);
+    answer.mid_index = MidIndex::new(&answer.media);
+
+    answer
+}
+
+// the groups of the offer without the mids of the rejected m lines
+fn answer_groups<'a>(offer: &Sdp<'a>, media: &[Media<'a>]) -> Vec<Group<'a>> {
+    let accepted = |mid: &&str| {
+        media
+            .iter()
+            .any(|media| media.mid == Some(*mid) && !media.is_rejected())
+    };
+
+    offer
+        .groups
+        .iter()
+        .map(|group| Group {
+            semantics: group.semantics,
+            mids: group.mids.iter().cloned().filter(accepted).collect(),
+        })
+        .filter(|group| !group.mids.is_empty())
+        .collect()
+}
+
+// answer an offered media section with a local one
+// return None when they have nothing in common
+fn accept<'a>(
+    local: &Sdp<'a>,
+    offer: &Sdp<'a>,
+    supported: &Media<'a>,
+    offered: &Media<'a>,
+) -> Option<Media<'a>> {
+    let mut media = Media {
+        r#type: offered.r#type,
+        port: supported.port,
+        protocol: offered.protocol,
+        ptime: supported.ptime,
+        maxptime: supported.maxptime,
+        connection: supported.connection.clone(),
+        candidates: supported.candidates.clone(),
+        ice_ufrag: supported.ice_ufrag,
+        ice_pwd: supported.ice_pwd,
+        fingerprints: supported.fingerprints.clone(),
+        setup: offered
+            .setup
+            .map(|setup| answer_setup(supported.setup.or(local.setup), setup)),
+        application: supported.application.clone(),
+        t38: supported.t38.clone(),
+        mid: offered.mid,
+        rtcp_mux: supported.rtcp_mux && offered.rtcp_mux,
+        ..Default::default()
+    };
+
+    for &payload in &offered.payloads {
+        let local_payload = match matching_payload(supported, offered, payload) {
+            Some(local_payload) => local_payload,
+            None => continue,
+        };
+
+        media.payloads.push(payload);
+        media
+            .rtpmap
+            .extend(offered.codec_for_payload(payload).cloned());
+        media
+            .fmtp
+            .extend(supported.fmtp_for_payload(local_payload).map(|fmtp| Fmtp {
+                payload: Format::Payload(payload),
+                config: fmtp.config.clone(),
+                line: SourceLine::default(),
+            }));
+        media.rtc_fb.extend(
+            offered
+                .rtc_fb
+                .iter()
+                .filter(|rtc_fb| rtc_fb.payload_type() == Some(payload))
+                .filter(|rtc_fb| {
+                    supported.rtc_fb.iter().any(|local_rtc_fb| {
+                        local_rtc_fb.applies_to(local_payload)
+                            && local_rtc_fb.r#type == rtc_fb.r#type
+                            && local_rtc_fb.parameter == rtc_fb.parameter
+                    })
+                })
+                .cloned(),
+        );
+    }
+
+    media.formats = offered
+        .formats
+        .iter()
+        .filter(|format| supported.formats.contains(format))
+        .cloned()
+        .collect();
+
+    if media.payloads.is_empty() && media.formats.is_empty() {
+        return None;
+    }
+
+    if !media.payloads.is_empty() {
+        media.direction = Some(answer_direction(
+            supported.effective_direction(local),
+            offered.effective_direction(offer),
+        ));
+    }
+
+    Some(media)
+}
+
+// find the local payload type of an offered codec: dynamic payload types are
+// matched on their rtpmap, static ones on their number
+fn matching_payload(supported: &Media, offered: &Media, payload: u8) -> Option<u8> {
+    match offered.codec_for_payload(payload) {
+        Some(offered_rtpmap) => supported
+            .rtpmap
+            .iter()
+            .find(|rtpmap| {
+                rtpmap.codec.eq_ignore_ascii_case(offered_rtpmap.codec)
+                    && rtpmap.rate == offered_rtpmap.rate
+                    && rtpmap.channels.unwrap_or(1) == offered_rtpmap.channels.unwrap_or(1)
+            })
+            .map(|rtpmap| rtpmap.payload),
+        None => supported
+            .payloads
+            .iter()
+            .cloned()
+            .find(|&local_payload| local_payload == payload && payload < 96),
+    }
+}
+
+// we send what the offerer receives and receive what it sends
+fn answer_direction(local: Direction, offered: Direction) -> Direction {
+    Direction::from_capabilities(
+        local.sends() && offered.receives(),
+        local.receives() && offered.sends(),
+    )
+}
+
+// the DTLS role of the answerer (RFC 5763)
+fn answer_setup<'a>(local: Option<&'a str>, offered: &'a str) -> &'a str {
+    match (offered, local) {
+        ("active", _) => "passive",
+        ("passive", _) => "active",
+        (_, Some(local @ ("active" | "passive"))) => local,
+        _ => "active",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::Rtpmap;
+
+    const LOCAL: &str = "v=0
+o=- 1 0 IN IP4 198.51.100.1
+s=-
+t=0 0
+a=setup:actpass
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0
+a=rtpmap:111 opus/48000/2
+a=rtpmap:0 PCMU/8000
+a=fmtp:111 minptime=10;useinbandfec=1
+a=rtcp-fb:111 transport-cc
+a=rtcp-mux
+a=sendrecv
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel
+a=sctp-port:5000";
+
+    const OFFER: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=setup:actpass
+a=group:BUNDLE 0 1 2
+m=audio 54400 UDP/TLS/RTP/SAVPF 9 0 96
+a=mid:0
+a=rtcp-mux
+a=rtpmap:9 G722/8000
+a=rtpmap:0 PCMU/8000
+a=rtpmap:96 opus/48000/2
+a=rtcp-fb:96 transport-cc
+a=rtcp-fb:96 nack
+a=sendonly
+m=video 55400 UDP/TLS/RTP/SAVPF 97
+a=mid:1
+a=rtcp-mux
+a=rtpmap:97 VP8/90000
+m=application 56400 UDP/DTLS/SCTP webrtc-datachannel
+a=mid:2
+a=sctp-port:5000";
+
+    #[test]
+    fn it_answers_an_offer() {
+        let local = Sdp::parse(LOCAL).unwrap();
+        let offer = Sdp::parse(OFFER).unwrap();
+        let answer = answer(&local, &offer);
+        let (audio, video, application) = (&answer.media[0], &answer.media[1], &answer.media[2]);
+
+        assert_eq!(answer.setup, Some("active"));
+        assert_eq!(answer.media.len(), 3);
+        assert_eq!(
+            answer.groups,
+            vec![Group {
+                semantics: "BUNDLE",
+                mids: vec!["0", "2"],
+            }]
+        );
+        assert_eq!(answer.media_by_mid("2"), Some(application));
+        assert!(answer.to_sdp_string().contains("a=group:BUNDLE 0 2\r\n"));
+
+        assert_eq!(audio.port, 9);
+        assert_eq!(audio.mid, Some("0"));
+        assert!(audio.rtcp_mux);
+        assert_eq!(audio.payloads, vec![0, 96]);
+        assert_eq!(audio.direction, Some(Direction::RecvOnly));
+        assert_eq!(
+            audio.rtpmap,
+            vec![
+                Rtpmap {
+                    codec: "PCMU",
+                    payload: 0,
+                    rate: Some(8000),
+                    channels: None,
+                    ..Default::default()
+                },
+                Rtpmap {
+                    codec: "opus",
+                    payload: 96,
+                    rate: Some(48000),
+                    channels: Some(2),
+                    ..Default::default()
+                },
+            ]
+        );
+        assert_eq!(
+            audio.fmtp,
+            vec![Fmtp {
+                payload: Format::Payload(96),
+                config: "minptime=10;useinbandfec=1".into(),
+                line: SourceLine::default(),
+            }]
+        );
+        assert_eq!(audio.rtc_fb.len(), 1);
+        assert_eq!(audio.rtc_fb[0].r#type, "transport-cc");
+
+        assert_eq!(video.port, 0);
+        assert_eq!(video.payloads, vec![97]);
+        assert!(video.rtpmap.is_empty());
+
+        assert_eq!(application.port, 9);
+        assert_eq!(application.mid, Some("2"));
+        assert!(!application.rtcp_mux);
+        assert_eq!(application.formats, vec!["webrtc-datachannel"]);
+        assert_eq!(
+            application.application.as_ref().unwrap().sctp_port,
+            Some(5000)
+        );
+    }
+
+    #[test]
+    fn it_mirrors_directions() {
+        use Direction::*;
+
+        assert_eq!(answer_direction(SendRecv, SendOnly), RecvOnly);
+        assert_eq!(answer_direction(SendRecv, RecvOnly), SendOnly);
+        assert_eq!(answer_direction(SendRecv, SendRecv), SendRecv);
+        assert_eq!(answer_direction(RecvOnly, RecvOnly), Inactive);
+        assert_eq!(answer_direction(SendRecv, Inactive), Inactive);
+    }
+}
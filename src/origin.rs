@@ -1,5 +1,8 @@
+use crate::address::parse_ip;
 use crate::error::Result;
-use crate::utils::{parse_number, parse_str};
+use crate::utils::{parse_number, parse_saturating, parse_str};
+use core::fmt;
+use core::net::IpAddr;
 
 /// SDP Origin
 ///
@@ -14,7 +17,7 @@ use crate::utils::{parse_number, parse_str};
 /// IP address type (version 4) and unicast address of the machine which
 /// created the SDP. These three values are not relevant for the negotiation.
 ///
-#[derive(Debug, Default, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Origin<'a> {
     pub username: &'a str,
     pub session_id: u64,
@@ -25,14 +28,14 @@ pub struct Origin<'a> {
 }
 
 impl<'a> Origin<'a> {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let username = parse_str(split.next(), 1)?;
-        let session_id = parse_number::<u64>(split.next(), 2)?;
-        let session_version = parse_number::<u64>(split.next(), 3)?;
-        let network_type = parse_str(split.next(), 4)?;
-        let ip_type = parse_str(split.next(), 5)?;
-        let ip_address = parse_str(split.next(), 6)?;
+        let username = parse_str(split.next(), "username")?;
+        let session_id = parse_number::<u64>(split.next(), "session id")?;
+        let session_version = parse_number::<u64>(split.next(), "session version")?;
+        let network_type = parse_str(split.next(), "network type")?;
+        let ip_type = parse_str(split.next(), "ip type")?;
+        let ip_address = parse_str(split.next(), "ip address")?;
 
         Ok(Self {
             username,
@@ -43,6 +46,49 @@ impl<'a> Origin<'a> {
             ip_address,
         })
     }
+
+    // the origin of a broken gateway, with fields separated by any whitespace
+    // and session ids or versions overflowing u64, and whether one did
+    pub(crate) fn new_tolerant(value: &'a str) -> Result<(Self, bool)> {
+        let mut split = value.split_whitespace();
+        let username = parse_str(split.next(), "username")?;
+        let (session_id, id_overflow) = parse_saturating(split.next(), "session id")?;
+        let (session_version, version_overflow) =
+            parse_saturating(split.next(), "session version")?;
+        let network_type = parse_str(split.next(), "network type")?;
+        let ip_type = parse_str(split.next(), "ip type")?;
+        let ip_address = parse_str(split.next(), "ip address")?;
+        let origin = Self {
+            username,
+            session_id,
+            session_version,
+            network_type,
+            ip_type,
+            ip_address,
+        };
+
+        Ok((origin, id_overflow || version_overflow))
+    }
+
+    /// The unicast address as an IP, checked against the IP4 or IP6 address type
+    pub fn addr(&self) -> Result<IpAddr> {
+        parse_ip(self.ip_address, Some(self.ip_type))
+    }
+}
+
+impl<'a> fmt::Display for Origin<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.username,
+            self.session_id,
+            self.session_version,
+            self.network_type,
+            self.ip_type,
+            self.ip_address
+        )
+    }
 }
 
 #[cfg(test)]
@@ -63,5 +109,19 @@ mod tests {
         };
 
         assert_eq!(parsed, expected);
+        assert!(parsed.addr().unwrap().is_loopback());
+    }
+
+    #[test]
+    fn it_parses_the_origin_of_a_broken_gateway() {
+        let origin = "-  +123 99999999999999999999999 IN IP4 127.0.0.1";
+        let (parsed, overflow) = Origin::new_tolerant(origin).unwrap();
+
+        assert!(Origin::new(origin).is_err());
+        assert!(overflow);
+        assert_eq!(parsed.session_id, 123);
+        assert_eq!(parsed.session_version, u64::MAX);
+        assert_eq!(parsed.ip_address, "127.0.0.1");
+        assert!(Origin::new_tolerant("- 12a 2 IN IP4 127.0.0.1").is_err());
     }
 }
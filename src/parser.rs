@@ -0,0 +1,1099 @@
+use crate::error::{Error, Result};
+use crate::extension::AttributeParser;
+use crate::lossless;
+use crate::media_id::MidIndex;
+use crate::origin::Origin;
+use crate::reader::SdpLine;
+use crate::sdp::Sdp;
+use crate::warning::{self, Warning, WarningKind};
+use alloc::{collections::BTreeSet, format, string::String, string::ToString, sync::Arc, vec::Vec};
+use core::fmt;
+
+/// Incremental SDP parser
+///
+/// let mut parser = SdpParser::new();
+/// parser.feed_line("v=0")?;
+/// let sdp = parser.finish()?;
+///
+/// Lines are fed one at a time, e.g. as they arrive over a SIP TCP stream or a
+/// chunked WebSocket, so the message never has to be buffered and re-scanned.
+/// Parsed values borrow from the lines, which must outlive the resulting Sdp.
+/// Line endings are stripped and blank lines at the end of the message are
+/// ignored.
+#[derive(Debug, Default, Clone)]
+pub struct SdpParser<'a> {
+    sdp: Sdp<'a>,
+    line: usize,
+    blank: Option<(usize, &'a str)>,
+    seen: Vec<char>,
+    section: Section,
+    options: ParseOptions,
+    order: Option<usize>,
+
+    /// The lines of the current section, to find duplicates in lenient mode
+    section_lines: BTreeSet<&'a str>,
+
+    /// The singleton lines of the current section, as their type, the name
+    /// of an attribute and the payload type of a rtpmap
+    singletons: BTreeSet<(char, &'a str, &'a str)>,
+    section_attributes: usize,
+    warnings: Vec<Warning>,
+
+    /// Keep parsing after an invalid line, see `Sdp::parse_all_errors`
+    collect_errors: bool,
+    errors: Vec<Error>,
+
+    /// A limit was exceeded while collecting errors, so the rest of the
+    /// message is ignored rather than adding an error for each line
+    exceeded: bool,
+
+    /// The m line of the current section failed, so its lines are skipped
+    /// rather than taken for lines of the section before it
+    skipping: bool,
+
+    /// The span of the current media section, the parent of its line spans
+    #[cfg(feature = "tracing")]
+    media_span: Option<tracing::Span>,
+}
+
+/// Options for parsing a SDP
+///
+/// let mut options = ParseOptions::default();
+/// options.register(SourceFilter);
+/// let sdp = Sdp::parse_with(sdp_string, &options)?;
+///
+/// Attributes the crate doesn't know are an error, unless a parser for them
+/// is registered, see `AttributeParser`.
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    /// Reject lines out of the order of RFC 8866 section 5, e.g. a t= line
+    /// after the session attributes, to check the SDP of our own generators
+    pub enforce_ordering: bool,
+
+    /// Keep every line as it appeared, see `Sdp::to_sdp_string_lossless`
+    pub lossless: bool,
+
+    /// Skip unknown attributes, clamp numbers out of range and read the o=
+    /// lines of broken gateways rather than failing, and report those and
+    /// duplicate lines as warnings, see `SdpParser::warnings`
+    pub lenient: bool,
+
+    /// What to do with a second line of a type its section has one of, e.g.
+    /// a second o= line
+    pub duplicates: DuplicatePolicy,
+
+    /// Bounds on the size of the message, for SDPs from untrusted peers
+    pub limits: Limits,
+    attribute_parsers: Vec<Arc<dyn AttributeParser>>,
+}
+
+impl ParseOptions {
+    // parse the attribute of `parser` into an extension, replacing any parser
+    // registered for the same name
+    pub fn register(&mut self, parser: impl AttributeParser + 'static) -> &mut Self {
+        self.attribute_parsers
+            .retain(|registered| registered.name() != parser.name());
+        self.attribute_parsers.push(Arc::new(parser));
+        self
+    }
+
+    pub(crate) fn attribute_parser(&self, name: &str) -> Option<&dyn AttributeParser> {
+        self.attribute_parsers
+            .iter()
+            .find(|parser| parser.name() == name)
+            .map(|parser| parser.as_ref())
+    }
+}
+
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = self.attribute_parsers.iter().map(|p| p.name()).collect();
+
+        f.debug_struct("ParseOptions")
+            .field("enforce_ordering", &self.enforce_ordering)
+            .field("lossless", &self.lossless)
+            .field("lenient", &self.lenient)
+            .field("duplicates", &self.duplicates)
+            .field("limits", &self.limits)
+            .field("attribute_parsers", &names)
+            .finish()
+    }
+}
+
+/// The largest message a parse accepts, unbounded by default
+///
+/// let mut options = ParseOptions::default();
+/// options.limits = Limits::untrusted();
+///
+/// A message over a limit fails with `Error::LimitExceeded` as soon as the
+/// line going over it is fed, so a hostile peer can't make a server parse or
+/// allocate more than the limits allow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub max_lines: Option<usize>,
+    pub max_media: Option<usize>,
+
+    /// The a= lines of the session or of a media section
+    pub max_attributes: Option<usize>,
+
+    /// In bytes, without the line ending
+    pub max_line_length: Option<usize>,
+}
+
+impl Limits {
+    /// Limits well above the SDPs of browsers and SIP phones, e.g. for a
+    /// server on the public internet
+    pub fn untrusted() -> Self {
+        Self {
+            max_lines: Some(10_000),
+            max_media: Some(256),
+            max_attributes: Some(1_000),
+            max_line_length: Some(4_096),
+        }
+    }
+}
+
+/// What to do with a repeated singleton line
+///
+/// The session has one of each of the v, o, s, i, u, c and k lines, a media
+/// section one i and one k line, and both at most one a=mid, a=ice-ufrag,
+/// a=ice-pwd and a=setup.  A media section also has one a=rtpmap per payload
+/// type.  A broken client repeating one usually sent a
+/// malformed offer, which the default hides by keeping the last line.  In
+/// lenient mode the repeated lines are also reported as duplicates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// The later line replaces the earlier one
+    #[default]
+    LastWins,
+
+    /// The later line is skipped
+    FirstWins,
+
+    /// The later line is an error
+    Reject,
+}
+
+/// The part of the message the next line belongs to
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) enum Section {
+    #[default]
+    Session,
+    /// The media section at this index, from its m line to the next one
+    Media(usize),
+}
+
+// the lines every SDP must have (RFC 4566)
+const REQUIRED_LINES: [char; 4] = ['v', 'o', 's', 't'];
+
+// the order of the lines of a section (RFC 8866 section 5), as the line types
+// of each step, whether the step repeats and whether it is required
+type Step = (&'static str, bool, bool);
+
+const SESSION_ORDER: [Step; 14] = [
+    ("v", false, true),
+    ("o", false, true),
+    ("s", false, true),
+    ("i", false, false),
+    ("u", false, false),
+    ("e", true, false),
+    ("p", true, false),
+    ("c", false, false),
+    ("b", true, false),
+    ("tr", true, true),
+    ("z", false, false),
+    ("k", false, false),
+    ("a", true, false),
+    ("m", false, false),
+];
+
+// the line types a section has one of at most
+// the line types of a media section, the others only describe the session
+const MEDIA_FIELDS: [char; 4] = ['i', 'c', 'b', 'k'];
+
+const SESSION_SINGLETONS: [char; 8] = ['v', 'o', 's', 'i', 'u', 'c', 'z', 'k'];
+const MEDIA_SINGLETONS: [char; 2] = ['i', 'k'];
+const SINGLETON_ATTRIBUTES: [&str; 4] = ["mid", "ice-ufrag", "ice-pwd", "setup"];
+
+const MEDIA_ORDER: [Step; 6] = [
+    ("i", false, false),
+    ("c", true, false),
+    ("b", true, false),
+    ("k", false, false),
+    ("a", true, false),
+    ("m", false, false),
+];
+
+impl<'a> SdpParser<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    // a parser keeping the errors of the invalid lines instead of failing
+    pub(crate) fn collecting_errors(options: ParseOptions) -> Self {
+        Self {
+            options,
+            collect_errors: true,
+            ..Self::default()
+        }
+    }
+
+    // parse the next line of the message
+    pub fn feed_line(&mut self, raw: &'a str) -> Result<()> {
+        if self.exceeded {
+            return Ok(());
+        }
+
+        match self.feed(raw) {
+            Err(error) if self.collect_errors => {
+                self.exceeded = matches!(error.kind(), Error::LimitExceeded { .. });
+                self.errors.push(error);
+                Ok(())
+            }
+            fed => fed,
+        }
+    }
+
+    fn feed(&mut self, raw: &'a str) -> Result<()> {
+        let line = raw.trim_end_matches(['\r', '\n']);
+        self.line += 1;
+
+        let limits = self.options.limits;
+        check_limit("lines", self.line, limits.max_lines)
+            .and_then(|_| check_limit("line length", line.len(), limits.max_line_length))
+            .map_err(|error| error.at_line(self.line, line))?;
+
+        // a blank line is only an error if more lines follow it
+        if line.trim().is_empty() {
+            self.blank.get_or_insert((self.line, line));
+            self.record(raw);
+            return Ok(());
+        }
+
+        if let Some((number, blank)) = self.blank {
+            let fed = SdpLine::new(blank)
+                .map(|_| ())
+                .map_err(|error| error.at_line(number, blank));
+
+            match fed {
+                Err(error) if self.collect_errors => {
+                    self.errors.push(error);
+                    self.blank = None;
+                }
+                fed => return fed,
+            }
+        }
+
+        if self.skipping && !line.starts_with("m=") {
+            return Ok(());
+        }
+
+        self.skipping = self.collect_errors && line.starts_with("m=");
+
+        let sdp_line = SdpLine::new(line).map_err(|error| error.at_line(self.line, line))?;
+
+        match sdp_line {
+            SdpLine::Media(_) => check_limit("media", self.sdp.media.len() + 1, limits.max_media),
+            SdpLine::Attribute { .. } => {
+                self.section_attributes += 1;
+                check_limit("attributes", self.section_attributes, limits.max_attributes)
+            }
+            SdpLine::Field { .. } => Ok(()),
+        }
+        .map_err(|error| error.at_line(self.line, line))?;
+
+        if self.options.enforce_ordering {
+            self.check_order(&sdp_line)?;
+        }
+
+        // e.g. an o= or s= line after an m= line would replace the session's
+        if let (Section::Media(_), SdpLine::Field { key, .. }) = (self.section, sdp_line) {
+            if !MEDIA_FIELDS.contains(&key) {
+                return Err(
+                    Error::invalid_line(format!("'{}=' line in a media section", key))
+                        .at_line(self.line, line),
+                );
+            }
+        }
+
+        if let SdpLine::Field { key, .. } = sdp_line {
+            if REQUIRED_LINES.contains(&key) && !self.seen.contains(&key) {
+                self.seen.push(key);
+            }
+        }
+
+        let repeated = match self.singleton(&sdp_line) {
+            Some(singleton) if !self.singletons.insert(singleton) => Some(singleton),
+            _ => None,
+        };
+
+        if let Some((key, name, payload)) = repeated {
+            let kept = match self.options.duplicates {
+                DuplicatePolicy::Reject => {
+                    let attribute = match payload {
+                        "" => name.into(),
+                        payload => format!("{}:{}", name, payload),
+                    };
+
+                    return Err(Error::Duplicate {
+                        key,
+                        attribute: (!name.is_empty()).then_some(attribute),
+                    }
+                    .at_line(self.line, line));
+                }
+                DuplicatePolicy::FirstWins => "the first",
+                DuplicatePolicy::LastWins => "this",
+            };
+
+            if self.options.lenient {
+                let message = format!("{} is repeated, kept {} line", line_key(&sdp_line), kept);
+                self.warn(WarningKind::Duplicate, message);
+            }
+
+            if self.options.duplicates == DuplicatePolicy::FirstWins {
+                self.section_lines.insert(line);
+                self.record(raw);
+                return Ok(());
+            }
+        }
+
+        let media_count = self.sdp.media.len();
+
+        #[cfg(feature = "tracing")]
+        let span = self.line_span(&sdp_line);
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+
+        let parsed = self
+            .sdp
+            .parse_line(sdp_line, self.line, self.section, &self.options);
+
+        match parsed {
+            Err(error) if self.options.lenient => self.recover(error, sdp_line),
+            parsed => parsed,
+        }
+        .map_err(|error| error.at_line(self.line, line))?;
+
+        if let SdpLine::Media(_) = sdp_line {
+            self.section = Section::Media(media_count);
+            self.skipping = false;
+            self.order = None;
+            self.section_lines.clear();
+            self.singletons.clear();
+            self.section_attributes = 0;
+        }
+
+        if self.options.lenient && !self.section_lines.insert(line) && repeated.is_none() {
+            self.warn(WarningKind::Duplicate, format!("'{}' is repeated", line));
+        }
+
+        self.record(raw);
+
+        Ok(())
+    }
+
+    // a span per line, inside the span of its media section, and one per media
+    // section from its m line
+    #[cfg(feature = "tracing")]
+    fn line_span(&mut self, sdp_line: &SdpLine) -> tracing::Span {
+        let (key, attribute) = match *sdp_line {
+            SdpLine::Media(media) => {
+                let index = self.sdp.media.len();
+                self.media_span = Some(tracing::debug_span!("sdp_media", index, media));
+                ('m', None)
+            }
+            SdpLine::Attribute { name, .. } => ('a', Some(name)),
+            SdpLine::Field { key, .. } => (key, None),
+        };
+
+        match &self.media_span {
+            Some(media_span) => tracing::trace_span!(
+                parent: media_span,
+                "sdp_line",
+                line = self.line,
+                key = %key,
+                attribute
+            ),
+            None => tracing::trace_span!("sdp_line", line = self.line, key = %key, attribute),
+        }
+    }
+
+    // the type of a line its section has one of, the name of an attribute and
+    // the payload type of a rtpmap
+    fn singleton(&self, sdp_line: &SdpLine<'a>) -> Option<(char, &'a str, &'a str)> {
+        let fields: &[char] = match self.section {
+            Section::Session => &SESSION_SINGLETONS,
+            Section::Media(_) => &MEDIA_SINGLETONS,
+        };
+
+        match *sdp_line {
+            SdpLine::Field { key, .. } if fields.contains(&key) => Some((key, "", "")),
+            SdpLine::Attribute { name, .. } if SINGLETON_ATTRIBUTES.contains(&name) => {
+                Some(('a', name, ""))
+            }
+            SdpLine::Attribute {
+                name: "rtpmap",
+                value: Some(value),
+            } if self.section != Section::Session => {
+                Some(('a', "rtpmap", value.split(' ').next().unwrap_or_default()))
+            }
+            _ => None,
+        }
+    }
+
+    // skip a line of an unknown attribute, or parse a number out of range as
+    // the largest value of its attribute
+    fn recover(&mut self, error: Error, sdp_line: SdpLine<'a>) -> Result<()> {
+        if let Error::UnknownAttribute { .. } = error {
+            self.warn(WarningKind::UnknownAttribute, error.to_string());
+            return Ok(());
+        }
+
+        let (name, value) = match sdp_line {
+            SdpLine::Attribute {
+                name,
+                value: Some(value),
+            } => (name, value),
+            SdpLine::Field { key: 'o', value } => return self.recover_origin(error, value),
+            _ => return Err(error),
+        };
+
+        match warning::clamp(name, value) {
+            Some(max) => {
+                let clamped = SdpLine::Attribute {
+                    name,
+                    value: Some(max),
+                };
+                self.sdp
+                    .parse_line(clamped, self.line, self.section, &self.options)?;
+                self.warn(
+                    WarningKind::Clamped,
+                    format!("{} {} is out of range, read as {}", name, value, max),
+                );
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    // read the o= line of a broken gateway, saturating numbers overflowing u64
+    fn recover_origin(&mut self, error: Error, value: &'a str) -> Result<()> {
+        let (origin, overflow) = Origin::new_tolerant(value).map_err(|_| error.clone())?;
+        let kind = match overflow {
+            true => WarningKind::Clamped,
+            false => WarningKind::Malformed,
+        };
+
+        self.sdp.origin = origin;
+        self.warn(kind, format!("{}, read as '{}'", error, self.sdp.origin));
+
+        Ok(())
+    }
+
+    fn warn(&mut self, kind: WarningKind, message: String) {
+        self.warnings.push(Warning {
+            kind,
+            line: self.line,
+            message,
+        });
+    }
+
+    // the issues recovered from so far, in lenient mode
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    // keep the line in its section for a lossless parse
+    fn record(&mut self, raw: &'a str) {
+        if !self.options.lossless {
+            return;
+        }
+
+        match self.section {
+            Section::Session => self.sdp.raw.lines.push(raw),
+            Section::Media(index) => self.sdp.media[index].raw.lines.push(raw),
+        }
+    }
+
+    // the line must be one of the steps from the last line's, up to the next
+    // required one
+    fn check_order(&mut self, sdp_line: &SdpLine) -> Result<()> {
+        let found = match sdp_line {
+            SdpLine::Field { key, .. } => *key,
+            SdpLine::Media(_) => 'm',
+            SdpLine::Attribute { .. } => 'a',
+        };
+        let order: &[Step] = match self.section {
+            Section::Session => &SESSION_ORDER,
+            Section::Media(_) => &MEDIA_ORDER,
+        };
+        let start = match self.order {
+            Some(last) if order[last].1 => last,
+            Some(last) => last + 1,
+            None => 0,
+        };
+        let mut allowed = Vec::new();
+
+        for (index, &(_, _, required)) in order.iter().enumerate().skip(start) {
+            allowed.push(index);
+
+            if required && self.order != Some(index) {
+                break;
+            }
+        }
+
+        // a step of several line types starts with its first one, e.g. an r=
+        // line follows its t= line
+        let current = self.order;
+        let accepts = |index: usize| match current == Some(index) {
+            true => order[index].0.contains(found),
+            false => order[index].0.starts_with(found),
+        };
+
+        match allowed.iter().find(|&&index| accepts(index)) {
+            Some(&index) => {
+                self.order = Some(index);
+                Ok(())
+            }
+            None => Err(Error::OutOfOrder {
+                expected: expected_lines(order, &allowed, current),
+                found,
+                line: self.line,
+            }),
+        }
+    }
+
+    // the number of lines fed so far
+    pub fn lines(&self) -> usize {
+        self.line
+    }
+
+    // the sdp as far as it was parsed, with the errors of its invalid lines
+    // and of the required lines it lacks
+    pub(crate) fn finish_with_errors(mut self) -> (Sdp<'a>, Vec<Error>) {
+        let seen = &self.seen;
+        let missing = REQUIRED_LINES
+            .iter()
+            .filter(|key| !seen.contains(key))
+            .map(|&key| Error::MissingRequiredLine { key });
+
+        self.errors.extend(missing);
+        self.sdp.mid_index = MidIndex::new(&self.sdp.media);
+
+        if self.options.lossless {
+            lossless::snapshot(&mut self.sdp);
+        }
+
+        (self.sdp, self.errors)
+    }
+
+    // the SDP parsed from all the lines fed, which must include the required lines
+    pub fn finish(self) -> Result<Sdp<'a>> {
+        match REQUIRED_LINES.iter().find(|key| !self.seen.contains(key)) {
+            Some(&key) => Err(Error::MissingRequiredLine { key }),
+            None => {
+                let mut sdp = self.sdp;
+                sdp.mid_index = MidIndex::new(&sdp.media);
+
+                if self.options.lossless {
+                    lossless::snapshot(&mut sdp);
+                }
+
+                Ok(sdp)
+            }
+        }
+    }
+}
+
+// fail once `count` goes over the limit, if any
+fn check_limit(limit: &'static str, count: usize, max: Option<usize>) -> Result<()> {
+    match max {
+        Some(max) if count > max => Err(Error::LimitExceeded { limit, max }),
+        _ => Ok(()),
+    }
+}
+
+// the type of a line and the name of an attribute, e.g. 'o=' or 'a=mid'
+fn line_key(sdp_line: &SdpLine) -> String {
+    match sdp_line {
+        SdpLine::Field { key, .. } => format!("'{}='", key),
+        SdpLine::Media(_) => "'m='".into(),
+        SdpLine::Attribute { name, .. } => format!("'a={}'", name),
+    }
+}
+
+// the line types of the steps, e.g. 'i=', 'c=' or 'a=', only the first one of
+// a step other than the current one
+fn expected_lines(order: &[Step], steps: &[usize], current: Option<usize>) -> String {
+    let keys = steps
+        .iter()
+        .flat_map(|&step| {
+            let keys = order[step].0.chars();
+            let count = if current == Some(step) {
+                keys.clone().count()
+            } else {
+                1
+            };
+            keys.take(count)
+        })
+        .map(|key| format!("'{}='", key))
+        .collect::<Vec<_>>();
+
+    match keys.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_lines_incrementally() {
+        let mut parser = SdpParser::new();
+
+        for line in &["v=0", "o=- 20518 0 IN IP4 203.0.113.1", "s=", "t=0 0"] {
+            parser.feed_line(line).unwrap();
+        }
+
+        parser.feed_line("m=audio 54400 RTP/SAVPF 0").unwrap();
+        parser.feed_line("a=rtpmap:0 PCMU/8000").unwrap();
+
+        assert_eq!(parser.lines(), 6);
+
+        let sdp = parser.finish().unwrap();
+
+        assert_eq!(sdp.origin.session_id, 20518);
+        assert_eq!(sdp.media[0].rtpmap[0].codec, "PCMU");
+    }
+
+    #[test]
+    fn it_ignores_trailing_blank_lines() {
+        let mut parser = SdpParser::new();
+
+        for line in &[
+            "v=0\r",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=\r",
+            "t=0 0",
+            "",
+            "\r",
+        ] {
+            parser.feed_line(line).unwrap();
+        }
+
+        assert_eq!(parser.finish().unwrap().session_name, "");
+
+        let mut parser = SdpParser::new();
+        parser.feed_line("v=0").unwrap();
+        parser.feed_line("").unwrap();
+
+        match parser.feed_line("s=") {
+            Err(Error::Parse { line, .. }) => assert_eq!(line, 2),
+            result => panic!("expected a parse error, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn it_requires_the_session_lines() {
+        let mut parser = SdpParser::new();
+        parser.feed_line("v=0").unwrap();
+        parser.feed_line("s=").unwrap();
+
+        assert_eq!(
+            parser.finish().unwrap_err(),
+            Error::MissingRequiredLine { key: 'o' }
+        );
+    }
+
+    #[test]
+    fn it_enforces_the_order_of_lines() {
+        let options = ParseOptions {
+            enforce_ordering: true,
+            ..Default::default()
+        };
+
+        let feed = |lines: &[&'static str]| {
+            let mut parser = SdpParser::with_options(options.clone());
+            lines.iter().try_for_each(|line| parser.feed_line(line))
+        };
+
+        assert!(feed(&[
+            "v=0",
+            "o=- 20518 0 IN IP4 203.0.113.1",
+            "s=",
+            "c=IN IP4 203.0.113.1",
+            "t=0 0",
+            "a=ice-lite",
+            "m=audio 54400 RTP/SAVPF 0",
+            "c=IN IP4 203.0.113.1",
+            "a=rtpmap:0 PCMU/8000",
+            "m=video 55400 RTP/SAVPF 96",
+        ])
+        .is_ok());
+        assert_eq!(
+            feed(&["o=- 20518 0 IN IP4 203.0.113.1"]).unwrap_err(),
+            Error::OutOfOrder {
+                expected: "'v='".into(),
+                found: 'o',
+                line: 1,
+            }
+        );
+        assert_eq!(
+            feed(&[
+                "v=0",
+                "o=- 1 0 IN IP4 203.0.113.1",
+                "s=",
+                "a=ice-lite",
+                "t=0 0"
+            ])
+            .unwrap_err()
+            .to_string(),
+            "'a=' on line 4 is out of order, expected 'i=', 'u=', 'e=', 'p=', 'c=', 'b=' or 't='"
+        );
+        assert_eq!(
+            feed(&["v=0", "o=- 1 0 IN IP4 203.0.113.1", "s=", "r=7d 1h 0 25h"]).unwrap_err(),
+            Error::OutOfOrder {
+                expected: "'i=', 'u=', 'e=', 'p=', 'c=', 'b=' or 't='".into(),
+                found: 'r',
+                line: 4,
+            }
+        );
+        assert!(feed(&[
+            "v=0",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=",
+            "t=3034423619 3042462419",
+            "r=7d 1h 0 25h",
+            "t=0 0",
+            "m=audio 9 RTP/AVP 0",
+        ])
+        .is_ok());
+
+        let time = feed(&[
+            "v=0",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=",
+            "t=0 0",
+            "a=ice-lite",
+            "t=0 0",
+        ]);
+
+        assert_eq!(time.unwrap_err().line(), Some(6));
+        assert!(feed(&[
+            "v=0",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=",
+            "t=0 0",
+            "m=audio 9 RTP/AVP 0",
+            "a=sendrecv",
+            "c=IN IP4 203.0.113.1"
+        ])
+        .is_err());
+        assert!(SdpParser::new().feed_line("s=").is_ok());
+    }
+
+    #[test]
+    fn it_applies_the_duplicate_policy() {
+        let lines = [
+            "v=0",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=",
+            "c=IN IP4 203.0.113.1",
+            "c=IN IP4 203.0.113.2",
+            "t=0 0",
+            "m=audio 9 RTP/AVP 0",
+            "a=mid:0",
+            "a=mid:1",
+            "a=rtpmap:0 PCMU/8000",
+            "a=rtpmap:0 G711U/8000",
+        ];
+        let parse = |duplicates, lenient| {
+            let options = ParseOptions {
+                duplicates,
+                lenient,
+                ..Default::default()
+            };
+
+            let mut parser = SdpParser::with_options(options);
+            lines.iter().try_for_each(|line| parser.feed_line(line))?;
+            let warnings = parser.warnings().len();
+            parser.finish().map(|sdp| (sdp, warnings))
+        };
+
+        let (last, warnings) = parse(DuplicatePolicy::LastWins, false).unwrap();
+
+        assert_eq!(last.connection.unwrap().ip_address, "203.0.113.2");
+        assert_eq!(last.media[0].mid, Some("1"));
+        assert_eq!(last.media[0].rtpmap_for(0).unwrap().codec, "G711U");
+        assert_eq!(last.media[0].rtpmap.len(), 1);
+        assert_eq!(warnings, 0);
+
+        let (first, warnings) = parse(DuplicatePolicy::FirstWins, true).unwrap();
+
+        assert_eq!(first.connection.unwrap().ip_address, "203.0.113.1");
+        assert_eq!(first.media[0].mid, Some("0"));
+        assert_eq!(first.media[0].rtpmap_for(0).unwrap().codec, "PCMU");
+        assert_eq!(first.media[0].rtpmap.len(), 1);
+        assert_eq!(warnings, 3);
+
+        let error = parse(DuplicatePolicy::Reject, false).unwrap_err();
+
+        assert_eq!(error.line(), Some(5));
+        assert_eq!(
+            error.kind(),
+            &Error::Duplicate {
+                key: 'c',
+                attribute: None
+            }
+        );
+        assert_eq!(
+            error.kind().to_string(),
+            "'c=' may only appear once in its section"
+        );
+
+        let options = ParseOptions {
+            duplicates: DuplicatePolicy::Reject,
+            ..Default::default()
+        };
+        let mut parser = SdpParser::with_options(options);
+        let error = lines
+            .iter()
+            .filter(|line| !line.ends_with(".2"))
+            .try_for_each(|line| parser.feed_line(line))
+            .unwrap_err();
+
+        assert_eq!(
+            error.kind(),
+            &Error::Duplicate {
+                key: 'a',
+                attribute: Some("mid".into())
+            }
+        );
+        assert_eq!(
+            error.kind().to_string(),
+            "'a=mid' may only appear once in its section"
+        );
+
+        let options = ParseOptions {
+            duplicates: DuplicatePolicy::Reject,
+            ..Default::default()
+        };
+        let mut parser = SdpParser::with_options(options);
+        let error = lines
+            .iter()
+            .filter(|line| !line.ends_with(".2") && **line != "a=mid:1")
+            .try_for_each(|line| parser.feed_line(line))
+            .unwrap_err();
+
+        assert_eq!(error.line(), Some(9));
+        assert_eq!(
+            error.kind().to_string(),
+            "'a=rtpmap:0' may only appear once in its section"
+        );
+    }
+
+    #[test]
+    fn it_enforces_the_limits() {
+        let lines = [
+            "v=0",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=",
+            "t=0 0",
+            "m=audio 9 RTP/AVP 0",
+            "a=rtpmap:0 PCMU/8000",
+            "a=sendrecv",
+            "m=video 9 RTP/AVP 96",
+            "a=rtpmap:96 VP8/90000",
+        ];
+        let parse = |limits| {
+            let options = ParseOptions {
+                limits,
+                ..Default::default()
+            };
+            let mut parser = SdpParser::with_options(options);
+            lines.iter().try_for_each(|line| parser.feed_line(line))
+        };
+        let exceeded = |limits| {
+            let error = parse(limits).unwrap_err();
+            (error.kind().clone(), error.line().unwrap())
+        };
+
+        assert!(parse(Limits::untrusted()).is_ok());
+        assert_eq!(
+            exceeded(Limits {
+                max_lines: Some(8),
+                ..Default::default()
+            }),
+            (
+                Error::LimitExceeded {
+                    limit: "lines",
+                    max: 8
+                },
+                9
+            )
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_media: Some(1),
+                ..Default::default()
+            }),
+            (
+                Error::LimitExceeded {
+                    limit: "media",
+                    max: 1
+                },
+                8
+            )
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_attributes: Some(1),
+                ..Default::default()
+            }),
+            (
+                Error::LimitExceeded {
+                    limit: "attributes",
+                    max: 1
+                },
+                7
+            )
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_line_length: Some(20),
+                ..Default::default()
+            }),
+            (
+                Error::LimitExceeded {
+                    limit: "line length",
+                    max: 20
+                },
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn it_rejects_session_lines_in_a_media_section() {
+        let lines = [
+            "v=0",
+            "o=- 1 0 IN IP4 203.0.113.1",
+            "s=",
+            "t=0 0",
+            "m=audio 9 RTP/AVP 0",
+            "s=hijack",
+            "o=evil 9 9 IN IP4 10.0.0.1",
+        ];
+
+        for duplicates in [
+            DuplicatePolicy::LastWins,
+            DuplicatePolicy::FirstWins,
+            DuplicatePolicy::Reject,
+        ] {
+            let options = ParseOptions {
+                duplicates,
+                ..Default::default()
+            };
+            let mut parser = SdpParser::with_options(options);
+            let error = lines
+                .iter()
+                .try_for_each(|line| parser.feed_line(line))
+                .unwrap_err();
+
+            assert_eq!(error.line(), Some(6));
+            assert!(matches!(error.kind(), Error::InvalidLine { .. }));
+        }
+
+        // nor is the o= line read as the one of a broken gateway
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let mut parser = SdpParser::with_options(options);
+        let fed = [&lines[..5], &lines[6..]].concat();
+
+        assert!(fed
+            .iter()
+            .try_for_each(|line| parser.feed_line(line))
+            .is_err());
+
+        let mut parser = SdpParser::new();
+
+        for line in &lines[..5] {
+            parser.feed_line(line).unwrap();
+        }
+
+        parser.feed_line("c=IN IP4 203.0.113.2").unwrap();
+        parser.feed_line("b=AS:64").unwrap();
+
+        assert_eq!(parser.finish().unwrap().session_name, "");
+    }
+
+    #[test]
+    fn it_reports_the_line_fed() {
+        let mut parser = SdpParser::new();
+        parser.feed_line("v=0").unwrap();
+
+        match parser.feed_line("t=0 later") {
+            Err(Error::Parse { line, .. }) => assert_eq!(line, 2),
+            result => panic!("expected a parse error, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn it_collects_the_errors_of_blank_lines() {
+        let mut parser = SdpParser::collecting_errors(ParseOptions::default());
+
+        for line in &["v=0", "", "o=- 1 0 IN IP4 203.0.113.1", "s=", "", "t=0 0"] {
+            parser.feed_line(line).unwrap();
+        }
+
+        let (sdp, errors) = parser.finish_with_errors();
+        let lines = errors.iter().map(Error::line).collect::<Vec<_>>();
+
+        assert_eq!(lines, vec![Some(2), Some(5)]);
+        assert_eq!(sdp.origin.session_id, 1);
+    }
+
+    #[test]
+    fn it_stops_collecting_errors_over_a_limit() {
+        let options = ParseOptions {
+            limits: Limits {
+                max_lines: Some(4),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut parser = SdpParser::collecting_errors(options);
+
+        for line in &["v=0", "o=- 1 0 IN IP4 203.0.113.1", "s=", "t=0 0"] {
+            parser.feed_line(line).unwrap();
+        }
+
+        for _ in 0..1_000 {
+            parser.feed_line("a=unknown").unwrap();
+        }
+
+        let (_, errors) = parser.finish_with_errors();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind(),
+            &Error::LimitExceeded {
+                limit: "lines",
+                max: 4
+            }
+        );
+        assert_eq!(errors[0].line(), Some(5));
+    }
+}
@@ -0,0 +1,238 @@
+//! Payload type conflicts between media sections, and their remapping.
+//!
+//! A bridge between two endpoints that picked the same dynamic payload type
+//! for different codecs, e.g. 96 for VP8 on one side and H264 on the other,
+//! has to move one of them to a free payload type in every line naming it.
+
+use crate::error::{Error, Result};
+use crate::media::{Format, Media, Rtpmap};
+use crate::media_id::MediaId;
+use crate::sdp::Sdp;
+use alloc::{borrow::Cow, format, string::String, vec, vec::Vec};
+
+/// A payload type mapped to different codecs by the rtpmap lines of the media
+/// sections of a SDP
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadConflict<'a> {
+    pub payload: u8,
+
+    /// Each media section mapping the payload type, with its rtpmap
+    pub rtpmaps: Vec<(MediaId, Rtpmap<'a>)>,
+}
+
+pub(crate) fn conflicts<'a>(sdp: &Sdp<'a>) -> Vec<PayloadConflict<'a>> {
+    let mut conflicts: Vec<PayloadConflict<'a>> = vec![];
+
+    for (id, media) in sdp.media_ids().zip(&sdp.media) {
+        for rtpmap in &media.rtpmap {
+            match conflicts
+                .iter_mut()
+                .find(|conflict| conflict.payload == rtpmap.payload)
+            {
+                Some(conflict) => conflict.rtpmaps.push((id, rtpmap.clone())),
+                None => conflicts.push(PayloadConflict {
+                    payload: rtpmap.payload,
+                    rtpmaps: vec![(id, rtpmap.clone())],
+                }),
+            }
+        }
+    }
+
+    // a payload type only mapped to one codec isn't a conflict
+    conflicts.retain(|conflict| {
+        let (_, first) = &conflict.rtpmaps[0];
+        conflict
+            .rtpmaps
+            .iter()
+            .any(|(_, rtpmap)| !same_codec(first, rtpmap))
+    });
+    conflicts
+}
+
+// codec names are case-insensitive, and a missing channel count means 1
+fn same_codec(rtpmap: &Rtpmap, other: &Rtpmap) -> bool {
+    rtpmap.codec.eq_ignore_ascii_case(other.codec)
+        && rtpmap.rate == other.rate
+        && rtpmap.channels.unwrap_or(1) == other.channels.unwrap_or(1)
+}
+
+pub(crate) fn remap(sdp: &mut Sdp, old: u8, new: u8) -> Result<()> {
+    for media in &sdp.media {
+        check_remap(media, old, new)?;
+    }
+
+    for media in &mut sdp.media {
+        remap_media(media, old, new);
+    }
+
+    Ok(())
+}
+
+// the new payload type must be valid and free in a section using the old one
+pub(crate) fn check_remap(media: &Media, old: u8, new: u8) -> Result<()> {
+    if new > 127 {
+        return Err(Error::InvalidNumber {
+            field: "payload type",
+            value: format!("{}", new),
+        });
+    }
+
+    if old != new && media.payloads.contains(&old) && media.payloads.contains(&new) {
+        return Err(Error::invalid_value(
+            "payload type",
+            format!("{} is already used by the media section", new),
+        ));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn remap_media(media: &mut Media, old: u8, new: u8) {
+    for payload in media.payloads.iter_mut().filter(|p| **p == old) {
+        *payload = new;
+    }
+
+    for rtpmap in media.rtpmap.iter_mut().filter(|r| r.payload == old) {
+        rtpmap.payload = new;
+    }
+
+    for fmtp in &mut media.fmtp {
+        if fmtp.payload == Format::Payload(old) {
+            fmtp.payload = Format::Payload(new);
+        }
+
+        if fmtp.apt() == Some(old) {
+            fmtp.config = Cow::Owned(remap_apt(&fmtp.config, old, new));
+        }
+    }
+
+    for rtc_fb in media
+        .rtc_fb
+        .iter_mut()
+        .filter(|r| r.payload == Format::Payload(old))
+    {
+        rtc_fb.payload = Format::Payload(new);
+    }
+
+    for imageattr in media
+        .imageattr
+        .iter_mut()
+        .filter(|i| i.payload == Format::Payload(old))
+    {
+        imageattr.payload = Format::Payload(new);
+    }
+
+    for restriction in media
+        .rid
+        .iter_mut()
+        .flat_map(|rid| rid.restrictions.iter_mut())
+        .filter(|restriction| restriction.name == "pt")
+    {
+        if let Some(payloads) = &mut restriction.value {
+            *payloads = Cow::Owned(remap_list(payloads, old, new));
+        }
+    }
+}
+
+// the payload types of a rid's pt= restriction, e.g. 96,97, with old as new
+fn remap_list(payloads: &str, old: u8, new: u8) -> String {
+    payloads
+        .split(',')
+        .map(|payload| match payload.parse::<u8>() {
+            Ok(payload) if payload == old => format!("{}", new),
+            _ => payload.into(),
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// the fmtp parameters with apt=old, of a retransmission payload, as apt=new
+fn remap_apt(config: &str, old: u8, new: u8) -> String {
+    config
+        .split(';')
+        .map(|parameter| match parameter.trim().strip_prefix("apt=") {
+            Some(apt) if apt.parse() == Ok(old) => {
+                parameter.replacen(&format!("apt={}", apt), &format!("apt={}", new), 1)
+            }
+            _ => parameter.into(),
+        })
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+m=video 9 UDP/TLS/RTP/SAVPF 96 97
+a=mid:0
+a=rtpmap:96 VP8/90000
+a=rtpmap:97 rtx/90000
+a=fmtp:97 apt=96
+a=rtcp-fb:96 nack
+a=rtcp-fb:* ccm fir
+a=imageattr:96 send [x=1280,y=720] recv *
+a=rid:hi send pt=96,97;max-width=1280
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=mid:1
+a=rtpmap:96 H264/90000
+a=fmtp:96 packetization-mode=1";
+
+    #[test]
+    fn it_detects_payload_conflicts() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let conflicts = parsed.detect_payload_conflicts();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].payload, 96);
+        assert_eq!(conflicts[0].rtpmaps[1].0.index(), 1);
+        assert_eq!(conflicts[0].rtpmaps[1].1.codec, "H264");
+
+        let same = SDP.replace("H264/90000", "vp8/90000");
+
+        assert!(Sdp::parse(&same)
+            .unwrap()
+            .detect_payload_conflicts()
+            .is_empty());
+    }
+
+    #[test]
+    fn it_remaps_a_payload_type() {
+        let mut parsed = Sdp::parse(SDP).unwrap();
+        let mut first = parsed.media[0].clone();
+
+        first.remap_payload(96, 100).unwrap();
+
+        assert_eq!(
+            first.to_string(),
+            "m=video 9 UDP/TLS/RTP/SAVPF 100 97\r
+a=mid:0\r
+a=rtpmap:100 VP8/90000\r
+a=rtpmap:97 rtx/90000\r
+a=fmtp:97 apt=100\r
+a=rtcp-fb:100 nack\r
+a=rtcp-fb:* ccm fir\r
+a=imageattr:100 send [x=1280,y=720] recv *\r
+a=rid:hi send pt=100,97;max-width=1280\r
+"
+        );
+        assert!(first.remap_payload(100, 97).is_err());
+        assert!(first.remap_payload(100, 128).is_err());
+
+        parsed.media[0] = first;
+
+        assert!(parsed.detect_payload_conflicts().is_empty());
+
+        parsed.remap_payload(96, 102).unwrap();
+
+        assert_eq!(
+            parsed.media[1].fmtp[0].to_string(),
+            "102 packetization-mode=1"
+        );
+        assert_eq!(parsed.media[0].payloads, vec![100, 97]);
+    }
+}
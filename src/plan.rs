@@ -0,0 +1,229 @@
+//! Conversions between the Plan B and Unified Plan SDP dialects.
+//!
+//! Plan B carries every track of a media type in a single m line, telling the
+//! tracks apart by their a=ssrc msid (or legacy label) attributes.  Unified
+//! Plan, the standard since Chrome M72, gives each track its own m line.
+//!
+//! Sources without a msid or label follow the track of their ssrc-group, e.g.
+//! the RTX source of a video track, or stay with the first track.
+
+use crate::direction::Direction;
+use crate::group::Group;
+use crate::media::Media;
+use crate::sdp::Sdp;
+use alloc::{vec, vec::Vec};
+
+/// The sources of a track of a media section
+struct Track<'a> {
+    id: Option<&'a str>,
+    ssrcs: Vec<u32>,
+}
+
+pub(crate) fn to_unified_plan<'a>(sdp: &Sdp<'a>) -> Sdp<'a> {
+    let mut unified = sdp.clone();
+    unified.media.clear();
+
+    for media in &sdp.media {
+        let tracks = tracks(media);
+
+        if tracks.len() <= 1 {
+            unified.media.push(media.clone());
+            continue;
+        }
+
+        let mut mids = vec![];
+
+        for (index, track) in tracks.iter().enumerate() {
+            let mut section = media.clone();
+            section.ssrc.retain(|ssrc| track.ssrcs.contains(&ssrc.id));
+            section
+                .ssrc_groups
+                .retain(|group| group.ssrcs.iter().all(|id| track.ssrcs.contains(id)));
+
+            // the first track keeps the m line, and its mid, of the Plan B section
+            if index > 0 {
+                section.mid = track.id;
+                mids.extend(track.id);
+            }
+
+            unified.media.push(section);
+        }
+
+        if let Some(mid) = media.mid {
+            for group in bundle_groups(&mut unified) {
+                if let Some(position) = group.mids.iter().position(|&other| other == mid) {
+                    group
+                        .mids
+                        .splice(position + 1..position + 1, mids.iter().cloned());
+                }
+            }
+        }
+    }
+
+    unified
+}
+
+pub(crate) fn to_plan_b<'a>(sdp: &Sdp<'a>) -> Sdp<'a> {
+    let mut plan_b = sdp.clone();
+    plan_b.media.clear();
+    let mut merged_mids = vec![];
+
+    for media in &sdp.media {
+        let section = plan_b.media.iter_mut().find(|section| {
+            !media.is_rejected()
+                && !section.is_rejected()
+                && section.r#type == media.r#type
+                && section.protocol == media.protocol
+        });
+
+        let section = match section {
+            Some(section) => section,
+            None => {
+                plan_b.media.push(media.clone());
+                continue;
+            }
+        };
+
+        // the merged section sends if any of its tracks does, and so on
+        let (old, new) = (
+            section.effective_direction(sdp),
+            media.effective_direction(sdp),
+        );
+        section.direction = Some(Direction::from_capabilities(
+            old.sends() || new.sends(),
+            old.receives() || new.receives(),
+        ));
+        section.ssrc.extend(media.ssrc.iter().cloned());
+        section
+            .ssrc_groups
+            .extend(media.ssrc_groups.iter().cloned());
+        merged_mids.extend(media.mid);
+    }
+
+    for group in bundle_groups(&mut plan_b) {
+        group.mids.retain(|mid| !merged_mids.contains(mid));
+    }
+
+    plan_b
+}
+
+// the tracks of a media section, in the order of their first source
+fn tracks<'a>(media: &Media<'a>) -> Vec<Track<'a>> {
+    let mut tracks: Vec<Track<'a>> = vec![];
+
+    for info in media.ssrc_info() {
+        let id = info
+            .msid
+            .map(|msid| msid.split_once(' ').map_or(msid, |(_, track)| track))
+            .or(info.label);
+
+        let index = match id {
+            Some(id) => tracks.iter().position(|track| track.id == Some(id)),
+            None => media
+                .ssrc_groups
+                .iter()
+                .filter(|group| group.ssrcs.contains(&info.id))
+                .find_map(|group| {
+                    tracks
+                        .iter()
+                        .position(|track| group.ssrcs.iter().any(|id| track.ssrcs.contains(id)))
+                })
+                .or(if tracks.is_empty() { None } else { Some(0) }),
+        };
+
+        match index {
+            Some(index) => tracks[index].ssrcs.push(info.id),
+            None => tracks.push(Track {
+                id,
+                ssrcs: vec![info.id],
+            }),
+        }
+    }
+
+    tracks
+}
+
+fn bundle_groups<'a, 'b>(sdp: &'b mut Sdp<'a>) -> impl Iterator<Item = &'b mut Group<'a>> {
+    sdp.groups
+        .iter_mut()
+        .filter(|group| group.semantics == "BUNDLE")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN_B: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE audio video
+a=msid-semantic: WMS stream
+m=audio 9 UDP/TLS/RTP/SAVPF 111
+a=mid:audio
+a=sendrecv
+a=rtpmap:111 opus/48000/2
+a=ssrc:1001 cname:foo
+a=ssrc:1001 msid:stream mic
+m=video 9 UDP/TLS/RTP/SAVPF 96 97
+a=mid:video
+a=sendrecv
+a=rtpmap:96 VP8/90000
+a=rtpmap:97 rtx/90000
+a=fmtp:97 apt=96
+a=ssrc-group:FID 2001 2002
+a=ssrc-group:FID 3001 3002
+a=ssrc:2001 cname:foo
+a=ssrc:2001 msid:stream camera
+a=ssrc:2002 cname:foo
+a=ssrc:2002 msid:stream camera
+a=ssrc:3001 cname:foo
+a=ssrc:3001 msid:stream screen
+a=ssrc:3002 cname:foo";
+
+    #[test]
+    fn it_splits_tracks_into_media_sections() {
+        let plan_b = Sdp::parse(PLAN_B).unwrap();
+        let unified = plan_b.to_unified_plan();
+        let ssrcs = |media: &Media| {
+            media
+                .ssrc_info()
+                .iter()
+                .map(|info| info.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(unified.media.len(), 3);
+        assert_eq!(unified.groups[0].mids, vec!["audio", "video", "screen"]);
+        assert_eq!(unified.media[1].mid, Some("video"));
+        assert_eq!(ssrcs(&unified.media[1]), vec![2001, 2002]);
+        assert_eq!(unified.media[1].ssrc_groups[0].ssrcs, vec![2001, 2002]);
+        assert_eq!(unified.media[2].mid, Some("screen"));
+        assert_eq!(ssrcs(&unified.media[2]), vec![3001, 3002]);
+        assert_eq!(unified.media[2].rtpmap, plan_b.media[1].rtpmap);
+    }
+
+    #[test]
+    fn it_merges_media_sections_of_the_same_type() {
+        let plan_b = Sdp::parse(PLAN_B).unwrap();
+        let mut unified = plan_b.to_unified_plan();
+        unified.media[2].direction = Some(Direction::SendOnly);
+        unified.media[1].direction = Some(Direction::RecvOnly);
+
+        let merged = unified.to_plan_b();
+
+        assert_eq!(merged.media.len(), 2);
+        assert_eq!(merged.groups, plan_b.groups);
+        assert_eq!(merged.media[1].mid, Some("video"));
+        assert_eq!(merged.media[1].direction, Some(Direction::SendRecv));
+        assert_eq!(merged.media[1].ssrc_groups, plan_b.media[1].ssrc_groups);
+    }
+
+    #[test]
+    fn it_keeps_single_track_sections() {
+        let sdp = Sdp::parse(PLAN_B).unwrap().to_unified_plan();
+
+        assert_eq!(sdp.to_unified_plan(), sdp);
+        assert_eq!(sdp.to_plan_b().to_plan_b(), sdp.to_plan_b());
+    }
+}
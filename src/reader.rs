@@ -0,0 +1,158 @@
+use crate::error::{Error, Result};
+use crate::utils::lines;
+use alloc::format;
+
+/// SDP Reader
+///
+/// let reader = SdpReader::new(sdp_message);
+/// let ufrag = reader.attribute("ice-ufrag");
+///
+/// A pull API over the lines of a SDP message that splits each line into its
+/// key and value without building the whole model, for callers that only need
+/// to peek at one or two attributes.  Nothing is allocated unless a line is
+/// malformed.
+#[derive(Debug, Clone, Copy)]
+pub struct SdpReader<'a> {
+    text: &'a str,
+}
+
+/// A single line of a SDP message
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SdpLine<'a> {
+    /// An m line, starting a media section, e.g. audio 54400 RTP/SAVPF 0 96
+    Media(&'a str),
+
+    /// An a line, with the value after the colon if there is one
+    Attribute {
+        name: &'a str,
+        value: Option<&'a str>,
+    },
+
+    /// Any other line, e.g. v=0 or c=IN IP4 203.0.113.1
+    Field { key: char, value: &'a str },
+}
+
+impl<'a> SdpLine<'a> {
+    pub fn new(line: &'a str) -> Result<Self> {
+        if line.trim().is_empty() {
+            return Err(Error::invalid_line("empty line"));
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| Error::invalid_line("missing '=' after the line type"))?;
+        let value = value.trim();
+        let mut chars = key.chars();
+
+        // the line type is exactly one character
+        let key = match (chars.next(), chars.next()) {
+            (Some(key), None) => key,
+            (None, _) => return Err(Error::invalid_line("missing line type")),
+            _ => return Err(Error::invalid_line(format!("invalid line type '{}'", key))),
+        };
+
+        Ok(match key {
+            'm' => SdpLine::Media(value),
+            'a' => match value.split_once(':') {
+                Some((name, value)) => SdpLine::Attribute {
+                    name,
+                    value: Some(value.trim_start()),
+                },
+                None => SdpLine::Attribute {
+                    name: value,
+                    value: None,
+                },
+            },
+            key => SdpLine::Field { key, value },
+        })
+    }
+}
+
+impl<'a> SdpReader<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    // the lines of the message, in order, without their line endings
+    // blank lines at the end of the message are skipped
+    pub fn lines(&self) -> impl Iterator<Item = Result<SdpLine<'a>>> + 'a {
+        lines(self.text.trim_end())
+            .enumerate()
+            .map(|(index, line)| SdpLine::new(line).map_err(|error| error.at_line(index + 1, line)))
+    }
+
+    // the value of the first attribute with this name, at any level
+    pub fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.lines().find_map(|line| match line {
+            Ok(SdpLine::Attribute {
+                name: attribute,
+                value,
+            }) if attribute == name => value,
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-lite
+m=audio 54400 RTP/SAVPF 0
+a=ice-ufrag:F7gI";
+
+    #[test]
+    fn it_reads_lines() {
+        let lines = SdpReader::new(SDP)
+            .lines()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(
+            lines[0],
+            SdpLine::Field {
+                key: 'v',
+                value: "0"
+            }
+        );
+        assert_eq!(
+            lines[4],
+            SdpLine::Attribute {
+                name: "ice-lite",
+                value: None
+            }
+        );
+        assert_eq!(lines[5], SdpLine::Media("audio 54400 RTP/SAVPF 0"));
+    }
+
+    #[test]
+    fn it_splits_lines_like_str_lines() {
+        for text in ["", "\n", "v=0", "v=0\r\n", "v=0\r\n\r\ns=\n", "v=0\n\rs=\r"] {
+            assert_eq!(
+                lines(text).collect::<Vec<_>>(),
+                text.lines().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn it_peeks_at_an_attribute() {
+        let reader = SdpReader::new(SDP);
+
+        assert_eq!(reader.attribute("ice-ufrag"), Some("F7gI"));
+        assert_eq!(reader.attribute("ice-pwd"), None);
+    }
+
+    #[test]
+    fn it_rejects_malformed_lines() {
+        assert!(SdpLine::new("").is_err());
+        assert!(SdpLine::new("v0").is_err());
+        assert!(SdpLine::new("=0").is_err());
+        assert!(SdpLine::new("vv=0").is_err());
+    }
+}
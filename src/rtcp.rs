@@ -0,0 +1,88 @@
+use crate::error::Result;
+use crate::utils::{parse_number, parse_str};
+use core::fmt;
+
+/// SDP RTCP
+///
+/// a=rtcp:55401 IN IP4 203.0.113.1
+///
+/// Endpoints that don't multiplex RTP and RTCP send RTCP to the port (and
+/// optionally the address) advertised here instead of the next port after the
+/// one in the m line (RFC 3605).  Legacy SIP endpoints like Asterisk and
+/// FreeSWITCH include it in their offers.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Rtcp<'a> {
+    pub port: u16,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_type: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_type: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<&'a str>,
+}
+
+impl<'a> Rtcp<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.split(' ');
+        let port = parse_number::<u16>(split.next(), "rtcp port")?;
+        let mut rtcp = Self {
+            port,
+            ..Default::default()
+        };
+
+        // the address is optional, but all of its parts must be present
+        if let Some(network_type) = split.next() {
+            rtcp.network_type = Some(network_type);
+            rtcp.ip_type = Some(parse_str(split.next(), "ip type")?);
+            rtcp.address = Some(parse_str(split.next(), "ip address")?);
+        }
+
+        Ok(rtcp)
+    }
+}
+
+impl<'a> fmt::Display for Rtcp<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.port)?;
+
+        if let (Some(network_type), Some(ip_type), Some(address)) =
+            (self.network_type, self.ip_type, self.address)
+        {
+            write!(f, " {} {} {}", network_type, ip_type, address)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_rtcp() {
+        let rtcp = "55401 IN IP4 203.0.113.1";
+        let parsed = Rtcp::new(rtcp).unwrap();
+        let expected = Rtcp {
+            port: 55401,
+            network_type: Some("IN"),
+            ip_type: Some("IP4"),
+            address: Some("203.0.113.1"),
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), rtcp);
+    }
+
+    #[test]
+    fn it_parses_rtcp_without_an_address() {
+        let parsed = Rtcp::new("53020").unwrap();
+
+        assert_eq!(parsed.port, 53020);
+        assert_eq!(parsed.address, None);
+        assert!(Rtcp::new("53020 IN IP4").is_err());
+    }
+}
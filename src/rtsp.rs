@@ -0,0 +1,77 @@
+//! The control URLs of a RTSP presentation.
+//!
+//! A DESCRIBE response names the URL of each media section with a=control,
+//! either absolute or relative to the aggregate URL of the session-level
+//! a=control, which is in turn relative to the Content-Base of the response
+//! (RFC 2326 appendix C.1.1).
+
+use alloc::{format, string::String};
+
+pub(crate) fn control_url(base: &str, session: Option<&str>, media: &str) -> Option<String> {
+    let base = match session {
+        Some("*") | None => String::from(base),
+        Some(session) => resolve(base, session)?,
+    };
+
+    resolve(&base, media)
+}
+
+// "*" is the base itself, and a URL with a scheme replaces it
+fn resolve(base: &str, control: &str) -> Option<String> {
+    match control {
+        "*" => Some(base.into()),
+        control if control.contains("://") => Some(control.into()),
+        _ if base.is_empty() => None,
+        control => Some(format!("{}/{}", base.trim_end_matches('/'), control)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sdp::Sdp;
+
+    const SDP: &str = "v=0\r
+o=- 1 1 IN IP4 203.0.113.1\r
+s=Camera\r
+t=0 0\r
+a=control:*\r
+a=range:npt=0-\r
+m=video 0 RTP/AVP 96\r
+a=control:trackID=1\r
+a=rtpmap:96 H264/90000\r
+m=audio 0 RTP/AVP 97\r
+a=control:rtsp://203.0.113.1/camera/audio\r
+a=rtpmap:97 MPEG4-GENERIC/16000/1\r
+";
+
+    #[test]
+    fn it_parses_rtsp_control_attributes() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let base = "rtsp://203.0.113.1/camera/";
+
+        assert_eq!(parsed.control, Some("*"));
+        assert_eq!(parsed.range, Some("npt=0-"));
+        assert_eq!(parsed.media[0].control, Some("trackID=1"));
+        assert_eq!(
+            parsed.media[0].control_url(&parsed, base).unwrap(),
+            "rtsp://203.0.113.1/camera/trackID=1"
+        );
+        assert_eq!(
+            parsed.media[1].control_url(&parsed, "").unwrap(),
+            "rtsp://203.0.113.1/camera/audio"
+        );
+        assert_eq!(parsed.media[0].control_url(&parsed, ""), None);
+        assert_eq!(parsed.to_string(), SDP);
+    }
+
+    #[test]
+    fn it_resolves_media_against_the_session_control() {
+        let aggregate = SDP.replace("a=control:*", "a=control:rtsp://203.0.113.1/live");
+        let parsed = Sdp::parse(&aggregate).unwrap();
+
+        assert_eq!(
+            parsed.media[0].control_url(&parsed, "").unwrap(),
+            "rtsp://203.0.113.1/live/trackID=1"
+        );
+    }
+}
@@ -1,98 +1,1036 @@
+use crate::anonymize::{self, AnonymizeOptions};
+use crate::attribute::Attribute;
+use crate::bandwidth::{self, parse_maxprate, Bandwidth, BandwidthType};
+use crate::charset::{self, Decoding};
+use crate::clock::{MediaClock, TsRefclk};
 use crate::connection::Connection;
+use crate::diff::{self, SdpDelta};
+use crate::direction::Direction;
 use crate::error::{Error, Result};
+use crate::extension::Extension;
 use crate::fingerprint::Fingerprint;
-use crate::media::Media;
+use crate::flatten::{self, ResolvedMedia};
+use crate::fragment::{self, SdpFragment};
+use crate::group::Group;
+use crate::ice::{self, IceCredentials};
+use crate::lossless::{Lossless, RawLines};
+use crate::media::{Candidate, Media, MediaType, Rtpmap, Ssrc};
+use crate::media_id::{self, MediaId, MidIndex};
+use crate::msid::MsidSemantic;
 use crate::origin::Origin;
-use crate::set_value;
-use crate::time::Time;
-use crate::utils::{parse_number, parse_str};
+use crate::parser::{ParseOptions, SdpParser, Section};
+use crate::payload::{self, PayloadConflict};
+use crate::plan;
+use crate::reader::SdpLine;
+#[cfg(feature = "json")]
+use crate::sdp_transform;
+use crate::serialize::{SerializeOptions, Serialized};
+use crate::stats::{self, SdpStats};
+use crate::template::{self, WebrtcOfferConfig};
+use crate::time::{self, Repeat, Time, TimeDescription, ZoneAdjustment, ZoneAdjustments};
+use crate::utils::{lines, parse_number, parse_str, write_attribute, write_flag, write_line};
+use crate::validate::{self, Diagnostic, SourceLine};
+use crate::visit::{self, Visitor};
+use crate::warning::Warning;
+use crate::{push_value, set_value};
+use alloc::{borrow::Cow, format, string::String, string::ToString, vec, vec::Vec};
+use core::fmt;
 
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// The line ending written between the lines of a serialized SDP
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LineEnding {
+    /// \r\n, as required by RFC 4566 and used over SIP
+    #[default]
+    CrLf,
+    Lf,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Sdp<'a> {
-    version: u32,
-    session_name: &'a str,
-    ice_ufrag: &'a str,
-    ice_pwd: &'a str,
-    fingerprint: Fingerprint<'a>,
-    origin: Origin<'a>,
-    time: Time,
-    connection: Connection<'a>,
-    media: Vec<Media<'a>>,
+    pub version: u32,
+    pub session_name: &'a str,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_information: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_key: Option<&'a str>,
+
+    /// The category of the session, as a dot-separated hierarchy (RFC 8866)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<&'a str>,
+
+    /// Keywords describing the session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<&'a str>,
+
+    /// The tool that created the session, e.g. Asterisk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<&'a str>,
+
+    /// The aggregate control URL of a RTSP presentation, or * for the
+    /// Content-Base of the DESCRIBE response (RFC 2326)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control: Option<&'a str>,
+
+    /// The time range the RTSP presentation is available in, e.g. npt=0-
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<&'a str>,
+
+    /// The character set of the s= and i= lines, UTF-8 when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charset: Option<&'a str>,
+
+    /// The reference clocks of the media sections without their own (RFC 7273)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ts_refclk: Vec<TsRefclk<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mediaclk: Option<MediaClock<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_ufrag: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_pwd: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fingerprints: Vec<Fingerprint<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<&'a str>,
+
+    #[serde(default)]
+    pub ice_lite: bool,
+
+    /// One and two-byte RTP header extensions may be mixed in every media
+    /// section (RFC 8285 section 6)
+    #[serde(default)]
+    pub extmap_allow_mixed: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ice_options: Vec<&'a str>,
+
+    /// The milliseconds between the connectivity checks the agent wants,
+    /// 50 when missing (RFC 8839 section 5.5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_pacing: Option<u32>,
+
+    pub origin: Origin<'a>,
+
+    /// The t lines, each with its r lines
+    pub times: Vec<TimeDescription>,
+
+    /// The adjustments of the z line, for repeats across e.g. daylight saving
+    /// time changes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zone_adjustments: Vec<ZoneAdjustment>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<Connection<'a>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bandwidth: Vec<Bandwidth<'a>>,
+
+    /// The highest packet rate of the whole session, in packets per second
+    /// (RFC 3890)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxprate: Option<f32>,
+
+    pub groups: Vec<Group<'a>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msid_semantic: Option<MsidSemantic<'a>>,
 
+    /// Every session-level attribute line, in the order of the message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Attribute<'a>>,
+
+    /// Session-level attributes parsed by a registered `AttributeParser`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<Extension<'a>>,
+    pub media: Vec<Media<'a>>,
+
+    /// The session-level lines, when parsed with `ParseOptions::lossless`
+    #[serde(skip)]
+    pub raw: RawLines<'a>,
+
+    /// The media sections by mid, see `Sdp::media_by_mid`
     #[serde(skip)]
-    current_media: Option<usize>,
+    pub mid_index: MidIndex<'a>,
 }
 
 impl<'a> Sdp<'a> {
+    // the text of a message received as bytes, to be parsed, in the character
+    // set of its a=charset line
+    pub fn decode(sdp_message: &[u8], decoding: Decoding) -> Result<Cow<'_, str>> {
+        charset::decode(sdp_message, decoding)
+    }
+
+    // parse a UTF-8 message received as bytes, e.g. a SIP body, without a
+    // byte order mark and rejecting NUL bytes, see `decode` for other charsets
+    pub fn parse_bytes(sdp_message: &'a [u8]) -> Result<Self> {
+        Self::parse(charset::utf8(sdp_message)?)
+    }
+
     // parse each line of the SDP
     pub fn parse(sdp_message: &'a str) -> Result<Self> {
-        let mut sdp = Sdp::default();
-        let lines = sdp_message.lines();
+        Self::parse_with(sdp_message, &ParseOptions::default())
+    }
+
+    // parse each SDP body of a message carrying several, see `split_bodies`
+    // an invalid body doesn't stop the others from being parsed, and the lines
+    // of its errors are counted from its v= line
+    pub fn parse_all(message: &'a str) -> Vec<Result<Self>> {
+        split_bodies(message).into_iter().map(Sdp::parse).collect()
+    }
+
+    // parse every line of the SDP, keeping what could be parsed and the error
+    // of each invalid line, with its line number, e.g. to list every issue of
+    // a generator's output at once
+    // the lines of a media section whose m line is invalid are skipped
+    pub fn parse_all_errors(sdp_message: &'a str) -> (Self, Vec<Error>) {
+        Sdp::parse_all_errors_with(sdp_message, &ParseOptions::default())
+    }
+
+    // parse_all_errors() with options, e.g. limits for untrusted input
+    // the lines after one going over a limit are ignored
+    pub fn parse_all_errors_with(
+        sdp_message: &'a str,
+        options: &ParseOptions,
+    ) -> (Self, Vec<Error>) {
+        let mut parser = SdpParser::collecting_errors(options.clone());
+
+        for line in lines(sdp_message) {
+            // the errors are kept by the parser
+            let _ = parser.feed_line(line);
+        }
 
-        for line in lines {
-            sdp.parse_line(line)?;
+        parser.finish_with_errors()
+    }
+
+    // a baseline offer of a bundled audio and video section, see template
+    pub fn webrtc_audio_video_offer(config: &WebrtcOfferConfig<'a>) -> Self {
+        template::webrtc_offer(config)
+    }
+
+    // parse each line of the SDP, e.g. with parsers for custom attributes
+    pub fn parse_with(sdp_message: &'a str, options: &ParseOptions) -> Result<Self> {
+        let mut parser = SdpParser::with_options(options.clone());
+
+        // lossless lines keep their line endings
+        if options.lossless {
+            for line in sdp_message.split_inclusive('\n') {
+                parser.feed_line(line)?;
+            }
+        } else {
+            for line in lines(sdp_message) {
+                parser.feed_line(line)?;
+            }
         }
 
-        Ok(sdp)
+        parser.finish()
     }
 
-    // parse an individual SDP line
+    // parse an individual SDP line of `section`
     // return errors for invalid entries
-    fn parse_line(&mut self, line: &'a str) -> Result<()> {
-        let split = line.splitn(2, '=').collect::<Vec<&str>>();
-        let (key, value) = (split[0], split[1].trim());
+    pub(crate) fn parse_line(
+        &mut self,
+        line: SdpLine<'a>,
+        number: usize,
+        section: Section,
+        options: &ParseOptions,
+    ) -> Result<()> {
+        match line {
+            SdpLine::Media(value) => self.parse_media(value, number),
+            SdpLine::Field { key, value } => self.parse_field(key, value, section),
+            SdpLine::Attribute { name, value } => match self.section_media(section) {
+                Some(media) => match value {
+                    Some(value) => media.parse_attribute(name, value),
+                    None => media.parse_flag(name),
+                }
+                .map(|()| media.set_attribute_line(name, number))
+                .or_else(|error| {
+                    push_value!(
+                        media.extensions,
+                        parse_extension(error, name, value, options)
+                    )
+                }),
+                None => {
+                    self.parse_session_line(name, value).or_else(|error| {
+                        push_value!(
+                            self.extensions,
+                            parse_extension(error, name, value, options)
+                        )
+                    })?;
+                    self.attributes.push(Attribute { key: name, value });
 
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    fn parse_field(&mut self, key: char, value: &'a str, section: Section) -> Result<()> {
         match key {
-            "v" => set_value!(self.version, parse_number::<u32>(Some(value), 1)),
-            "o" => set_value!(self.origin, Origin::new(value)),
-            "s" => set_value!(self.session_name, parse_str(Some(value), 1)),
-            "t" => set_value!(self.time, Time::new(value)),
-            "c" => set_value!(self.connection, Connection::new(value)),
-            "a" => self.parse_attribute(value),
-            "m" => self.parse_media(value),
-            _ => Err(Error::Parse(format!("Unsupported attribute: {}", key))),
+            'v' => set_value!(self.version, parse_number::<u32>(Some(value), "version")),
+            'o' => set_value!(self.origin, Origin::new(value)),
+            's' => set_value!(self.session_name, parse_str(Some(value), "session name")),
+            'i' => self.parse_information(value, section),
+            'u' => set_value!(self.uri, Result::Ok(Some(value))),
+            'e' => set_value!(self.email, Result::Ok(Some(value))),
+            'p' => set_value!(self.phone, Result::Ok(Some(value))),
+            't' => push_value!(self.times, Time::new(value).map(TimeDescription::new)),
+            'r' => self.parse_repeat(value),
+            'z' => set_value!(self.zone_adjustments, time::parse_zone_adjustments(value)),
+            'c' => self.parse_connection(value, section),
+            'b' => self.parse_bandwidth(value, section),
+            'k' => self.parse_encryption_key(value, section),
+            _ => Err(Error::invalid_line(format!(
+                "unsupported line type '{}'",
+                key
+            ))),
         }
     }
 
-    // media parsing is slightly more complex
-    // maintain state as subsequent lines relate to the current_media
-    fn parse_media(&mut self, value: &'a str) -> Result<()> {
-        let count = self.current_media.unwrap_or(0);
-        self.current_media = Some(count + 1);
-        self.media.push(Media::new(value)?);
+    // an r line repeats the t line before it
+    fn parse_repeat(&mut self, value: &'a str) -> Result<()> {
+        match self.times.last_mut() {
+            Some(description) => push_value!(description.repeats, Repeat::new(value)),
+            None => Err(Error::invalid_line("r= line without a t= line before it")),
+        }
+    }
+
+    // subsequent lines relate to the new media section, see SdpParser
+    fn parse_media(&mut self, value: &'a str, number: usize) -> Result<()> {
+        let mut media = Media::new(value)?;
+        media.line = SourceLine(Some(number));
+
+        self.media.push(media);
 
         Ok(())
     }
 
-    fn parse_media_attribute(&mut self, attribute: &'a str, value: &'a str) -> Result<()> {
-        let count = self.current_media.unwrap_or(0);
-        let media = self.media.get_mut(count - 1).ok_or_else(|| {
-            Error::Parse("Cannot parse a media attribute before a 'm' line".into())
-        })?;
+    // the media section of the lines of `section`, None at the session level
+    fn section_media(&mut self, section: Section) -> Option<&mut Media<'a>> {
+        match section {
+            Section::Session => None,
+            Section::Media(index) => self.media.get_mut(index),
+        }
+    }
+
+    // a connection line applies to the current media section, if any
+    fn parse_connection(&mut self, value: &'a str, section: Section) -> Result<()> {
+        let connection = Some(Connection::new(value)?);
 
-        media.parse_attribute(attribute, value)
+        match self.section_media(section) {
+            Some(media) => media.connection = connection,
+            None => self.connection = connection,
+        }
+
+        Ok(())
     }
 
-    fn parse_attribute(&mut self, value: &'a str) -> Result<()> {
-        let split = value.splitn(2, ':').collect::<Vec<&str>>();
+    // a bandwidth line applies to the current media section, if any
+    fn parse_bandwidth(&mut self, value: &'a str, section: Section) -> Result<()> {
+        let bandwidth = Bandwidth::new(value)?;
 
-        if split.len() == 1 {
-            self.parse_media_attribute("direction", split[0])?
-        } else {
-            match split[0] {
-                "ice-ufrag" => self.ice_ufrag = split[1],
-                "ice-pwd" => self.ice_pwd = split[1],
-                "fingerprint" => self.fingerprint = Fingerprint::new(split[1])?,
-                _ => self.parse_media_attribute(split[0], split[1])?,
+        match self.section_media(section) {
+            Some(media) => media.bandwidth.push(bandwidth),
+            None => self.bandwidth.push(bandwidth),
+        }
+
+        Ok(())
+    }
+
+    // an information line titles the current media section, if any
+    fn parse_information(&mut self, value: &'a str, section: Section) -> Result<()> {
+        match self.section_media(section) {
+            Some(media) => media.information = Some(value),
+            None => self.session_information = Some(value),
+        }
+
+        Ok(())
+    }
+
+    // an encryption key line applies to the current media section, if any
+    fn parse_encryption_key(&mut self, value: &'a str, section: Section) -> Result<()> {
+        match self.section_media(section) {
+            Some(media) => media.encryption_key = Some(value),
+            None => self.encryption_key = Some(value),
+        }
+
+        Ok(())
+    }
+
+    // attributes before the first 'm' line describe the whole session
+    fn parse_session_attribute(&mut self, attribute: &'a str, value: &'a str) -> Result<()> {
+        match attribute {
+            "ice-ufrag" => set_value!(self.ice_ufrag, Result::Ok(Some(value))),
+            "ice-pwd" => set_value!(self.ice_pwd, Result::Ok(Some(value))),
+            "fingerprint" => push_value!(self.fingerprints, Fingerprint::new(value)),
+            "setup" => set_value!(self.setup, Result::Ok(Some(value))),
+            "charset" => set_value!(self.charset, Result::Ok(Some(value))),
+            "tool" => set_value!(self.tool, Result::Ok(Some(value))),
+            "cat" => set_value!(self.category, Result::Ok(Some(value))),
+            "keywds" => set_value!(self.keywords, Result::Ok(Some(value))),
+            "maxprate" => set_value!(self.maxprate, parse_maxprate(value).map(Some)),
+            "control" => set_value!(self.control, Result::Ok(Some(value))),
+            "range" => set_value!(self.range, Result::Ok(Some(value))),
+            "ts-refclk" => push_value!(self.ts_refclk, TsRefclk::new(value)),
+            "mediaclk" => set_value!(self.mediaclk, MediaClock::new(value).map(Some)),
+            "group" => push_value!(self.groups, Group::new(value)),
+            "msid-semantic" => set_value!(self.msid_semantic, MsidSemantic::new(value).map(Some)),
+            "ice-options" => set_value!(self.ice_options, Result::Ok(ice::parse_options(value))),
+            "ice-pacing" => set_value!(
+                self.ice_pacing,
+                parse_number::<u32>(Some(value), "ice-pacing").map(Some)
+            ),
+            _ => Err(Error::unknown_attribute("session", attribute)),
+        }
+    }
+
+    // value-less session attributes, e.g. a=ice-lite or a=sendrecv
+    fn parse_session_flag(&mut self, flag: &'a str) -> Result<()> {
+        match flag {
+            "ice-lite" => set_value!(self.ice_lite, Result::Ok(true)),
+            "extmap-allow-mixed" => set_value!(self.extmap_allow_mixed, Result::Ok(true)),
+            "sendrecv" | "sendonly" | "recvonly" | "inactive" => {
+                set_value!(self.direction, Direction::new(flag).map(Some))
+            }
+            _ => Err(Error::unknown_attribute("session", flag)),
+        }
+    }
+
+    fn parse_session_line(&mut self, attribute: &'a str, value: Option<&'a str>) -> Result<()> {
+        match value {
+            Some(value) => self.parse_session_attribute(attribute, value),
+            None => self.parse_session_flag(attribute),
+        }
+    }
+
+    // the ICE credentials of a media section, falling back to the session-level
+    // values, if both the ufrag and pwd are known
+    pub fn ice_credentials(&self, media_index: usize) -> Option<IceCredentials<'a>> {
+        let media = self.media.get(media_index)?;
+
+        Some(IceCredentials {
+            ufrag: media.effective_ice_ufrag(self)?,
+            pwd: media.effective_ice_pwd(self)?,
+        })
+    }
+
+    // the bandwidth of a session-level b line, e.g. b=AS
+    pub fn bandwidth_for(&self, r#type: BandwidthType) -> Option<u64> {
+        bandwidth::find(&self.bandwidth, r#type)
+    }
+
+    // the session-level TIAS bandwidth with the headers of a=maxprate packets
+    // of `overhead` bytes, in bits per second
+    pub fn transport_bandwidth(&self, overhead: u32) -> Option<u64> {
+        bandwidth::transport(&self.bandwidth, self.maxprate, overhead)
+    }
+
+    // merge the trickled candidates of a fragment into the media sections of their mid
+    pub fn apply_fragment(&mut self, fragment: &SdpFragment<'a>) -> Result<()> {
+        fragment::apply(self, fragment)
+    }
+
+    // reject the media section at `index`, keeping its m line with port 0 so the
+    // sections after it keep their index, and take its mid out of the bundle
+    pub fn reject_media(&mut self, index: usize) -> Result<()> {
+        let media = self.media.get_mut(index).ok_or_else(|| {
+            Error::invalid_value("media index", format!("no media section {}", index))
+        })?;
+        *media = media.rejected();
+
+        if let Some(mid) = media.mid {
+            for group in self.groups.iter_mut().filter(|g| g.semantics == "BUNDLE") {
+                group.mids.retain(|&other| other != mid);
             }
         }
 
         Ok(())
     }
 
+    // the media section with this mid, found through the index built while parsing
+    pub fn media_by_mid(&self, mid: &str) -> Option<&Media<'a>> {
+        self.media_by_id(self.media_id(mid)?)
+    }
+
+    // a handle to the media section with this mid, for later lookups and edits
+    pub fn media_id(&self, mid: &str) -> Option<MediaId> {
+        media_id::find(self, mid)
+    }
+
+    // handles to every media section, in order
+    pub fn media_ids(&self) -> impl Iterator<Item = MediaId> {
+        media_id::ids(self)
+    }
+
+    pub fn media_by_id(&self, id: MediaId) -> Option<&Media<'a>> {
+        self.media.get(id.index())
+    }
+
+    pub fn media_by_id_mut(&mut self, id: MediaId) -> Option<&mut Media<'a>> {
+        self.media.get_mut(id.index())
+    }
+
+    // the payload types mapped to different codecs by different rtpmap lines
+    pub fn detect_payload_conflicts(&self) -> Vec<PayloadConflict<'a>> {
+        payload::conflicts(self)
+    }
+
+    // rename a payload type in every media section, see `Media::remap_payload`
+    // nothing is renamed if the new payload type is taken in any section
+    pub fn remap_payload(&mut self, old: u8, new: u8) -> Result<()> {
+        payload::remap(self, old, new)
+    }
+
+    // the distinct media types of the m lines, in order
+    pub fn media_types(&self) -> Vec<MediaType<'a>> {
+        let mut media_types = vec![];
+
+        for media_type in self.media.iter().map(|media| media.media_type()) {
+            if !media_types.contains(&media_type) {
+                media_types.push(media_type);
+            }
+        }
+
+        media_types
+    }
+
+    pub fn has_audio(&self) -> bool {
+        self.media_types().contains(&MediaType::Audio)
+    }
+
+    pub fn has_video(&self) -> bool {
+        self.media_types().contains(&MediaType::Video)
+    }
+
+    // the distinct codec names of the rtpmap lines of every media section, in order
+    // names are compared case-insensitively, e.g. opus and OPUS are the same codec
+    pub fn codecs(&self) -> Vec<&'a str> {
+        let mut codecs: Vec<&'a str> = vec![];
+
+        for rtpmap in self.media.iter().flat_map(|media| &media.rtpmap) {
+            if !codecs
+                .iter()
+                .any(|codec| codec.eq_ignore_ascii_case(rtpmap.codec))
+            {
+                codecs.push(rtpmap.codec);
+            }
+        }
+
+        codecs
+    }
+
+    // the candidates of every media section, with the index of their section
+    pub fn candidates(&self) -> impl Iterator<Item = (usize, &Candidate<'a>)> {
+        self.media.iter().enumerate().flat_map(|(index, media)| {
+            media
+                .candidates
+                .iter()
+                .map(move |candidates| (index, candidates))
+        })
+    }
+
+    // the rtpmap lines of every media section, with the index of their section
+    pub fn rtpmaps(&self) -> impl Iterator<Item = (usize, &Rtpmap<'a>)> {
+        self.media
+            .iter()
+            .enumerate()
+            .flat_map(|(index, media)| media.rtpmap.iter().map(move |rtpmap| (index, rtpmap)))
+    }
+
+    // the ssrc lines of every media section, with the index of their section
+    pub fn ssrcs(&self) -> impl Iterator<Item = (usize, &Ssrc<'a>)> {
+        self.media
+            .iter()
+            .enumerate()
+            .flat_map(|(index, media)| media.ssrc.iter().map(move |ssrc| (index, ssrc)))
+    }
+
+    // walk the session and every media section, see `Visitor`
+    pub fn visit(&self, visitor: &mut impl Visitor<'a>) {
+        visit::walk(self, visitor)
+    }
+
+    // each media section with the session-level values it inherits and the
+    // transport of its BUNDLE group, see flatten
+    pub fn flatten(&self) -> Vec<ResolvedMedia<'a>> {
+        flatten::flatten(self)
+    }
+
+    // the counts of the media sections, codecs and candidates, see stats
+    pub fn stats(&self) -> SdpStats<'a> {
+        stats::stats(self)
+    }
+
+    // the media sections of each group with these semantics, e.g. the audio
+    // and video of each participant in the LS groups of a telepresence call
+    pub fn grouped_media(&self, semantics: &str) -> Vec<Vec<&Media<'a>>> {
+        self.groups
+            .iter()
+            .filter(|group| group.semantics == semantics)
+            .map(|group| group.media(self))
+            .collect()
+    }
+
+    // a BUNDLE group ties media sections to a single transport
+    pub fn uses_bundle(&self) -> bool {
+        self.groups
+            .iter()
+            .any(|group| group.semantics == "BUNDLE" && !group.mids.is_empty())
+    }
+
+    // every RTP media section multiplexes RTP and RTCP on one port
+    pub fn uses_rtcp_mux(&self) -> bool {
+        let mut rtp = self
+            .media
+            .iter()
+            .filter(|media| media.transport_protocol().is_rtp())
+            .peekable();
+
+        rtp.peek().is_some() && rtp.all(|media| media.rtcp_mux)
+    }
+
+    pub fn is_ice_lite(&self) -> bool {
+        self.ice_lite
+    }
+
+    // candidates may be sent after the SDP, a=ice-options:trickle (RFC 8840)
+    pub fn is_ice_trickle(&self) -> bool {
+        self.ice_options.contains(&"trickle")
+    }
+
+    // the DTLS setup role of the session, or of its first media section with one
+    pub fn dtls_role(&self) -> Option<&'a str> {
+        self.setup
+            .or_else(|| self.media.iter().find_map(|media| media.setup))
+    }
+
+    // whether the digest of a DTLS certificate matches the fingerprints of a
+    // media section, its own overriding the session-level ones, or the session's
+    // without media
+    pub fn matches_certificate_fingerprint(&self, algorithm: &str, digest: &[u8]) -> bool {
+        let matches = |fingerprints: &[Fingerprint]| {
+            fingerprints
+                .iter()
+                .any(|fingerprint| fingerprint.matches(algorithm, digest))
+        };
+
+        if self.media.is_empty() {
+            return matches(&self.fingerprints);
+        }
+
+        self.media
+            .iter()
+            .filter(|media| !media.is_rejected())
+            .any(|media| matches(media.effective_fingerprints(self)))
+    }
+
+    // mask addresses, credentials and keys in place, e.g. before logging the SDP
+    pub fn anonymize(&mut self, options: AnonymizeOptions) {
+        anonymize::anonymize(self, options)
+    }
+
+    // check the constraints between lines, e.g. that DTLS media have a fingerprint
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate::validate(self)
+    }
+
+    // one media section per track, split from the Plan B sections by ssrc msid
+    pub fn to_unified_plan(&self) -> Sdp<'a> {
+        plan::to_unified_plan(self)
+    }
+
+    // one media section per media type, merging the Unified Plan sections
+    pub fn to_plan_b(&self) -> Sdp<'a> {
+        plan::to_plan_b(self)
+    }
+
+    // what changed from this SDP to `other`, e.g. to detect an ICE restart
+    pub fn diff(&self, other: &Sdp<'a>) -> SdpDelta<'a> {
+        diff::diff(self, other)
+    }
+
+    // whether this SDP and `other` negotiate the same session: the codecs,
+    // directions, SSRCs and transport parameters, whatever the session
+    // version, the order of the candidates and of the attribute lines
+    pub fn semantically_equal(&self, other: &Sdp<'a>) -> bool {
+        diff::semantically_equal(self, other)
+    }
+
+    // remove a codec from every media section, see Media::remove_codec()
+    pub fn remove_codec(&mut self, codec: &str) {
+        self.media
+            .iter_mut()
+            .for_each(|media| media.remove_codec(codec));
+    }
+
+    // remove every other codec from every media section
+    pub fn keep_only_codecs(&mut self, codecs: &[&str]) {
+        self.media
+            .iter_mut()
+            .for_each(|media| media.keep_only_codecs(codecs));
+    }
+
+    // replace the format parameters of every opus payload
+    pub fn set_opus_params(&mut self, config: &'a str) {
+        self.media
+            .iter_mut()
+            .for_each(|media| media.set_opus_params(config));
+    }
+
+    // prefer the given codecs, in order, in every media section
+    pub fn reorder_payloads(&mut self, codecs: &[&str]) {
+        self.media
+            .iter_mut()
+            .for_each(|media| media.reorder_payloads(codecs));
+    }
+
+    #[cfg(feature = "json")]
     pub fn to_json(&self) -> Result<String> {
         serde_json::to_string_pretty(&self).map_err(|e| Error::ConvertToJson(e.to_string()))
     }
+
+    // serialize into the JSON shape of the sdp-transform JavaScript library
+    #[cfg(feature = "json")]
+    pub fn to_json_sdp_transform(&self) -> Result<String> {
+        serde_json::to_string_pretty(&sdp_transform::to_value(self))
+            .map_err(|e| Error::ConvertToJson(e.to_string()))
+    }
+
+    // serialize into YAML, in the shape of to_json()
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String> {
+        serde_norway::to_string(&self).map_err(|e| Error::ConvertToYaml(e.to_string()))
+    }
+
+    // serialize into TOML, in the shape of to_json(), e.g. for golden files
+    // TOML integers are signed, so an origin session id over i64::MAX fails
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(&self).map_err(|e| Error::ConvertToToml(e.to_string()))
+    }
+
+    // restore a SDP previously converted with to_json()
+    // strings are borrowed from the json, so they cannot contain escapes,
+    // see SdpJson to restore any json of to_json()
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &'a str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| Error::ConvertFromJson(e.to_string()))
+    }
+
+    // serialize back into a SDP message
+    pub fn to_sdp_string(&self) -> String {
+        self.to_string()
+    }
+
+    // serialize the lines of a lossless parse as they were, only writing the
+    // sections without them, e.g. added ones, from the typed fields
+    pub fn to_sdp_string_lossless(&self) -> String {
+        Lossless(self).to_string()
+    }
+
+    // serialize with the given line endings
+    pub fn to_sdp_string_with(&self, line_ending: LineEnding) -> String {
+        self.to_sdp_string_with_options(&SerializeOptions {
+            line_ending,
+            ..Default::default()
+        })
+    }
+
+    // serialize with the line endings, and the order of the lines of each
+    // media section, of the options
+    pub fn to_sdp_string_with_options(&self, options: &SerializeOptions) -> String {
+        let serialized = Serialized(self, options.profile).to_string();
+
+        match options.line_ending {
+            LineEnding::CrLf => serialized,
+            LineEnding::Lf => serialized.replace("\r\n", "\n"),
+        }
+    }
+}
+
+/// Parse a SDP in lenient mode, with the issues recovered from
+///
+/// let (sdp, warnings) = sdp::parse_with_warnings(offer);
+///
+/// Unknown attributes are skipped and numbers out of range clamped, and those
+/// and duplicate lines are reported, see `ParseOptions::lenient`.  The
+/// warnings found before a line that couldn't be recovered from are still
+/// returned with its error.
+pub fn parse_with_warnings(sdp_message: &str) -> (Result<Sdp<'_>>, Vec<Warning>) {
+    let mut options = ParseOptions::default();
+    options.lenient = true;
+
+    let mut parser = SdpParser::with_options(options);
+    let fed = lines(sdp_message).try_for_each(|line| parser.feed_line(line));
+    let warnings = parser.warnings().to_vec();
+
+    (fed.and_then(|_| parser.finish()), warnings)
+}
+
+/// The SDP bodies of a message, e.g. the parts of a multipart/mixed SIP body
+/// or the offer and early media answers of a SIP trace
+///
+/// Each body starts at a v= line and ends at the last SDP line before the next
+/// one, so MIME boundaries and part headers between them are left out.
+pub fn split_bodies(message: &str) -> Vec<&str> {
+    let mut bodies = vec![];
+    let mut body: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for line in message.split_inclusive('\n') {
+        let start = offset;
+        let text = line.trim_end_matches(['\r', '\n']);
+        offset += line.len();
+
+        if text.starts_with("v=") {
+            bodies.extend(body.map(|(start, end)| &message[start..end]));
+            body = Some((start, start + text.len()));
+        } else if let Some((_, end)) = body.as_mut() {
+            if is_sdp_line(text) {
+                *end = start + text.len();
+            }
+        }
+    }
+
+    bodies.extend(body.map(|(start, end)| &message[start..end]));
+    bodies
+}
+
+// a type letter followed by "=", e.g. a=rtcp-mux but not Content-Type: or --boundary
+fn is_sdp_line(line: &str) -> bool {
+    let bytes = line.as_bytes();
+
+    bytes.len() >= 2 && bytes[0].is_ascii_lowercase() && bytes[1] == b'='
+}
+
+// the session-level attributes of the typed model, in the order they're written
+const SESSION_ATTRIBUTES: [&str; 23] = [
+    "cat",
+    "keywds",
+    "tool",
+    "control",
+    "range",
+    "maxprate",
+    "charset",
+    "ts-refclk",
+    "mediaclk",
+    "ice-lite",
+    "sendrecv",
+    "sendonly",
+    "recvonly",
+    "inactive",
+    "ice-ufrag",
+    "ice-pwd",
+    "ice-options",
+    "ice-pacing",
+    "fingerprint",
+    "setup",
+    "group",
+    "msid-semantic",
+    "extmap-allow-mixed",
+];
+
+impl<'a> Sdp<'a> {
+    // write the lines of a session-level attribute from the typed fields
+    fn write_session_attribute(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        match name {
+            "ice-lite" if self.ice_lite => write_flag(f, "ice-lite"),
+            "extmap-allow-mixed" if self.extmap_allow_mixed => write_flag(f, name),
+            "sendrecv" | "sendonly" | "recvonly" | "inactive" => match self.direction {
+                Some(direction) if direction.as_str() == name => write_flag(f, name),
+                _ => Ok(()),
+            },
+            "ice-ufrag" => self
+                .ice_ufrag
+                .map_or(Ok(()), |ice_ufrag| write_attribute(f, name, ice_ufrag)),
+            "ice-pwd" => self
+                .ice_pwd
+                .map_or(Ok(()), |ice_pwd| write_attribute(f, name, ice_pwd)),
+            "ice-options" if !self.ice_options.is_empty() => {
+                write_attribute(f, name, self.ice_options.join(" "))
+            }
+            "ice-pacing" => self
+                .ice_pacing
+                .map_or(Ok(()), |ice_pacing| write_attribute(f, name, ice_pacing)),
+            "fingerprint" => self
+                .fingerprints
+                .iter()
+                .try_for_each(|fingerprint| write_attribute(f, name, fingerprint)),
+            "setup" => self
+                .setup
+                .map_or(Ok(()), |setup| write_attribute(f, name, setup)),
+            "charset" => self
+                .charset
+                .map_or(Ok(()), |charset| write_attribute(f, name, charset)),
+            "tool" => self
+                .tool
+                .map_or(Ok(()), |tool| write_attribute(f, name, tool)),
+            "cat" => self
+                .category
+                .map_or(Ok(()), |category| write_attribute(f, name, category)),
+            "keywds" => self
+                .keywords
+                .map_or(Ok(()), |keywords| write_attribute(f, name, keywords)),
+            "control" => self
+                .control
+                .map_or(Ok(()), |control| write_attribute(f, name, control)),
+            "range" => self
+                .range
+                .map_or(Ok(()), |range| write_attribute(f, name, range)),
+            "maxprate" => self
+                .maxprate
+                .map_or(Ok(()), |maxprate| write_attribute(f, name, maxprate)),
+            "ts-refclk" => self
+                .ts_refclk
+                .iter()
+                .try_for_each(|ts_refclk| write_attribute(f, name, ts_refclk)),
+            "mediaclk" => self
+                .mediaclk
+                .as_ref()
+                .map_or(Ok(()), |mediaclk| write_attribute(f, name, mediaclk)),
+            "group" => self
+                .groups
+                .iter()
+                .try_for_each(|group| write_attribute(f, name, group)),
+            "msid-semantic" => self.msid_semantic.as_ref().map_or(Ok(()), |msid_semantic| {
+                write_attribute(f, name, msid_semantic)
+            }),
+            _ => self
+                .extensions
+                .iter()
+                .filter(|extension| extension.attribute.key == name)
+                .try_for_each(|extension| write_line(f, 'a', extension)),
+        }
+    }
+}
+
+// an attribute the crate doesn't know, parsed by a registered parser if any
+fn parse_extension<'a>(
+    error: Error,
+    name: &'a str,
+    value: Option<&'a str>,
+    options: &ParseOptions,
+) -> Result<Extension<'a>> {
+    match (&error, options.attribute_parser(name)) {
+        (Error::UnknownAttribute { .. }, Some(parser)) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                attribute = name,
+                "parsing with a registered AttributeParser"
+            );
+
+            Extension::new(Attribute { key: name, value }, parser)
+        }
+        _ => Err(error),
+    }
+}
+
+impl<'a> fmt::Display for Sdp<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_session(f)?;
+
+        for media in &self.media {
+            write!(f, "{}", media)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Sdp<'a> {
+    // the lines before the first m line
+    pub(crate) fn write_session(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_line(f, 'v', self.version)?;
+        write_line(f, 'o', &self.origin)?;
+        write_line(f, 's', self.session_name)?;
+
+        let lines = [
+            ('i', self.session_information),
+            ('u', self.uri),
+            ('e', self.email),
+            ('p', self.phone),
+        ];
+
+        for (key, value) in lines.iter() {
+            if let Some(value) = value {
+                write_line(f, *key, value)?;
+            }
+        }
+
+        if let Some(connection) = &self.connection {
+            write_line(f, 'c', connection)?;
+        }
+
+        for bandwidth in &self.bandwidth {
+            write_line(f, 'b', bandwidth)?;
+        }
+
+        // a session without times is permanent
+        if self.times.is_empty() {
+            write_line(f, 't', Time::default())?;
+        }
+
+        for description in &self.times {
+            write_line(f, 't', &description.time)?;
+
+            for repeat in &description.repeats {
+                write_line(f, 'r', repeat)?;
+            }
+        }
+
+        if !self.zone_adjustments.is_empty() {
+            write_line(f, 'z', ZoneAdjustments(&self.zone_adjustments))?;
+        }
+
+        if let Some(encryption_key) = self.encryption_key {
+            write_line(f, 'k', encryption_key)?;
+        }
+
+        // known attributes keep the order they were parsed in
+        let mut names = vec![];
+
+        for attribute in &self.attributes {
+            if !names.contains(&attribute.key) {
+                names.push(attribute.key);
+            }
+        }
+
+        for name in SESSION_ATTRIBUTES.iter() {
+            if !names.contains(name) {
+                names.push(name);
+            }
+        }
+
+        for extension in &self.extensions {
+            if !names.contains(&extension.attribute.key) {
+                names.push(extension.attribute.key);
+            }
+        }
+
+        for name in names {
+            self.write_session_attribute(f, name)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[macro_export]
@@ -114,16 +1052,18 @@ macro_rules! push_value {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::media::{Candidate, Fmtp, Media, RtcpFb, Rtpmap, Ssrc};
+    use crate::application::{Application, Sctpmap};
+    use crate::ice::IceOption;
+    use crate::media::{Candidate, Fmtp, Format, Media, RtcpFb, Rtpmap, Ssrc};
 
-    const SDP: &'static str = "v=0
+    const SDP: &str = "v=0
 o=- 20518 0 IN IP4 203.0.113.1
 s=
 t=0 0
 c=IN IP4 203.0.113.1
 a=ice-ufrag:F7gI
 a=ice-pwd:x9cml/YzichV2+XlhiMu8g
-a=fingerprint:sha-1 42:89:c5:c6:55:9d:6e:c8:e8:83:55:2a:39:f9:b6:eb:e9:a3:a9:e7
+a=fingerprint:sha-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7
 m=audio 54400 RTP/SAVPF 0 96
 a=rtpmap:0 PCMU/8000
 a=rtpmap:96 opus/48000
@@ -152,12 +1092,13 @@ a=ssrc:1399694169 baz";
         let expected = Sdp {
             version: 0,
             session_name: "",
-            ice_ufrag: "F7gI",
-            ice_pwd: "x9cml/YzichV2+XlhiMu8g",
-            fingerprint: Fingerprint {
+            ice_ufrag: Some("F7gI"),
+            ice_pwd: Some("x9cml/YzichV2+XlhiMu8g"),
+            fingerprints: vec![Fingerprint {
                 r#type: "sha-1",
-                hash: "42:89:c5:c6:55:9d:6e:c8:e8:83:55:2a:39:f9:b6:eb:e9:a3:a9:e7",
-            },
+                hash: "42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7",
+            }],
+            setup: None,
             origin: Origin {
                 username: "-",
                 session_id: 20518,
@@ -166,123 +1107,145 @@ a=ssrc:1399694169 baz";
                 ip_type: "IP4",
                 ip_address: "203.0.113.1",
             },
-            time: Time {
-                start_time: 0,
-                stop_time: 0,
-                bounded: false,
-            },
-            connection: Connection {
+            times: vec![TimeDescription {
+                time: Time {
+                    start_time: 0,
+                    stop_time: 0,
+                },
+                repeats: vec![],
+            }],
+            connection: Some(Connection {
                 network_type: "IN",
                 ip_type: "IP4",
                 ip_address: "203.0.113.1",
-            },
+                ..Default::default()
+            }),
+            groups: vec![],
             media: vec![
                 Media {
                     r#type: "audio",
                     port: 54400,
                     protocol: "RTP/SAVPF",
-                    payloads: "0",
+                    payloads: vec![0, 96],
                     candidates: vec![
                         Candidate {
-                            component: 0,
-                            foundation: "1",
+                            foundation: "0",
+                            component: 1,
                             transport: "UDP",
                             priority: 2113667327,
                             ip: "203.0.113.1",
                             port: 54400,
                             r#type: "host",
+                            ..Default::default()
                         },
                         Candidate {
-                            component: 1,
-                            foundation: "2",
+                            foundation: "1",
+                            component: 2,
                             transport: "UDP",
                             priority: 2113667326,
                             ip: "203.0.113.1",
                             port: 54401,
                             r#type: "host",
+                            ..Default::default()
                         },
                     ],
-                    direction: "sendrecv",
+                    direction: Some(Direction::SendRecv),
                     fmtp: vec![],
-                    ptime: 20,
+                    ptime: Some(20.0),
                     rtpmap: vec![
                         Rtpmap {
                             codec: "PCMU",
-                            payload: "0",
-                            rate: 8000,
+                            payload: 0,
+                            rate: Some(8000),
+                            channels: None,
+                            ..Default::default()
                         },
                         Rtpmap {
                             codec: "opus",
-                            payload: "96",
-                            rate: 48000,
+                            payload: 96,
+                            rate: Some(48000),
+                            channels: None,
+                            ..Default::default()
                         },
                     ],
                     rtc_fb: vec![],
                     ssrc: vec![],
+                    ..Default::default()
                 },
                 Media {
                     r#type: "video",
                     port: 55400,
                     protocol: "RTP/SAVPF",
-                    payloads: "97",
+                    payloads: vec![97, 98],
                     candidates: vec![
                         Candidate {
-                            component: 0,
-                            foundation: "1",
+                            foundation: "0",
+                            component: 1,
                             transport: "UDP",
                             priority: 2113667327,
                             ip: "203.0.113.1",
                             port: 55400,
                             r#type: "host",
+                            ..Default::default()
                         },
                         Candidate {
-                            component: 1,
-                            foundation: "2",
+                            foundation: "1",
+                            component: 2,
                             transport: "UDP",
                             priority: 2113667326,
                             ip: "203.0.113.1",
                             port: 55401,
                             r#type: "host",
+                            ..Default::default()
                         },
                     ],
-                    direction: "sendrecv",
+                    direction: Some(Direction::SendRecv),
                     fmtp: vec![Fmtp {
-                        config: "profile-level-id=4d0028;packetization-mode=1",
-                        payload: 97,
+                        config: "profile-level-id=4d0028;packetization-mode=1".into(),
+                        payload: Format::Payload(97),
+                        line: SourceLine::default(),
                     }],
-                    ptime: 0,
                     rtpmap: vec![
                         Rtpmap {
                             codec: "H264",
-                            payload: "97",
-                            rate: 90000,
+                            payload: 97,
+                            rate: Some(90000),
+                            channels: None,
+                            ..Default::default()
                         },
                         Rtpmap {
                             codec: "VP8",
-                            payload: "98",
-                            rate: 90000,
+                            payload: 98,
+                            rate: Some(90000),
+                            channels: None,
+                            ..Default::default()
                         },
                     ],
                     rtc_fb: vec![
                         RtcpFb {
-                            payload: "*",
+                            payload: Format::Token("*"),
                             r#type: "nack",
+                            parameter: None,
                         },
                         RtcpFb {
-                            payload: "97",
+                            payload: Format::Payload(97),
                             r#type: "trr-int",
+                            parameter: Some("100"),
                         },
                         RtcpFb {
-                            payload: "97",
+                            payload: Format::Payload(97),
                             r#type: "nack",
+                            parameter: Some("rpsi"),
                         },
                         RtcpFb {
-                            payload: "98",
+                            payload: Format::Payload(98),
                             r#type: "trr-int",
+                            parameter: Some("100"),
                         },
                         RtcpFb {
-                            payload: "98",
+                            payload: Format::Payload(98),
                             r#type: "nack",
+                            parameter: Some("rpsi"),
                         },
                     ],
                     ssrc: vec![
@@ -297,11 +1260,773 @@ a=ssrc:1399694169 baz";
                             value: None,
                         },
                     ],
+                    ..Default::default()
+                },
+            ],
+            attributes: vec![
+                Attribute {
+                    key: "ice-ufrag",
+                    value: Some("F7gI"),
+                },
+                Attribute {
+                    key: "ice-pwd",
+                    value: Some("x9cml/YzichV2+XlhiMu8g"),
+                },
+                Attribute {
+                    key: "fingerprint",
+                    value: Some(
+                        "sha-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7",
+                    ),
                 },
             ],
-            current_media: Some(2),
+            ..Default::default()
         };
 
         assert_eq!(parsed, expected);
     }
+
+    #[test]
+    fn it_reports_the_line_of_an_error() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+m=audio 54400 RTP/SAVPF 0
+a=candidate:0 1 UDP high 203.0.113.1 54400 typ host";
+
+        match Sdp::parse(sdp) {
+            Err(Error::Parse { line, raw, source }) => {
+                assert_eq!(line, 6);
+                assert_eq!(raw, "a=candidate:0 1 UDP high 203.0.113.1 54400 typ host");
+                assert_eq!(
+                    *source,
+                    Error::InvalidNumber {
+                        field: "priority",
+                        value: "high".into()
+                    }
+                );
+            }
+            result => panic!("expected a parse error, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn it_reports_every_error() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+m=audio 54400 RTP/SAVPF 0
+a=mid:0
+a=candidate:0 1 UDP high 203.0.113.1 54400 typ host
+a=rtcp-mux
+m=video high RTP/SAVPF 96
+a=mid:1
+m=video 55400 RTP/SAVPF 97
+a=mid:2
+a=ptime:soon";
+        let (parsed, errors) = Sdp::parse_all_errors(sdp);
+        let lines: Vec<_> = errors
+            .iter()
+            .map(|error| match error {
+                Error::Parse { line, .. } => Some(*line),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(lines, vec![Some(6), Some(8), Some(12), None]);
+        assert_eq!(errors[3], Error::MissingRequiredLine { key: 't' });
+        assert_eq!(parsed.media.len(), 2);
+        assert!(parsed.media[0].rtcp_mux && parsed.media[0].candidates.is_empty());
+        assert_eq!(parsed.media_by_mid("2").unwrap().port, 55400);
+        assert!(parsed.media_by_mid("1").is_none());
+        assert!(Sdp::parse_all_errors(SDP).1.is_empty());
+    }
+
+    #[test]
+    fn it_round_trips_through_sdp() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let serialized = parsed.to_sdp_string();
+        let reparsed = Sdp::parse(&serialized).unwrap();
+
+        assert!(serialized.starts_with("v=0\r\no=- 20518 0 IN IP4 203.0.113.1\r\ns=\r\n"));
+        assert_eq!(reparsed, parsed);
+        assert_eq!(reparsed.to_sdp_string(), serialized);
+    }
+
+    #[test]
+    fn it_parses_the_benchmark_fixtures() {
+        let fixtures = [
+            (include_str!("../fixtures/webrtc_offer.sdp"), 2),
+            (include_str!("../fixtures/sfu_offer.sdp"), 64),
+        ];
+
+        for (fixture, sections) in fixtures {
+            let parsed = Sdp::parse(fixture).unwrap();
+
+            assert_eq!(parsed.media.len(), sections);
+            assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn it_accepts_crlf_line_endings() {
+        let crlf = format!("{}\r\n\r\n", SDP.replace('\n', "\r\n"));
+        let parsed = Sdp::parse(&crlf).unwrap();
+
+        assert_eq!(parsed, Sdp::parse(SDP).unwrap());
+        assert!(!parsed.fingerprints[0].hash.ends_with('\r'));
+
+        let lf = parsed.to_sdp_string_with(LineEnding::Lf);
+
+        assert!(lf.starts_with("v=0\no=- 20518 0 IN IP4 203.0.113.1\ns=\n"));
+        assert!(!lf.contains('\r'));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn it_round_trips_through_json() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let json = parsed.to_json().unwrap();
+        let restored = Sdp::from_json(&json).unwrap();
+
+        assert_eq!(restored, parsed);
+        assert_eq!(restored.to_sdp_string(), parsed.to_sdp_string());
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn it_serializes_to_yaml() {
+        let yaml = Sdp::parse(SDP).unwrap().to_yaml().unwrap();
+
+        assert!(yaml.contains("ice_ufrag: F7gI\n"));
+        assert!(yaml.contains("  rtpmap:\n  - codec: PCMU\n    payload: 0\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn it_serializes_to_toml() {
+        let mut parsed = Sdp::parse(SDP).unwrap();
+        let toml = parsed.to_toml().unwrap();
+
+        assert!(toml.contains("ice_ufrag = \"F7gI\"\n"));
+        assert!(toml.contains("[[media.rtpmap]]\ncodec = \"PCMU\"\npayload = 0\n"));
+
+        parsed.origin.session_id = u64::MAX;
+
+        assert!(matches!(parsed.to_toml(), Err(Error::ConvertToToml(_))));
+    }
+
+    #[test]
+    fn it_parses_session_information_lines() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=SDP Seminar
+i=A Seminar on the session description protocol
+u=http://www.example.com/seminars/sdp.pdf
+e=j.doe@example.com (Jane Doe)
+p=+1 617 555-6011
+c=IN IP4 224.2.17.12
+t=2873397496 2873404696
+k=clear:secret
+m=audio 49170 RTP/AVP 0
+i=Main audio
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert_eq!(
+            parsed.session_information,
+            Some("A Seminar on the session description protocol")
+        );
+        assert_eq!(parsed.uri, Some("http://www.example.com/seminars/sdp.pdf"));
+        assert_eq!(parsed.email, Some("j.doe@example.com (Jane Doe)"));
+        assert_eq!(parsed.phone, Some("+1 617 555-6011"));
+        assert_eq!(parsed.encryption_key, Some("clear:secret"));
+        assert_eq!(parsed.media[0].information, Some("Main audio"));
+        assert_eq!(parsed.to_sdp_string(), sdp.replace('\n', "\r\n"));
+    }
+
+    #[test]
+    fn it_parses_multiple_fingerprints() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=fingerprint:sha-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7
+a=fingerprint:sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E
+m=audio 54400 UDP/TLS/RTP/SAVPF 0
+m=video 55400 UDP/TLS/RTP/SAVPF 97
+a=fingerprint:sha-1 00:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let (audio, video) = (&parsed.media[0], &parsed.media[1]);
+
+        assert_eq!(parsed.fingerprints.len(), 2);
+        assert_eq!(audio.effective_fingerprints(&parsed).len(), 2);
+        assert_eq!(video.effective_fingerprints(&parsed).len(), 1);
+        assert_eq!(
+            video.effective_fingerprints(&parsed)[0]
+                .digest_bytes()
+                .unwrap()[0],
+            0
+        );
+    }
+
+    #[test]
+    fn it_parses_ice_attributes() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-lite
+a=ice-options:trickle renomination
+a=ice-pacing:20
+m=audio 54400 RTP/SAVPF 0
+a=candidate:0 1 UDP 2113667327 203.0.113.1 54400 typ host
+a=end-of-candidates
+m=video 55400 RTP/SAVPF 97
+a=ice-options:ice2";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert!(parsed.ice_lite);
+        assert_eq!(parsed.ice_options, vec!["trickle", "renomination"]);
+        assert_eq!(parsed.ice_pacing, Some(20));
+        assert_eq!(
+            parsed.media[0].effective_ice_options(&parsed),
+            vec![IceOption::Trickle, IceOption::Renomination]
+        );
+        assert_eq!(
+            parsed.media[1].effective_ice_options(&parsed),
+            vec![IceOption::Ice2]
+        );
+        assert!(parsed.media[0].end_of_candidates);
+        assert!(!parsed.media[1].end_of_candidates);
+        assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+    }
+
+    #[test]
+    fn it_falls_back_to_session_ice_credentials() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+m=audio 54400 RTP/SAVPF 0
+m=video 55400 RTP/SAVPF 97
+a=ice-ufrag:Hl9k
+a=ice-pwd:asd88fgpdd777uzjYhagZg";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let credentials = |ufrag, pwd| Some(IceCredentials { ufrag, pwd });
+
+        assert_eq!(
+            parsed.ice_credentials(0),
+            credentials("F7gI", "x9cml/YzichV2+XlhiMu8g")
+        );
+        assert_eq!(
+            parsed.ice_credentials(1),
+            credentials("Hl9k", "asd88fgpdd777uzjYhagZg")
+        );
+        assert_eq!(parsed.ice_credentials(2), None);
+    }
+
+    #[test]
+    fn it_inherits_the_session_direction() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=inactive
+m=audio 54400 RTP/SAVPF 0
+m=video 55400 RTP/SAVPF 97
+a=sendonly";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert_eq!(parsed.direction, Some(Direction::Inactive));
+        assert_eq!(parsed.media[0].direction, None);
+        assert_eq!(
+            parsed.media[0].effective_direction(&parsed),
+            Direction::Inactive
+        );
+        assert_eq!(
+            parsed.media[1].effective_direction(&parsed),
+            Direction::SendOnly
+        );
+        assert_eq!(
+            Media::default().effective_direction(&Sdp::default()),
+            Direction::SendRecv
+        );
+        assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+    }
+
+    #[test]
+    fn it_rejects_media_sections() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE 0 1
+a=fingerprint:sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E
+m=audio 9 UDP/TLS/RTP/SAVPF 111
+a=mid:0
+a=rtpmap:111 opus/48000/2
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=mid:1
+a=rtpmap:96 VP8/90000
+a=rtcp-mux";
+        let mut parsed = Sdp::parse(sdp).unwrap();
+        parsed.reject_media(1).unwrap();
+
+        assert_eq!(parsed.media.len(), 2);
+        assert!(parsed.media[1].is_rejected());
+        assert!(parsed.media[1].rtpmap.is_empty());
+        assert_eq!(parsed.groups[0].mids, vec!["0"]);
+        assert!(parsed.reject_media(2).is_err());
+
+        let serialized = parsed.to_sdp_string();
+        let mut reparsed = Sdp::parse(&serialized).unwrap();
+
+        assert_eq!(reparsed.media, parsed.media);
+
+        reparsed.fingerprints.clear();
+        reparsed.reject_media(0).unwrap();
+
+        assert!(reparsed.validate().is_empty());
+    }
+
+    #[test]
+    fn it_parses_latin_1_session_names() {
+        let message = b"v=0\r\no=- 20518 0 IN IP4 203.0.113.1\r\ns=R\xe9union\r\ni=Salle \xe0 manger\r\nt=0 0\r\na=charset:ISO-8859-1\r\n";
+        let text = Sdp::decode(message, Decoding::Charset).unwrap();
+        let parsed = Sdp::parse(&text).unwrap();
+
+        assert_eq!(parsed.session_name, "Réunion");
+        assert_eq!(parsed.session_information, Some("Salle à manger"));
+        assert_eq!(parsed.charset, Some("ISO-8859-1"));
+        assert!(parsed.to_sdp_string().ends_with("a=charset:ISO-8859-1\r\n"));
+    }
+
+    #[test]
+    fn it_parses_registered_attributes() {
+        use crate::extension::AttributeParser;
+        use alloc::boxed::Box;
+        use core::any::Any;
+
+        // a=source-filter: incl IN IP4 239.0.0.1 192.168.1.1 (RFC 4570)
+        struct SourceFilter;
+
+        impl AttributeParser for SourceFilter {
+            fn name(&self) -> &str {
+                "source-filter"
+            }
+
+            fn parse(&self, value: Option<&str>) -> Result<Box<dyn Any + Send + Sync>> {
+                match parse_str(value, "source-filter")?.split_once(' ') {
+                    Some((mode, _)) => Ok(Box::new(String::from(mode))),
+                    None => Err(Error::invalid_value("source-filter", "missing filter")),
+                }
+            }
+        }
+
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=source-filter: excl IN IP4 239.0.0.1 192.168.1.2
+m=audio 5004 RTP/AVP 96
+a=rtpmap:96 L24/48000/2
+a=source-filter: incl IN IP4 239.0.0.1 192.168.1.1";
+        let mut options = ParseOptions::default();
+        options.register(SourceFilter);
+        let parsed = Sdp::parse_with(sdp, &options).unwrap();
+
+        assert_eq!(parsed.extensions[0].get::<String>().unwrap(), "excl");
+        assert_eq!(
+            parsed.media[0].extensions[0].get::<String>().unwrap(),
+            "incl"
+        );
+        assert_eq!(
+            Sdp::parse_with(&parsed.to_sdp_string(), &options).unwrap(),
+            parsed
+        );
+        assert!(parsed
+            .to_sdp_string()
+            .contains("a=source-filter:excl IN IP4 239.0.0.1 192.168.1.2\r\n"));
+        assert!(Sdp::parse(sdp).is_err());
+        assert!(Sdp::parse_with(
+            &sdp.replace("incl IN IP4 239.0.0.1 192.168.1.1", "incl"),
+            &options
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_summarizes_capabilities() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE 0 1 2
+a=ice-options:trickle
+a=setup:actpass
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0
+a=mid:0
+a=rtpmap:111 opus/48000/2
+a=rtpmap:0 PCMU/8000
+a=rtcp-mux
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=mid:1
+a=rtpmap:96 VP8/90000
+a=rtcp-mux
+m=audio 9 UDP/TLS/RTP/SAVPF 111
+a=mid:2
+a=rtpmap:111 OPUS/48000/2
+a=rtcp-mux";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert_eq!(
+            parsed.media_types(),
+            vec![MediaType::Audio, MediaType::Video]
+        );
+        assert!(parsed.has_audio() && parsed.has_video());
+        assert_eq!(parsed.codecs(), vec!["opus", "PCMU", "VP8"]);
+        assert!(parsed.uses_bundle());
+        assert!(parsed.uses_rtcp_mux());
+        assert!(!parsed.is_ice_lite());
+        assert!(parsed.is_ice_trickle());
+        assert_eq!(parsed.dtls_role(), Some("actpass"));
+        assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+
+        let empty = Sdp::default();
+
+        assert!(!empty.has_audio());
+        assert!(!empty.uses_bundle());
+        assert!(!empty.uses_rtcp_mux());
+        assert_eq!(empty.dtls_role(), None);
+    }
+
+    #[test]
+    fn it_matches_certificate_fingerprints() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=fingerprint:sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E
+m=audio 9 UDP/TLS/RTP/SAVPF 111
+a=fingerprint:SHA-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let session = parsed.fingerprints[0].digest_bytes().unwrap();
+        let media = parsed.media[0].fingerprints[0].digest_bytes().unwrap();
+
+        assert!(parsed.matches_certificate_fingerprint("sha-1", &media));
+        assert!(!parsed.matches_certificate_fingerprint("sha-256", &session));
+
+        let mut session_only = parsed.clone();
+        session_only.media[0].fingerprints.clear();
+
+        assert!(session_only.matches_certificate_fingerprint("SHA-256", &session));
+        assert!(!session_only.matches_certificate_fingerprint("sha-1", &media));
+    }
+
+    #[test]
+    fn it_splits_multipart_bodies() {
+        let message = "--boundary1\r
+Content-Type: application/sdp\r
+\r
+v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=\r
+t=0 0\r
+m=audio 54400 RTP/AVP 0\r
+\r
+--boundary1\r
+Content-Type: application/sdp\r
+\r
+v=0\r
+o=- 20519 0 IN IP4 203.0.113.1\r
+s=\r
+t=0 later\r
+\r
+--boundary1--\r
+v=0
+o=- 20520 0 IN IP4 203.0.113.1
+s=
+t=0 0";
+        let bodies = split_bodies(message);
+
+        assert_eq!(bodies.len(), 3);
+        assert!(bodies[0].starts_with("v=0\r\n"));
+        assert!(bodies[0].ends_with("m=audio 54400 RTP/AVP 0"));
+        assert!(bodies[1].ends_with("t=0 later"));
+
+        let parsed = Sdp::parse_all(message);
+
+        assert_eq!(parsed[0].as_ref().unwrap().media.len(), 1);
+        assert_eq!(parsed[1].as_ref().unwrap_err().line(), Some(4));
+        assert_eq!(parsed[2].as_ref().unwrap().origin.session_id, 20520);
+        assert!(split_bodies("INVITE sip:bob@example.com SIP/2.0").is_empty());
+    }
+
+    #[test]
+    fn it_resolves_grouped_media() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:LS 1 2
+a=group:LS 3 4 5
+a=group:FID 1 3
+m=audio 49200 RTP/AVP 0
+a=mid:1
+m=video 49202 RTP/AVP 31
+a=mid:2
+m=audio 49204 RTP/AVP 0
+a=mid:3
+m=video 49206 RTP/AVP 31
+a=mid:4";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let lip_sync = parsed.grouped_media("LS");
+        let mids =
+            |media: &[&Media<'static>]| media.iter().map(|m| m.mid.unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(lip_sync.len(), 2);
+        assert_eq!(mids(&lip_sync[0]), vec!["1", "2"]);
+        assert_eq!(mids(&lip_sync[1]), vec!["3", "4"]);
+        assert_eq!(parsed.grouped_media("FID")[0][1].port, 49204);
+        assert!(parsed.grouped_media("BUNDLE").is_empty());
+        assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+    }
+
+    #[test]
+    fn it_parses_conferencing_attributes() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=msid-semantic: WMS *
+m=video 55400 RTP/SAVPF 97
+a=content:slides
+a=label:1";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert_eq!(parsed.msid_semantic.as_ref().unwrap().semantic, "WMS");
+        assert_eq!(parsed.media[0].content, Some("slides"));
+        assert_eq!(parsed.media[0].label, Some("1"));
+        assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+    }
+
+    #[test]
+    fn it_parses_descriptive_session_attributes() {
+        let sdp = "v=0
+o=mcu 2890844526 2890842807 IN IP4 203.0.113.1
+s=Board meeting
+t=0 0
+a=cat:corporate.meetings
+a=keywds:board quarterly
+a=charset:ISO-8859-1
+m=audio 49170 RTP/AVP 0";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let serialized = parsed.to_sdp_string();
+
+        assert_eq!(parsed.category, Some("corporate.meetings"));
+        assert_eq!(parsed.keywords, Some("board quarterly"));
+        assert_eq!(parsed.charset, Some("ISO-8859-1"));
+        assert!(serialized.contains(
+            "a=cat:corporate.meetings\r\na=keywds:board quarterly\r\na=charset:ISO-8859-1\r\n"
+        ));
+        assert_eq!(Sdp::parse(&serialized).unwrap(), parsed);
+    }
+
+    #[test]
+    fn it_parses_sip_trunk_attributes() {
+        let sdp = "v=0
+o=root 1821 1821 IN IP4 203.0.113.1
+s=Asterisk PBX 18.0.0
+c=IN IP4 203.0.113.1
+t=0 0
+a=tool:Asterisk
+m=audio 10000 RTP/AVP 0 101
+a=rtpmap:0 PCMU/8000
+a=rtpmap:101 telephone-event/8000
+a=fmtp:101 0-16
+a=silenceSupp:off - - - -
+a=ptime:20
+a=recvonly";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let media = &parsed.media[0];
+
+        assert_eq!(parsed.tool, Some("Asterisk"));
+        assert_eq!(parsed.category, None);
+        assert!(!media.silence_supp.as_ref().unwrap().enabled);
+        assert_eq!(media.direction, Some(Direction::RecvOnly));
+        assert_eq!(Sdp::parse(&parsed.to_sdp_string()).unwrap(), parsed);
+    }
+
+    #[test]
+    fn it_keeps_the_order_of_session_attributes() {
+        let sdp = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=\r
+t=0 0\r
+a=group:BUNDLE 0\r
+a=fingerprint:sha-1 42:89:C5:C6:55:9D:6E:C8:E8:83:55:2A:39:F9:B6:EB:E9:A3:A9:E7\r
+a=ice-lite\r
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g\r
+a=ice-ufrag:F7gI\r
+m=audio 54400 RTP/SAVPF 0\r
+a=mid:0\r
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let names = parsed
+            .attributes
+            .iter()
+            .map(|attribute| attribute.key)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec!["group", "fingerprint", "ice-lite", "ice-pwd", "ice-ufrag"]
+        );
+        assert_eq!(parsed.to_sdp_string(), sdp);
+    }
+
+    #[test]
+    fn it_rejects_unknown_flags() {
+        assert!(Sdp::parse("v=0\na=foo").is_err());
+        assert!(Sdp::parse("v=0\nm=audio 54400 RTP/SAVPF 0\na=foo").is_err());
+    }
+
+    #[test]
+    fn it_parses_extmap_allow_mixed() {
+        let sdp = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=\r
+t=0 0\r
+a=extmap-allow-mixed\r
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r
+a=mid:0\r
+a=extmap-allow-mixed\r
+a=sendrecv\r
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert!(parsed.extmap_allow_mixed);
+        assert!(parsed.media[0].extmap_allow_mixed);
+        assert_eq!(parsed.media[0].direction, Some(Direction::SendRecv));
+        assert_eq!(parsed.to_string(), sdp);
+    }
+
+    #[test]
+    fn it_parses_a_data_channel() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel
+a=sctp-port:5000
+a=max-message-size:262144
+m=application 9 DTLS/SCTP 5000
+a=sctpmap:5000 webrtc-datachannel 1024";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let datachannel = Application {
+            sctp_port: Some(5000),
+            max_message_size: Some(262144),
+            sctpmap: None,
+        };
+        let legacy = Application {
+            sctp_port: None,
+            max_message_size: None,
+            sctpmap: Some(Sctpmap {
+                port: 5000,
+                app: "webrtc-datachannel",
+                streams: Some(1024),
+            }),
+        };
+
+        assert_eq!(parsed.media[0].formats, vec!["webrtc-datachannel"]);
+        assert!(parsed.media[0].payloads.is_empty());
+        assert_eq!(parsed.media[0].application, Some(datachannel));
+        assert_eq!(parsed.media[1].application, Some(legacy));
+    }
+
+    #[test]
+    fn it_scopes_attributes_to_the_session_or_media() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+c=IN IP4 203.0.113.1
+a=ice-ufrag:F7gI
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+a=setup:actpass
+m=audio 54400 RTP/SAVPF 0
+a=ice-ufrag:Hl9k
+m=video 55400 RTP/SAVPF 97
+c=IN IP4 203.0.113.2
+a=setup:active";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let (audio, video) = (&parsed.media[0], &parsed.media[1]);
+
+        assert_eq!(audio.effective_ice_ufrag(&parsed), Some("Hl9k"));
+        assert_eq!(video.effective_ice_ufrag(&parsed), Some("F7gI"));
+        assert_eq!(
+            audio.effective_ice_pwd(&parsed),
+            Some("x9cml/YzichV2+XlhiMu8g")
+        );
+        assert_eq!(audio.effective_setup(&parsed), Some("actpass"));
+        assert_eq!(video.effective_setup(&parsed), Some("active"));
+        assert!(audio.effective_fingerprints(&parsed).is_empty());
+        assert_eq!(
+            audio.effective_connection(&parsed).unwrap().ip_address,
+            "203.0.113.1"
+        );
+        assert_eq!(
+            video.effective_connection(&parsed).unwrap().ip_address,
+            "203.0.113.2"
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_lines() {
+        for line in &["", "v0", "=0", "vv=0"] {
+            let sdp = format!("v=0\n{}\ns=", line);
+
+            match Sdp::parse(&sdp) {
+                Err(Error::Parse { line, .. }) => assert_eq!(line, 2),
+                result => panic!("expected a parse error, found {:?}", result),
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn it_round_trips_arbitrary_sdps(sdp in crate::arbitrary::sdp()) {
+            let serialized = sdp.to_sdp_string();
+
+            proptest::prop_assert_eq!(Sdp::parse(&serialized).unwrap(), sdp);
+        }
+
+        #[test]
+        fn it_never_panics_on_arbitrary_bytes(bytes in proptest::collection::vec(0u8.., 0..512)) {
+            let _ = Sdp::parse(&String::from_utf8_lossy(&bytes));
+        }
+
+        #[test]
+        fn it_never_panics_on_arbitrary_lines(sdp in "([vostcam]=[ -~]{0,40}\n){0,20}") {
+            if let Ok(parsed) = Sdp::parse(&sdp) {
+                let _ = parsed.to_sdp_string();
+                let _ = parsed.validate();
+            }
+        }
+
+        #[test]
+        fn it_never_panics_on_arbitrary_attributes(
+            attributes in "(a=(rtpmap|fmtp|candidate|crypto|fingerprint|ssrc|rtcp-fb|rid|simulcast|rtcp|sctpmap|group|ptime)(:[ -~]{0,40})?\n){0,10}"
+        ) {
+            let sdp = format!("v=0\no=- 1 0 IN IP4 203.0.113.1\ns=\nt=0 0\nm=audio 9 RTP/AVP 0\n{}", attributes);
+
+            if let Ok(parsed) = Sdp::parse(&sdp) {
+                let _ = parsed.to_sdp_string();
+                let _ = parsed.validate();
+            }
+        }
+    }
 }
@@ -0,0 +1,621 @@
+//! The JSON shape of the JavaScript sdp-transform library.
+//!
+//! sdp-transform names its fields in camelCase and keeps list-like values as
+//! strings, e.g. `"payloads": "111 103"`, `"rtp"` for the rtpmap lines and
+//! `"rtcpFb"` for the rtcp-fb ones.  Attributes it doesn't parse are kept in
+//! an `"invalid"` list, as sdp-transform does.
+//!
+//! Matching that shape lets services written in Node and in Rust share stored
+//! SDP JSON.
+
+use crate::bandwidth::Bandwidth;
+use crate::clock::{MediaClock, TsRefclk};
+use crate::connection::Connection;
+use crate::fingerprint::Fingerprint;
+use crate::media::Media;
+use crate::sdp::Sdp;
+use crate::time::ZoneAdjustments;
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use serde_json::{json, Map, Value};
+
+pub(crate) fn to_value(sdp: &Sdp) -> Value {
+    let mut session = Map::new();
+
+    session.insert("version".into(), json!(sdp.version));
+    session.insert(
+        "origin".into(),
+        json!({
+            "username": sdp.origin.username,
+            "sessionId": sdp.origin.session_id,
+            "sessionVersion": sdp.origin.session_version,
+            "netType": sdp.origin.network_type,
+            "ipVer": ip_version(sdp.origin.ip_type),
+            "address": sdp.origin.ip_address,
+        }),
+    );
+    session.insert("name".into(), json!(sdp.session_name));
+    insert(&mut session, "description", sdp.session_information);
+    insert(&mut session, "uri", sdp.uri);
+    insert(&mut session, "email", sdp.email);
+    insert(&mut session, "phone", sdp.phone);
+    // sdp-transform keeps a single t line, and the last of its r lines
+    let description = sdp.times.first().cloned().unwrap_or_default();
+    session.insert(
+        "timing".into(),
+        json!({ "start": description.time.start_time, "stop": description.time.stop_time }),
+    );
+    insert(
+        &mut session,
+        "repeats",
+        description.repeats.last().map(|repeat| repeat.to_string()),
+    );
+    insert(
+        &mut session,
+        "timezones",
+        Some(ZoneAdjustments(&sdp.zone_adjustments).to_string())
+            .filter(|_| !sdp.zone_adjustments.is_empty()),
+    );
+    insert(
+        &mut session,
+        "connection",
+        sdp.connection.as_ref().map(connection),
+    );
+    insert_list(
+        &mut session,
+        "bandwidth",
+        sdp.bandwidth.iter().map(bandwidth),
+    );
+    insert(&mut session, "control", sdp.control);
+    insert_list(
+        &mut session,
+        "tsRefClocks",
+        sdp.ts_refclk.iter().map(ts_refclk),
+    );
+    insert(
+        &mut session,
+        "mediaClk",
+        sdp.mediaclk.as_ref().map(media_clock),
+    );
+    insert(&mut session, "iceUfrag", sdp.ice_ufrag);
+    insert(&mut session, "icePwd", sdp.ice_pwd);
+    insert(
+        &mut session,
+        "fingerprint",
+        sdp.fingerprints.last().map(fingerprint),
+    );
+    insert(&mut session, "setup", sdp.setup);
+    insert(
+        &mut session,
+        "icelite",
+        Some("ice-lite").filter(|_| sdp.ice_lite),
+    );
+    insert(
+        &mut session,
+        "extmapAllowMixed",
+        Some("extmap-allow-mixed").filter(|_| sdp.extmap_allow_mixed),
+    );
+    insert(
+        &mut session,
+        "iceOptions",
+        Some(sdp.ice_options.join(" ")).filter(|options| !options.is_empty()),
+    );
+    insert(&mut session, "direction", sdp.direction.map(|d| d.as_str()));
+    insert_list(
+        &mut session,
+        "groups",
+        sdp.groups
+            .iter()
+            .map(|group| json!({ "type": group.semantics, "mids": group.mids.join(" ") })),
+    );
+    insert(
+        &mut session,
+        "msidSemantic",
+        sdp.msid_semantic.as_ref().map(|msid_semantic| {
+            json!({
+                "semantic": msid_semantic.semantic,
+                "token": msid_semantic.ids.join(" "),
+            })
+        }),
+    );
+    insert_list(
+        &mut session,
+        "invalid",
+        [
+            sdp.category.map(|category| format!("cat:{}", category)),
+            sdp.keywords.map(|keywords| format!("keywds:{}", keywords)),
+            sdp.tool.map(|tool| format!("tool:{}", tool)),
+            sdp.maxprate
+                .map(|maxprate| format!("maxprate:{}", maxprate)),
+            sdp.ice_pacing
+                .map(|ice_pacing| format!("ice-pacing:{}", ice_pacing)),
+        ]
+        .iter()
+        .flatten()
+        .cloned()
+        .chain(sdp.extensions.iter().map(|extension| extension.to_string()))
+        .map(|value| json!({ "value": value })),
+    );
+    session.insert(
+        "media".into(),
+        sdp.media.iter().map(media).collect::<Vec<_>>().into(),
+    );
+
+    Value::Object(session)
+}
+
+fn media(media: &Media) -> Value {
+    let mut section = Map::new();
+    let payloads = match media.formats.is_empty() {
+        true => media
+            .payloads
+            .iter()
+            .map(|payload| payload.to_string())
+            .collect::<Vec<_>>(),
+        false => media
+            .formats
+            .iter()
+            .map(|format| format.to_string())
+            .collect(),
+    };
+
+    section.insert(
+        "rtp".into(),
+        media
+            .rtpmap
+            .iter()
+            .map(|rtpmap| {
+                let mut rtp = Map::new();
+                rtp.insert("payload".into(), json!(rtpmap.payload));
+                rtp.insert("codec".into(), json!(rtpmap.codec));
+                insert(&mut rtp, "rate", rtpmap.rate);
+                insert(&mut rtp, "encoding", rtpmap.channels);
+                Value::Object(rtp)
+            })
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    section.insert(
+        "fmtp".into(),
+        media
+            .fmtp
+            .iter()
+            .map(|fmtp| json!({ "payload": fmtp.payload, "config": fmtp.config }))
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    section.insert("type".into(), json!(media.r#type));
+    section.insert("port".into(), json!(media.port));
+    insert(&mut section, "numPorts", media.port_count);
+    section.insert("protocol".into(), json!(media.protocol));
+    section.insert("payloads".into(), json!(payloads.join(" ")));
+    insert(
+        &mut section,
+        "connection",
+        media.connection.as_ref().map(connection),
+    );
+    insert_list(
+        &mut section,
+        "bandwidth",
+        media.bandwidth.iter().map(bandwidth),
+    );
+    insert(
+        &mut section,
+        "rtcp",
+        media.rtcp.as_ref().map(|rtcp| {
+            let mut value = Map::new();
+            value.insert("port".into(), json!(rtcp.port));
+            insert(&mut value, "netType", rtcp.network_type);
+            insert(&mut value, "ipVer", rtcp.ip_type.map(ip_version));
+            insert(&mut value, "address", rtcp.address);
+            Value::Object(value)
+        }),
+    );
+    insert(&mut section, "mid", media.mid);
+    insert(&mut section, "label", media.label);
+    insert(&mut section, "control", media.control);
+    insert(&mut section, "content", media.content);
+    insert(&mut section, "description", media.information);
+    insert(&mut section, "iceUfrag", media.ice_ufrag);
+    insert(&mut section, "icePwd", media.ice_pwd);
+    insert(
+        &mut section,
+        "iceOptions",
+        Some(media.ice_options.join(" ")).filter(|options| !options.is_empty()),
+    );
+    insert(
+        &mut section,
+        "fingerprint",
+        media.fingerprints.last().map(fingerprint),
+    );
+    insert(&mut section, "setup", media.setup);
+    insert(
+        &mut section,
+        "direction",
+        media.direction.map(|d| d.as_str()),
+    );
+    insert(
+        &mut section,
+        "rtcpMux",
+        Some("rtcp-mux").filter(|_| media.rtcp_mux),
+    );
+    insert(
+        &mut section,
+        "bundleOnly",
+        Some("bundle-only").filter(|_| media.bundle_only),
+    );
+    insert(
+        &mut section,
+        "extmapAllowMixed",
+        Some("extmap-allow-mixed").filter(|_| media.extmap_allow_mixed),
+    );
+    insert(&mut section, "ptime", media.ptime);
+    insert(&mut section, "maxptime", media.maxptime);
+    insert(&mut section, "framerate", media.framerate);
+    insert_list(
+        &mut section,
+        "tsRefClocks",
+        media.ts_refclk.iter().map(ts_refclk),
+    );
+    insert(
+        &mut section,
+        "mediaClk",
+        media.mediaclk.as_ref().map(media_clock),
+    );
+    insert_list(
+        &mut section,
+        "rtcpFb",
+        media
+            .rtc_fb
+            .iter()
+            .filter(|rtc_fb| rtc_fb.trr_int().is_none())
+            .map(|rtc_fb| {
+                let mut value = Map::new();
+                value.insert("payload".into(), json!(rtc_fb.payload.to_string()));
+                value.insert("type".into(), json!(rtc_fb.r#type));
+                insert(&mut value, "subtype", rtc_fb.parameter);
+                Value::Object(value)
+            }),
+    );
+    insert_list(
+        &mut section,
+        "rtcpFbTrrInt",
+        media.rtc_fb.iter().filter_map(|rtc_fb| {
+            let interval = rtc_fb.trr_int()?;
+            Some(json!({ "payload": rtc_fb.payload.to_string(), "value": interval }))
+        }),
+    );
+    insert_list(
+        &mut section,
+        "ssrcs",
+        media.ssrc.iter().map(|ssrc| {
+            let mut value = Map::new();
+            value.insert("id".into(), json!(ssrc.id));
+            value.insert("attribute".into(), json!(ssrc.attribute));
+            insert(&mut value, "value", ssrc.value);
+            Value::Object(value)
+        }),
+    );
+    insert_list(
+        &mut section,
+        "ssrcGroups",
+        media.ssrc_groups.iter().map(|group| {
+            let ssrcs = group.ssrcs.iter().map(|id| id.to_string());
+            json!({
+                "semantics": group.semantics,
+                "ssrcs": ssrcs.collect::<Vec<_>>().join(" "),
+            })
+        }),
+    );
+    insert_list(
+        &mut section,
+        "candidates",
+        media.candidates.iter().map(|candidate| {
+            let mut value = json!({
+                "foundation": candidate.foundation,
+                "component": candidate.component,
+                "transport": candidate.transport,
+                "priority": candidate.priority,
+                "ip": candidate.ip,
+                "port": candidate.port,
+                "type": candidate.r#type,
+            });
+
+            // sdp-transform reads the numeric extensions as numbers
+            for &(name, extension) in &candidate.extensions {
+                value[name] = match (name, extension.parse::<u64>()) {
+                    ("rport" | "generation" | "network-id" | "network-cost", Ok(number)) => {
+                        json!(number)
+                    }
+                    _ => json!(extension),
+                };
+            }
+
+            value
+        }),
+    );
+    let remote_candidates = media.remote_candidates.iter().map(|c| c.to_string());
+    insert(
+        &mut section,
+        "remoteCandidates",
+        Some(remote_candidates.collect::<Vec<_>>().join(" ")).filter(|c| !c.is_empty()),
+    );
+    insert(
+        &mut section,
+        "endOfCandidates",
+        Some("end-of-candidates").filter(|_| media.end_of_candidates),
+    );
+    insert_list(
+        &mut section,
+        "crypto",
+        media.crypto.iter().map(|crypto| {
+            let value = crypto.to_string();
+            let config = value.splitn(3, ' ').nth(2).unwrap_or_default();
+            json!({ "id": crypto.tag, "suite": crypto.suite, "config": config })
+        }),
+    );
+    insert_list(
+        &mut section,
+        "rids",
+        media.rid.iter().map(|rid| {
+            let mut value = Map::new();
+            value.insert("id".into(), json!(rid.id));
+            value.insert("direction".into(), json!(rid.direction));
+
+            if !rid.restrictions.is_empty() {
+                let params = rid.restrictions.iter().map(|r| r.to_string());
+                value.insert("params".into(), json!(params.collect::<Vec<_>>().join(";")));
+            }
+
+            Value::Object(value)
+        }),
+    );
+    insert(
+        &mut section,
+        "simulcast",
+        media.simulcast.as_ref().map(|simulcast| {
+            let value = simulcast.to_string();
+            let mut split = value.split(' ');
+            let mut lists = Map::new();
+            let mut index = 1;
+
+            while let (Some(direction), Some(list)) = (split.next(), split.next()) {
+                lists.insert(format!("dir{}", index), json!(direction));
+                lists.insert(format!("list{}", index), json!(list));
+                index += 1;
+            }
+
+            Value::Object(lists)
+        }),
+    );
+
+    insert_list(
+        &mut section,
+        "imageattrs",
+        media.imageattr.iter().map(|imageattr| {
+            let mut value = Map::new();
+            value.insert("pt".into(), json!(imageattr.payload));
+
+            let directions = [("send", &imageattr.send), ("recv", &imageattr.recv)];
+            let directions = directions
+                .iter()
+                .filter_map(|(d, sets)| Some((d, sets.as_ref()?)));
+
+            for (index, (direction, sets)) in directions.enumerate() {
+                let attrs = match sets.is_empty() {
+                    true => "*".to_string(),
+                    false => sets
+                        .iter()
+                        .map(|set| set.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                };
+                value.insert(format!("dir{}", index + 1), json!(direction));
+                value.insert(format!("attrs{}", index + 1), json!(attrs));
+            }
+
+            Value::Object(value)
+        }),
+    );
+
+    if let Some(application) = &media.application {
+        insert(
+            &mut section,
+            "sctpmap",
+            application.sctpmap.as_ref().map(|sctpmap| {
+                let mut value = Map::new();
+                value.insert("sctpmapNumber".into(), json!(sctpmap.port));
+                value.insert("app".into(), json!(sctpmap.app));
+                insert(&mut value, "maxMessageSize", sctpmap.streams);
+                Value::Object(value)
+            }),
+        );
+        insert(&mut section, "sctpPort", application.sctp_port);
+        insert(&mut section, "maxMessageSize", application.max_message_size);
+    }
+
+    let invalid = [
+        media.quality.map(|quality| format!("quality:{}", quality)),
+        media
+            .maxprate
+            .map(|maxprate| format!("maxprate:{}", maxprate)),
+        media.orient.map(|orient| format!("orient:{}", orient)),
+        media
+            .cliprect
+            .as_ref()
+            .map(|cliprect| format!("cliprect:{}", cliprect)),
+        media
+            .silence_supp
+            .as_ref()
+            .map(|silence_supp| format!("silenceSupp:{}", silence_supp)),
+    ];
+    insert_list(
+        &mut section,
+        "invalid",
+        invalid
+            .iter()
+            .flatten()
+            .cloned()
+            .chain(media.t38.iter().flat_map(|t38| {
+                t38.to_string()
+                    .lines()
+                    .map(|line| line.trim_start_matches("a=").into())
+                    .collect::<Vec<String>>()
+            }))
+            .chain(
+                media
+                    .extensions
+                    .iter()
+                    .map(|extension| extension.to_string()),
+            )
+            .map(|value| json!({ "value": value })),
+    );
+
+    Value::Object(section)
+}
+
+fn connection(connection: &Connection) -> Value {
+    let mut ip = connection.ip_address.to_string();
+
+    if let Some(ttl) = connection.ttl {
+        ip = format!("{}/{}", ip, ttl);
+    }
+
+    if let Some(number_of_addresses) = connection.number_of_addresses {
+        ip = format!("{}/{}", ip, number_of_addresses);
+    }
+
+    json!({ "version": ip_version(connection.ip_type), "ip": ip })
+}
+
+fn bandwidth(bandwidth: &Bandwidth) -> Value {
+    json!({ "type": bandwidth.r#type, "limit": bandwidth.bandwidth })
+}
+
+fn ts_refclk(ts_refclk: &TsRefclk) -> Value {
+    let mut value = Map::new();
+    value.insert("clksrc".into(), json!(ts_refclk.source));
+    insert(&mut value, "clksrcExt", ts_refclk.value);
+    Value::Object(value)
+}
+
+fn media_clock(mediaclk: &MediaClock) -> Value {
+    let mut value = Map::new();
+    insert(&mut value, "id", mediaclk.id);
+    value.insert("mediaClockName".into(), json!(mediaclk.mode));
+    insert(
+        &mut value,
+        "mediaClockValue",
+        mediaclk.value.map(|clock| {
+            clock
+                .parse::<u64>()
+                .map_or_else(|_| json!(clock), |clock| json!(clock))
+        }),
+    );
+
+    if let Some((numerator, denominator)) = mediaclk.rate {
+        value.insert("rateNumerator".into(), json!(numerator));
+        value.insert("rateDenominator".into(), json!(denominator));
+    }
+
+    Value::Object(value)
+}
+
+fn fingerprint(fingerprint: &Fingerprint) -> Value {
+    json!({ "type": fingerprint.r#type, "hash": fingerprint.hash })
+}
+
+// IP4 and IP6 are numbers in sdp-transform
+fn ip_version(ip_type: &str) -> Value {
+    match ip_type.strip_prefix("IP").map(str::parse::<u8>) {
+        Some(Ok(version)) => json!(version),
+        _ => json!(ip_type),
+    }
+}
+
+fn insert<T: Into<Value>>(object: &mut Map<String, Value>, key: &str, value: Option<T>) {
+    if let Some(value) = value {
+        object.insert(key.into(), value.into());
+    }
+}
+
+fn insert_list(object: &mut Map<String, Value>, key: &str, values: impl Iterator<Item = Value>) {
+    let values = values.collect::<Vec<_>>();
+
+    if !values.is_empty() {
+        object.insert(key.into(), values.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_the_sdp_transform_shape() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=-
+t=0 0
+a=group:BUNDLE 0
+a=msid-semantic: WMS *
+m=audio 54400 UDP/TLS/RTP/SAVPF 111 0
+c=IN IP4 203.0.113.1
+a=mid:0
+a=ice-ufrag:F7gI
+a=sendrecv
+a=rtpmap:111 opus/48000/2
+a=rtpmap:0 PCMU/8000
+a=fmtp:111 minptime=10;useinbandfec=1
+a=rtcp-fb:111 transport-cc
+a=rtcp-fb:111 trr-int 100
+a=ssrc:1399694169 cname:foo
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54400 typ host
+a=quality:10
+a=mediaclk:direct=0 rate=48000/1
+m=video 54402 UDP/TLS/RTP/SAVPF 96
+a=imageattr:96 recv [x=1280,y=720] send *";
+        let value = to_value(&Sdp::parse(sdp).unwrap());
+        let audio = &value["media"][0];
+
+        assert_eq!(value["origin"]["ipVer"], 4);
+        assert_eq!(value["groups"], json!([{ "type": "BUNDLE", "mids": "0" }]));
+        assert_eq!(value["msidSemantic"]["token"], "*");
+        assert_eq!(audio["payloads"], "111 0");
+        assert_eq!(
+            audio["connection"],
+            json!({ "version": 4, "ip": "203.0.113.1" })
+        );
+        assert_eq!(audio["iceUfrag"], "F7gI");
+        assert_eq!(audio["direction"], "sendrecv");
+        assert_eq!(
+            audio["rtp"],
+            json!([
+                { "payload": 111, "codec": "opus", "rate": 48000, "encoding": 2 },
+                { "payload": 0, "codec": "PCMU", "rate": 8000 },
+            ])
+        );
+        assert_eq!(audio["fmtp"][0]["config"], "minptime=10;useinbandfec=1");
+        assert_eq!(
+            audio["rtcpFb"],
+            json!([{ "payload": "111", "type": "transport-cc" }])
+        );
+        assert_eq!(
+            audio["rtcpFbTrrInt"],
+            json!([{ "payload": "111", "value": 100 }])
+        );
+        assert_eq!(
+            audio["ssrcs"],
+            json!([{ "id": 1399694169u64, "attribute": "cname", "value": "foo" }])
+        );
+        assert_eq!(audio["candidates"][0]["priority"], 2113667327u32);
+        assert_eq!(audio["invalid"], json!([{ "value": "quality:10" }]));
+        assert_eq!(
+            audio["mediaClk"],
+            json!({ "mediaClockName": "direct", "mediaClockValue": 0, "rateNumerator": 48000, "rateDenominator": 1 })
+        );
+        assert_eq!(
+            value["media"][1]["imageattrs"],
+            json!([{ "pt": 96, "dir1": "send", "attrs1": "*", "dir2": "recv", "attrs2": "[x=1280,y=720]" }])
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Options for writing a SDP, e.g. for peers sensitive to the order of the
+//! lines of a media section.
+//!
+//! RFC 8866 only orders the line types of a section, so the attributes may
+//! come in any order, but some SIP stacks only accept the order their own
+//! implementation writes.  A profile writes the media sections like a known
+//! implementation does, the session lines are the same for every profile.
+
+use crate::sdp::{LineEnding, Sdp};
+use core::fmt;
+
+/// The order the lines of each media section are written in
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Profile {
+    /// The crate's order: the connection, the ICE and DTLS parameters, the
+    /// mid and the direction, then the codecs and the candidates
+    #[default]
+    Rfc,
+
+    /// The order of Chrome (libwebrtc): the candidates right after the
+    /// connection and a=rtcp, a=rtcp-mux after the direction, and the rtpmap,
+    /// rtcp-fb and fmtp lines of each payload type together, before the ssrc
+    /// lines
+    Chrome,
+}
+
+/// A group of lines of a media section, always written together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum MediaLines {
+    /// i= and c=
+    Info,
+    Ice,
+    /// fingerprint and setup
+    Dtls,
+    /// mid, content and label
+    Identification,
+    Crypto,
+    Direction,
+    Rtcp,
+    RtcpMux,
+    /// every rtpmap line, then every fmtp line, then every rtcp-fb line
+    Codecs,
+    /// the rtpmap, rtcp-fb and fmtp lines of each payload type in turn
+    CodecsByPayload,
+    /// imageattr, ptime and the other format parameters
+    Format,
+    Candidates,
+    Ssrc,
+    /// rid and simulcast
+    Simulcast,
+    /// the data channel and T.38 attributes
+    Application,
+    Extensions,
+}
+
+const RFC_ORDER: [MediaLines; 15] = [
+    MediaLines::Info,
+    MediaLines::Ice,
+    MediaLines::Dtls,
+    MediaLines::Identification,
+    MediaLines::Crypto,
+    MediaLines::Direction,
+    MediaLines::Rtcp,
+    MediaLines::RtcpMux,
+    MediaLines::Codecs,
+    MediaLines::Format,
+    MediaLines::Candidates,
+    MediaLines::Ssrc,
+    MediaLines::Simulcast,
+    MediaLines::Application,
+    MediaLines::Extensions,
+];
+
+const CHROME_ORDER: [MediaLines; 15] = [
+    MediaLines::Info,
+    MediaLines::Rtcp,
+    MediaLines::Candidates,
+    MediaLines::Ice,
+    MediaLines::Dtls,
+    MediaLines::Identification,
+    MediaLines::Application,
+    MediaLines::Direction,
+    MediaLines::RtcpMux,
+    MediaLines::Crypto,
+    MediaLines::CodecsByPayload,
+    MediaLines::Format,
+    MediaLines::Ssrc,
+    MediaLines::Simulcast,
+    MediaLines::Extensions,
+];
+
+impl Profile {
+    pub(crate) fn media_order(&self) -> &'static [MediaLines] {
+        match self {
+            Profile::Rfc => &RFC_ORDER,
+            Profile::Chrome => &CHROME_ORDER,
+        }
+    }
+}
+
+/// Options for writing a SDP
+///
+/// let options = SerializeOptions::profile(Profile::Chrome);
+/// let sdp_string = sdp.to_sdp_string_with_options(&options);
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SerializeOptions {
+    pub profile: Profile,
+    pub line_ending: LineEnding,
+}
+
+impl SerializeOptions {
+    /// The default options, with the lines in the order of `profile`
+    pub fn profile(profile: Profile) -> Self {
+        Self {
+            profile,
+            ..Default::default()
+        }
+    }
+}
+
+/// Writes the session, then each media section in the order of the profile
+pub(crate) struct Serialized<'b, 'a>(pub(crate) &'b Sdp<'a>, pub(crate) Profile);
+
+impl<'b, 'a> fmt::Display for Serialized<'b, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Serialized(sdp, profile) = self;
+        sdp.write_session(f)?;
+
+        for media in &sdp.media {
+            media.write_with(f, *profile)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=-\r
+t=0 0\r
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r
+c=IN IP4 0.0.0.0\r
+a=ice-ufrag:F7gI\r
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g\r
+a=mid:0\r
+a=sendrecv\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=rtcp-mux\r
+a=rtpmap:96 VP8/90000\r
+a=rtpmap:97 rtx/90000\r
+a=fmtp:97 apt=96\r
+a=rtcp-fb:96 nack\r
+a=rtcp-fb:* ccm fir\r
+a=candidate:1 1 udp 2122260223 192.168.0.196 46243 typ host\r
+a=ssrc:1 cname:user@example.com\r
+";
+
+    #[test]
+    fn it_writes_the_profile_order() {
+        let parsed = Sdp::parse(SDP).unwrap();
+
+        assert_eq!(parsed.to_sdp_string_with_options(&Default::default()), SDP);
+        assert_eq!(
+            parsed.to_sdp_string_with_options(&SerializeOptions::profile(Profile::Chrome)),
+            "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=-\r
+t=0 0\r
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=candidate:1 1 udp 2122260223 192.168.0.196 46243 typ host\r
+a=ice-ufrag:F7gI\r
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g\r
+a=mid:0\r
+a=sendrecv\r
+a=rtcp-mux\r
+a=rtpmap:96 VP8/90000\r
+a=rtcp-fb:96 nack\r
+a=rtpmap:97 rtx/90000\r
+a=fmtp:97 apt=96\r
+a=rtcp-fb:* ccm fir\r
+a=ssrc:1 cname:user@example.com\r
+"
+        );
+    }
+
+    #[test]
+    fn it_writes_the_line_ending() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let options = SerializeOptions {
+            line_ending: LineEnding::Lf,
+            ..SerializeOptions::profile(Profile::Chrome)
+        };
+        let written = parsed.to_sdp_string_with_options(&options);
+
+        assert!(!written.contains('\r'));
+        assert_eq!(Sdp::parse(&written).unwrap(), parsed);
+    }
+}
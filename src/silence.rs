@@ -0,0 +1,119 @@
+use crate::error::{Error, Result};
+use crate::utils::{parse_number, parse_str};
+use alloc::{format, string::String, string::ToString};
+use core::fmt;
+
+/// SDP Silence Suppression
+///
+/// a=silenceSupp:off - - - -
+/// a=silenceSupp:on 500 standard No SID 20
+///
+/// Whether a PSTN gateway suppresses silence and sends, or expects, comfort
+/// noise in its place (RFC 3108 section 5.6). The enable flag is followed by
+/// the silence timer in milliseconds, the preference, the use of silence
+/// insertion descriptors, e.g. "No SID" or "Fixed Noise", and the noise level
+/// in -dBm0.  Any of those may be "-" when it doesn't apply.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SilenceSupp<'a> {
+    pub enabled: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timer: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preference: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid_use: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fxns_level: Option<u8>,
+}
+
+impl<'a> SilenceSupp<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.splitn(4, ' ');
+        let enabled = match parse_str(split.next(), "silenceSupp enable")? {
+            "on" => true,
+            "off" => false,
+            enable => {
+                return Err(Error::invalid_value(
+                    "silenceSupp enable",
+                    format!("'{}' is neither on nor off", enable),
+                ))
+            }
+        };
+        let timer = parse_str(split.next(), "silenceSupp timer")?;
+        let preference = parse_str(split.next(), "silenceSupp preference")?;
+
+        // the SID use may be several words, e.g. No SID, before the noise level
+        let rest = parse_str(split.next(), "silenceSupp sid use")?;
+        let (sid_use, fxns_level) = rest.rsplit_once(' ').ok_or(Error::MissingField {
+            field: "silenceSupp fxnslevel",
+        })?;
+
+        Ok(Self {
+            enabled,
+            timer: dash_or(timer, |timer| {
+                parse_number::<u16>(Some(timer), "silenceSupp timer")
+            })?,
+            preference: dash_or(preference, Result::Ok)?,
+            sid_use: dash_or(sid_use, Result::Ok)?,
+            fxns_level: dash_or(fxns_level, |level| {
+                parse_number::<u8>(Some(level), "silenceSupp fxnslevel")
+            })?,
+        })
+    }
+}
+
+// "-" for a parameter that doesn't apply
+fn dash_or<'a, T>(value: &'a str, parse: impl FnOnce(&'a str) -> Result<T>) -> Result<Option<T>> {
+    match value {
+        "-" => Ok(None),
+        value => parse(value).map(Some),
+    }
+}
+
+impl<'a> fmt::Display for SilenceSupp<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dash = |value: Option<String>| value.unwrap_or_else(|| "-".into());
+
+        write!(
+            f,
+            "{} {} {} {} {}",
+            if self.enabled { "on" } else { "off" },
+            dash(self.timer.map(|timer| timer.to_string())),
+            self.preference.unwrap_or("-"),
+            self.sid_use.unwrap_or("-"),
+            dash(self.fxns_level.map(|level| level.to_string())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_silence_suppression() {
+        let parsed = SilenceSupp::new("off - - - -").unwrap();
+
+        assert_eq!(parsed, SilenceSupp::default());
+        assert_eq!(parsed.to_string(), "off - - - -");
+
+        let silence_supp = "on 500 standard No SID 20";
+        let parsed = SilenceSupp::new(silence_supp).unwrap();
+        let expected = SilenceSupp {
+            enabled: true,
+            timer: Some(500),
+            preference: Some("standard"),
+            sid_use: Some("No SID"),
+            fxns_level: Some(20),
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), silence_supp);
+        assert!(SilenceSupp::new("yes - - - -").is_err());
+        assert!(SilenceSupp::new("off - -").is_err());
+    }
+}
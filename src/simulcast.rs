@@ -0,0 +1,282 @@
+use crate::error::{Error, Result};
+use crate::utils::parse_str;
+use alloc::{borrow::Cow, format, vec::Vec};
+use core::fmt;
+
+/// RID
+///
+/// a=rid:hi send max-width=1280;max-height=720
+///
+/// A rid (RTP Stream Identifier, RFC 8851) names an RTP stream within a media
+/// section and optionally restricts it, e.g. to a set of payload types
+/// (pt=96,97) or to a maximum resolution. Rids are referenced from the
+/// a=simulcast line to describe the encodings of a simulcast stream.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Rid<'a> {
+    pub id: &'a str,
+    pub direction: &'a str,
+    pub restrictions: Vec<RidRestriction<'a>>,
+}
+
+/// A single rid restriction such as max-width=1280
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RidRestriction<'a> {
+    pub name: &'a str,
+
+    /// The value, owned once rewritten, e.g. the pt= list by
+    /// `Sdp::remap_payload`
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+impl<'a> Rid<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut split = value.splitn(3, ' ');
+        let id = parse_str(split.next(), "rid")?;
+        let direction = parse_direction(parse_str(split.next(), "direction")?)?;
+        let restrictions = split
+            .next()
+            .map(|restrictions| {
+                restrictions
+                    .split(';')
+                    .filter(|restriction| !restriction.is_empty())
+                    .map(RidRestriction::new)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            id,
+            direction,
+            restrictions,
+        })
+    }
+}
+
+impl<'a> fmt::Display for Rid<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.direction)?;
+
+        for (index, restriction) in self.restrictions.iter().enumerate() {
+            let separator = if index == 0 { ' ' } else { ';' };
+            write!(f, "{}{}", separator, restriction)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> RidRestriction<'a> {
+    pub fn new(value: &'a str) -> Self {
+        let mut split = value.splitn(2, '=');
+
+        Self {
+            name: split.next().unwrap_or_default(),
+            value: split.next().map(Cow::Borrowed),
+        }
+    }
+}
+
+impl<'a> fmt::Display for RidRestriction<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}={}", self.name, value),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Simulcast
+///
+/// a=simulcast:send hi;mid;~lo recv 1,2
+///
+/// The simulcast attribute (RFC 8853) lists the rids that make up the simulcast
+/// streams in each direction. Streams are separated by ';' and alternative
+/// encodings of the same stream by ','. A rid prefixed with '~' is paused.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Simulcast<'a> {
+    #[serde(borrow)]
+    pub send: Vec<Vec<SimulcastStream<'a>>>,
+
+    #[serde(borrow)]
+    pub recv: Vec<Vec<SimulcastStream<'a>>>,
+}
+
+/// A single rid referenced by a simulcast line
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SimulcastStream<'a> {
+    pub rid: &'a str,
+    pub paused: bool,
+}
+
+impl<'a> Simulcast<'a> {
+    pub fn new(value: &'a str) -> Result<Self> {
+        let mut simulcast = Self::default();
+        let mut split = value.split(' ');
+
+        while let Some(direction) = split.next() {
+            let streams = parse_streams(parse_str(split.next(), "streams")?)?;
+
+            match parse_direction(direction)? {
+                "send" => simulcast.send = streams,
+                _ => simulcast.recv = streams,
+            }
+        }
+
+        Ok(simulcast)
+    }
+}
+
+impl<'a> fmt::Display for Simulcast<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let directions = [("send", &self.send), ("recv", &self.recv)];
+        let mut separator = "";
+
+        for (direction, streams) in directions.iter().filter(|(_, s)| !s.is_empty()) {
+            write!(f, "{}{} ", separator, direction)?;
+            write_streams(f, streams)?;
+            separator = " ";
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for SimulcastStream<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.paused {
+            write!(f, "~")?;
+        }
+
+        write!(f, "{}", self.rid)
+    }
+}
+
+fn write_streams(f: &mut fmt::Formatter, streams: &[Vec<SimulcastStream>]) -> fmt::Result {
+    for (index, alternatives) in streams.iter().enumerate() {
+        if index > 0 {
+            write!(f, ";")?;
+        }
+
+        for (index, stream) in alternatives.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{}", stream)?;
+        }
+    }
+
+    Ok(())
+}
+
+// a stream list is ';' separated streams of ',' separated alternatives
+fn parse_streams(value: &str) -> Result<Vec<Vec<SimulcastStream<'_>>>> {
+    value
+        .split(';')
+        .map(|alternatives| {
+            alternatives
+                .split(',')
+                .map(|rid| {
+                    let (rid, paused) = match rid.strip_prefix('~') {
+                        Some(rid) => (rid, true),
+                        None => (rid, false),
+                    };
+
+                    if rid.is_empty() {
+                        return Err(Error::invalid_value(
+                            "rid",
+                            format!("empty rid in {}", value),
+                        ));
+                    }
+
+                    Ok(SimulcastStream { rid, paused })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_direction(value: &str) -> Result<&str> {
+    match value {
+        "send" | "recv" => Ok(value),
+        _ => Err(Error::invalid_value(
+            "direction",
+            format!("'{}', expected send or recv", value),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_rid() {
+        let rid = "hi send pt=96,97;max-width=1280;max-height=720";
+        let parsed = Rid::new(rid).unwrap();
+        let expected = Rid {
+            id: "hi",
+            direction: "send",
+            restrictions: vec![
+                RidRestriction {
+                    name: "pt",
+                    value: Some("96,97".into()),
+                },
+                RidRestriction {
+                    name: "max-width",
+                    value: Some("1280".into()),
+                },
+                RidRestriction {
+                    name: "max-height",
+                    value: Some("720".into()),
+                },
+            ],
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), rid);
+    }
+
+    #[test]
+    fn it_parses_a_simulcast() {
+        let simulcast = "send hi;mid;~lo recv 1,2";
+        let parsed = Simulcast::new(simulcast).unwrap();
+        let expected = Simulcast {
+            send: vec![
+                vec![SimulcastStream {
+                    rid: "hi",
+                    paused: false,
+                }],
+                vec![SimulcastStream {
+                    rid: "mid",
+                    paused: false,
+                }],
+                vec![SimulcastStream {
+                    rid: "lo",
+                    paused: true,
+                }],
+            ],
+            recv: vec![vec![
+                SimulcastStream {
+                    rid: "1",
+                    paused: false,
+                },
+                SimulcastStream {
+                    rid: "2",
+                    paused: false,
+                },
+            ]],
+        };
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), simulcast);
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_simulcast() {
+        assert!(Simulcast::new("both hi;lo").is_err());
+        assert!(Simulcast::new("send").is_err());
+        assert!(Simulcast::new("send hi;;lo").is_err());
+    }
+}
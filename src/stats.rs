@@ -0,0 +1,133 @@
+//! A summary of what a SDP offers, for dashboards tallying the offers of
+//! clients in production.
+
+use crate::bandwidth::BandwidthType;
+use crate::connection::Connection;
+use crate::media::{Candidate, Media, Rtpmap};
+use crate::sdp::Sdp;
+use crate::visit::Visitor;
+use alloc::{collections::BTreeMap, string::String};
+
+/// The counts of a SDP, see `Sdp::stats`
+///
+/// The transports and types of the candidates are lowercased, as ICE matches
+/// them without case (RFC 8839 section 5.1).
+#[derive(Debug, Default, Clone, Serialize, PartialEq, Eq)]
+pub struct SdpStats<'a> {
+    /// The media sections by media type, e.g. audio
+    pub media: BTreeMap<&'a str, usize>,
+
+    /// The rtpmap lines of every media section
+    pub codecs: usize,
+
+    pub candidates_by_type: BTreeMap<String, usize>,
+    pub candidates_by_transport: BTreeMap<String, usize>,
+
+    /// A c line, the origin or a candidate has an IPv6 address
+    pub has_ipv6: bool,
+
+    /// A media section is secured with DTLS
+    pub uses_dtls: bool,
+
+    /// The session's b=AS (or b=CT), or else the total b=AS of the media
+    /// sections, in kilobits per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bandwidth: Option<u64>,
+}
+
+// the counts, taken while walking the sdp
+struct Tally<'a>(SdpStats<'a>);
+
+impl<'a> Visitor<'a> for Tally<'a> {
+    fn visit_session(&mut self, sdp: &Sdp<'a>) {
+        let stats = &mut self.0;
+
+        stats.has_ipv6 |= sdp.origin.ip_type == "IP6" || is_ipv6(sdp.connection.as_ref());
+        stats.bandwidth = sdp
+            .bandwidth_for(BandwidthType::As)
+            .or_else(|| sdp.bandwidth_for(BandwidthType::Ct));
+    }
+
+    fn visit_media(&mut self, _media: usize, section: &Media<'a>) {
+        let stats = &mut self.0;
+
+        *stats.media.entry(section.r#type).or_default() += 1;
+        stats.has_ipv6 |= is_ipv6(section.connection.as_ref());
+        stats.uses_dtls |= section.transport_protocol().is_dtls();
+    }
+
+    fn visit_rtpmap(&mut self, _media: usize, _rtpmap: &Rtpmap<'a>) {
+        self.0.codecs += 1;
+    }
+
+    fn visit_candidate(&mut self, _media: usize, candidate: &Candidate<'a>) {
+        let stats = &mut self.0;
+
+        *stats
+            .candidates_by_type
+            .entry(candidate.r#type.to_ascii_lowercase())
+            .or_default() += 1;
+        *stats
+            .candidates_by_transport
+            .entry(candidate.transport.to_ascii_lowercase())
+            .or_default() += 1;
+        stats.has_ipv6 |= candidate.is_ipv6();
+    }
+}
+
+fn is_ipv6(connection: Option<&Connection>) -> bool {
+    matches!(connection, Some(connection) if connection.ip_type == "IP6")
+}
+
+pub(crate) fn stats<'a>(sdp: &Sdp<'a>) -> SdpStats<'a> {
+    let mut tally = Tally(SdpStats::default());
+    sdp.visit(&mut tally);
+
+    let mut stats = tally.0;
+
+    if stats.bandwidth.is_none() {
+        stats.bandwidth = sdp
+            .media
+            .iter()
+            .filter_map(|media| media.bandwidth_for(BandwidthType::As))
+            .reduce(|total, bandwidth| total + bandwidth);
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn it_gathers_the_stats_of_a_sdp() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0
+b=AS:64
+a=rtpmap:111 opus/48000/2
+a=rtpmap:0 PCMU/8000
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54400 typ host
+a=candidate:2 1 udp 1694498815 2001:db8::1 54400 typ srflx
+m=video 9 UDP/TLS/RTP/SAVPF 96
+b=AS:512
+a=rtpmap:96 VP8/90000
+a=candidate:3 1 TCP 1518280447 203.0.113.1 9 typ host
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel";
+        let stats = Sdp::parse(sdp).unwrap().stats();
+        let media: Vec<_> = stats.media.into_iter().collect();
+        let types: Vec<_> = stats.candidates_by_type.into_iter().collect();
+        let transports: Vec<_> = stats.candidates_by_transport.into_iter().collect();
+
+        assert_eq!(media, vec![("application", 1), ("audio", 1), ("video", 1)]);
+        assert_eq!(stats.codecs, 3);
+        assert_eq!(types, vec![("host".into(), 2), ("srflx".into(), 1)]);
+        assert_eq!(transports, vec![("tcp".into(), 1), ("udp".into(), 2)]);
+        assert!(stats.has_ipv6 && stats.uses_dtls);
+        assert_eq!(stats.bandwidth, Some(576));
+    }
+}
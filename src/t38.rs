@@ -0,0 +1,222 @@
+use crate::attribute::Attribute;
+use crate::error::{Error, Result};
+use crate::utils::{parse_number, parse_str, write_attribute, write_flag, write_line};
+use crate::{push_value, set_value};
+use alloc::{format, vec::Vec};
+use core::fmt;
+
+/// SDP T.38 Fax
+///
+/// m=image 12345 udptl t38
+/// a=T38FaxVersion:0
+/// a=T38MaxBitRate:14400
+/// a=T38FaxFillBitRemoval
+/// a=T38FaxRateManagement:transferredTCF
+/// a=T38FaxMaxDatagram:400
+/// a=T38FaxUdpEC:t38UDPRedundancy
+///
+/// A fax gateway relays the fax as T.38 over UDPTL, with the version of the
+/// protocol, the highest fax bit rate, the fax image options and how the
+/// training check is done (ITU-T T.38 Annex D, RFC 3362).  The buffer and
+/// datagram sizes are in bytes, and the error correction is either redundancy
+/// or FEC.  Other T38 attributes, e.g. a=T38VendorInfo, are kept as they are.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct T38<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bit_rate: Option<u32>,
+
+    #[serde(default)]
+    pub fill_bit_removal: bool,
+
+    #[serde(default)]
+    pub transcoding_mmr: bool,
+
+    #[serde(default)]
+    pub transcoding_jbig: bool,
+
+    /// localTCF or transferredTCF
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_management: Option<&'a str>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_buffer: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_datagram: Option<u32>,
+
+    /// t38UDPRedundancy or t38UDPFEC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub udp_ec: Option<&'a str>,
+
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub other: Vec<Attribute<'a>>,
+}
+
+impl<'a> T38<'a> {
+    // a=T38FaxVersion:0, or a value-less option such as a=T38FaxFillBitRemoval
+    pub(crate) fn parse_attribute(&mut self, key: &'a str, value: Option<&'a str>) -> Result<()> {
+        match key {
+            "T38FaxVersion" => set_value!(
+                self.version,
+                parse_number::<u8>(value, "T38FaxVersion").map(Some)
+            ),
+            "T38MaxBitRate" => set_value!(
+                self.max_bit_rate,
+                parse_number::<u32>(value, "T38MaxBitRate").map(Some)
+            ),
+            "T38FaxFillBitRemoval" => set_value!(
+                self.fill_bit_removal,
+                parse_option(value, "T38FaxFillBitRemoval")
+            ),
+            "T38FaxTranscodingMMR" => set_value!(
+                self.transcoding_mmr,
+                parse_option(value, "T38FaxTranscodingMMR")
+            ),
+            "T38FaxTranscodingJBIG" => set_value!(
+                self.transcoding_jbig,
+                parse_option(value, "T38FaxTranscodingJBIG")
+            ),
+            "T38FaxRateManagement" => set_value!(
+                self.rate_management,
+                parse_str(value, "T38FaxRateManagement").map(Some)
+            ),
+            "T38FaxMaxBuffer" => set_value!(
+                self.max_buffer,
+                parse_number::<u32>(value, "T38FaxMaxBuffer").map(Some)
+            ),
+            "T38FaxMaxDatagram" => set_value!(
+                self.max_datagram,
+                parse_number::<u32>(value, "T38FaxMaxDatagram").map(Some)
+            ),
+            "T38FaxUdpEC" => set_value!(self.udp_ec, parse_str(value, "T38FaxUdpEC").map(Some)),
+            _ => push_value!(self.other, Result::Ok(Attribute { key, value })),
+        }
+    }
+}
+
+// the image options are flags, though some gateways send them as :0 or :1
+fn parse_option(value: Option<&str>, field: &'static str) -> Result<bool> {
+    match value {
+        None | Some("1") => Ok(true),
+        Some("0") => Ok(false),
+        Some(value) => Err(Error::invalid_value(
+            field,
+            format!("'{}' is neither 0 nor 1", value),
+        )),
+    }
+}
+
+impl<'a> fmt::Display for T38<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(version) = self.version {
+            write_attribute(f, "T38FaxVersion", version)?;
+        }
+
+        if let Some(max_bit_rate) = self.max_bit_rate {
+            write_attribute(f, "T38MaxBitRate", max_bit_rate)?;
+        }
+
+        if self.fill_bit_removal {
+            write_flag(f, "T38FaxFillBitRemoval")?;
+        }
+
+        if self.transcoding_mmr {
+            write_flag(f, "T38FaxTranscodingMMR")?;
+        }
+
+        if self.transcoding_jbig {
+            write_flag(f, "T38FaxTranscodingJBIG")?;
+        }
+
+        if let Some(rate_management) = self.rate_management {
+            write_attribute(f, "T38FaxRateManagement", rate_management)?;
+        }
+
+        if let Some(max_buffer) = self.max_buffer {
+            write_attribute(f, "T38FaxMaxBuffer", max_buffer)?;
+        }
+
+        if let Some(max_datagram) = self.max_datagram {
+            write_attribute(f, "T38FaxMaxDatagram", max_datagram)?;
+        }
+
+        if let Some(udp_ec) = self.udp_ec {
+            write_attribute(f, "T38FaxUdpEC", udp_ec)?;
+        }
+
+        for attribute in &self.other {
+            write_line(f, 'a', attribute)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::{MediaType, TransportProtocol};
+    use crate::sdp::Sdp;
+
+    const SDP: &str = "v=0\r
+o=- 20518 0 IN IP4 203.0.113.1\r
+s=-\r
+c=IN IP4 203.0.113.1\r
+t=0 0\r
+m=image 12345 udptl t38\r
+a=T38FaxVersion:0\r
+a=T38MaxBitRate:14400\r
+a=T38FaxFillBitRemoval\r
+a=T38FaxRateManagement:transferredTCF\r
+a=T38FaxMaxBuffer:262\r
+a=T38FaxMaxDatagram:400\r
+a=T38FaxUdpEC:t38UDPRedundancy\r
+a=T38VendorInfo:0 0 0\r
+";
+
+    #[test]
+    fn it_parses_t38_fax_media() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let media = &parsed.media[0];
+        let expected = T38 {
+            version: Some(0),
+            max_bit_rate: Some(14400),
+            fill_bit_removal: true,
+            rate_management: Some("transferredTCF"),
+            max_buffer: Some(262),
+            max_datagram: Some(400),
+            udp_ec: Some("t38UDPRedundancy"),
+            other: vec![Attribute {
+                key: "T38VendorInfo",
+                value: Some("0 0 0"),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(media.media_type(), MediaType::Image);
+        assert_eq!(media.transport_protocol(), TransportProtocol::Udptl);
+        assert_eq!(media.formats, vec!["t38"]);
+        assert_eq!(media.t38, Some(expected));
+        assert_eq!(parsed.to_string(), SDP);
+    }
+
+    #[test]
+    fn it_parses_t38_options_with_a_value() {
+        let mut t38 = T38::default();
+        t38.parse_attribute("T38FaxTranscodingMMR", Some("1"))
+            .unwrap();
+        t38.parse_attribute("T38FaxFillBitRemoval", Some("0"))
+            .unwrap();
+
+        assert!(t38.transcoding_mmr);
+        assert!(!t38.fill_bit_removal);
+        assert!(t38
+            .parse_attribute("T38FaxTranscodingJBIG", Some("yes"))
+            .is_err());
+        assert!(t38.parse_attribute("T38FaxVersion", None).is_err());
+        assert_eq!(t38.to_string(), "a=T38FaxTranscodingMMR\r\n");
+    }
+}
@@ -0,0 +1,220 @@
+//! Baseline WebRTC offers, to bootstrap server-side peers.
+//!
+//! The offer bundles an audio and a video section on one transport with
+//! rtcp-mux, like browsers do, so any WebRTC endpoint can answer it.  The
+//! candidates are left out, to be trickled once the ICE agent gathers them.
+
+use crate::connection::Connection;
+use crate::direction::Direction;
+use crate::fingerprint::Fingerprint;
+use crate::group::Group;
+use crate::ice::IceCredentials;
+use crate::media::{Fmtp, Format, Media, RtcpFb, Rtpmap};
+use crate::media_id::MidIndex;
+use crate::origin::Origin;
+use crate::rtcp::Rtcp;
+use crate::sdp::Sdp;
+use crate::validate::SourceLine;
+use alloc::{format, string::String, vec};
+
+/// What a baseline WebRTC offer is made of
+///
+/// let config = WebrtcOfferConfig::new(session_id, ice, fingerprint);
+/// let offer = Sdp::webrtc_audio_video_offer(&config);
+///
+/// The ICE credentials must be random (RFC 8839 section 5.4: at least 4
+/// characters for the ufrag and 22 for the pwd), and the fingerprint is the
+/// hash of the DTLS certificate of the peer.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct WebrtcOfferConfig<'a> {
+    /// The session id of the o= line, random too
+    pub session_id: u64,
+    pub ice: IceCredentials<'a>,
+    pub fingerprint: Fingerprint<'a>,
+
+    /// Offer H264 as well as VP8
+    pub h264: bool,
+}
+
+impl<'a> WebrtcOfferConfig<'a> {
+    /// An offer of opus, VP8 and H264
+    pub fn new(session_id: u64, ice: IceCredentials<'a>, fingerprint: Fingerprint<'a>) -> Self {
+        Self {
+            session_id,
+            ice,
+            fingerprint,
+            h264: true,
+        }
+    }
+}
+
+// the feedback of the video codecs, as browsers offer it
+const VIDEO_FEEDBACK: [(&str, Option<&str>); 5] = [
+    ("goog-remb", None),
+    ("transport-cc", None),
+    ("ccm", Some("fir")),
+    ("nack", None),
+    ("nack", Some("pli")),
+];
+
+// a video codec: its payload type, its name and its fmtp parameters
+type VideoCodec = (u8, &'static str, Option<&'static str>);
+
+const VP8: VideoCodec = (96, "VP8", None);
+const H264: VideoCodec = (
+    102,
+    "H264",
+    Some("level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f"),
+);
+
+pub(crate) fn webrtc_offer<'a>(config: &WebrtcOfferConfig<'a>) -> Sdp<'a> {
+    let audio = Media {
+        payloads: vec![111],
+        rtpmap: vec![rtpmap(111, "opus", 48000, Some(2))],
+        fmtp: vec![fmtp(111, "minptime=10;useinbandfec=1")],
+        rtc_fb: vec![RtcpFb {
+            payload: Format::Payload(111),
+            r#type: "transport-cc",
+            parameter: None,
+        }],
+        ..media("audio", "0", config)
+    };
+
+    let mut video = media("video", "1", config);
+    add_video_codec(&mut video, VP8);
+
+    if config.h264 {
+        add_video_codec(&mut video, H264);
+    }
+
+    let media = vec![audio, video];
+
+    Sdp {
+        version: 0,
+        origin: Origin {
+            username: "-",
+            session_id: config.session_id,
+            session_version: 2,
+            network_type: "IN",
+            ip_type: "IP4",
+            ip_address: "127.0.0.1",
+        },
+        session_name: "-",
+        groups: vec![Group {
+            semantics: "BUNDLE",
+            mids: vec!["0", "1"],
+        }],
+        ice_options: vec!["trickle"],
+        mid_index: MidIndex::new(&media),
+        media,
+        ..Default::default()
+    }
+}
+
+// a sendrecv section on the discard port, its address to come with the
+// candidates
+fn media<'a>(r#type: &'a str, mid: &'a str, config: &WebrtcOfferConfig<'a>) -> Media<'a> {
+    Media {
+        r#type,
+        port: 9,
+        protocol: "UDP/TLS/RTP/SAVPF",
+        connection: Some(Connection {
+            network_type: "IN",
+            ip_type: "IP4",
+            ip_address: "0.0.0.0",
+            ..Default::default()
+        }),
+        rtcp: Some(Rtcp {
+            port: 9,
+            network_type: Some("IN"),
+            ip_type: Some("IP4"),
+            address: Some("0.0.0.0"),
+        }),
+        ice_ufrag: Some(config.ice.ufrag),
+        ice_pwd: Some(config.ice.pwd),
+        fingerprints: vec![config.fingerprint.clone()],
+        setup: Some("actpass"),
+        mid: Some(mid),
+        direction: Some(Direction::SendRecv),
+        rtcp_mux: true,
+        ..Default::default()
+    }
+}
+
+// a video codec, its feedback and its rtx payload on the next payload type
+fn add_video_codec(media: &mut Media, (payload, codec, config): VideoCodec) {
+    let rtx = payload + 1;
+
+    media.payloads.extend([payload, rtx]);
+    media.rtpmap.push(rtpmap(payload, codec, 90000, None));
+    media.rtpmap.push(rtpmap(rtx, "rtx", 90000, None));
+    media
+        .fmtp
+        .extend(config.map(|config| fmtp(payload, config)));
+    media.fmtp.push(fmtp(rtx, &format!("apt={}", payload)));
+    media
+        .rtc_fb
+        .extend(VIDEO_FEEDBACK.iter().map(|&(r#type, parameter)| RtcpFb {
+            payload: Format::Payload(payload),
+            r#type,
+            parameter,
+        }));
+}
+
+fn rtpmap(payload: u8, codec: &'static str, rate: u32, channels: Option<u8>) -> Rtpmap<'static> {
+    Rtpmap {
+        codec,
+        payload,
+        rate: Some(rate),
+        channels,
+        ..Default::default()
+    }
+}
+
+fn fmtp<'a>(payload: u8, config: &str) -> Fmtp<'a> {
+    Fmtp {
+        payload: Format::Payload(payload),
+        config: String::from(config).into(),
+        line: SourceLine::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::Severity;
+
+    const FINGERPRINT: &str = "sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E";
+
+    #[test]
+    fn it_builds_a_webrtc_offer() {
+        let ice = IceCredentials {
+            ufrag: "F7gI",
+            pwd: "x9cml/YzichV2+XlhiMu8g",
+        };
+        let config = WebrtcOfferConfig::new(20518, ice, Fingerprint::new(FINGERPRINT).unwrap());
+        let offer = Sdp::webrtc_audio_video_offer(&config);
+        let written = offer.to_string();
+
+        assert!(offer.uses_bundle() && offer.uses_rtcp_mux());
+        assert_eq!(offer.codecs(), vec!["opus", "VP8", "rtx", "H264"]);
+        assert_eq!(offer.ice_credentials(1), Some(ice));
+        assert_eq!(
+            offer.media[1].fmtp_for_payload(103).unwrap().config,
+            "apt=102"
+        );
+        assert!(offer
+            .validate()
+            .iter()
+            .all(|diagnostic| diagnostic.severity != Severity::Error));
+        assert_eq!(offer.media_by_mid("1").unwrap().r#type, "video");
+        assert_eq!(Sdp::parse(&written).unwrap().to_string(), written);
+
+        let vp8 = Sdp::webrtc_audio_video_offer(&WebrtcOfferConfig {
+            h264: false,
+            ..config
+        });
+
+        assert_eq!(vp8.media[1].payloads, vec![96, 97]);
+    }
+}
@@ -1,37 +1,283 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::utils::parse_number;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// SDP Time
 ///
 /// t=0 0
-/// Gives the starting and ending time. When they are both set to 0 like our
-/// case it means that the session is not bounded to a specific timing- in
-/// other words it’s permanent and valid at any time.
-#[derive(Debug, Default, Serialize, PartialEq)]
-pub(crate) struct Time {
+/// t=3724401600 3724405200
+///
+/// Gives the starting and ending time, as NTP timestamps: seconds since 1900.
+/// When they are both set to 0 like our case it means that the session is not
+/// bounded to a specific timing- in other words it’s permanent and valid at
+/// any time.  A stop time of 0 alone leaves the session unbounded after it
+/// starts.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Time {
     pub start_time: u64,
     pub stop_time: u64,
-    pub bounded: bool,
 }
 
+// the seconds from the NTP epoch (1900) to the Unix epoch (1970)
+#[cfg(feature = "std")]
+const NTP_TO_UNIX: u64 = 2_208_988_800;
+
 impl<'a> Time {
-    pub(crate) fn new(value: &'a str) -> Result<Self> {
+    pub fn new(value: &'a str) -> Result<Self> {
         let mut split = value.split(' ');
-        let start_time = parse_number::<u64>(split.next(), 1)?;
-        let stop_time = parse_number::<u64>(split.next(), 2)?;
-        let bounded = !(start_time == 0 && stop_time == 0);
+        let start_time = parse_number::<u64>(split.next(), "start time")?;
+        let stop_time = parse_number::<u64>(split.next(), "stop time")?;
 
         Ok(Self {
             start_time,
             stop_time,
-            bounded,
         })
     }
+
+    /// Whether the session is limited to a period of time
+    pub fn is_bounded(&self) -> bool {
+        self.start_time != 0 || self.stop_time != 0
+    }
+
+    /// When the session starts, None if it is permanent
+    #[cfg(feature = "std")]
+    pub fn start(&self) -> Option<SystemTime> {
+        ntp_to_system_time(self.start_time)
+    }
+
+    /// When the session stops, None if it is unbounded
+    #[cfg(feature = "std")]
+    pub fn stop(&self) -> Option<SystemTime> {
+        ntp_to_system_time(self.stop_time)
+    }
+}
+
+// 0 is the unbounded value rather than the NTP epoch
+#[cfg(feature = "std")]
+fn ntp_to_system_time(seconds: u64) -> Option<SystemTime> {
+    match seconds {
+        0 => None,
+        seconds if seconds >= NTP_TO_UNIX => {
+            UNIX_EPOCH.checked_add(Duration::from_secs(seconds - NTP_TO_UNIX))
+        }
+        seconds => UNIX_EPOCH.checked_sub(Duration::from_secs(NTP_TO_UNIX - seconds)),
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.start_time, self.stop_time)
+    }
+}
+
+/// A period of time in seconds, or in the compact notation of a unit
+///
+/// 90000
+/// 25h
+///
+/// The units are d (days), h (hours), m (minutes) and s (seconds), kept to
+/// write the time as it was given (RFC 8866 section 5.10).
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TypedTime {
+    pub value: i64,
+
+    /// d, h, m or s, None for a number of seconds without a unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<char>,
+}
+
+impl TypedTime {
+    pub fn new(value: &str) -> Result<Self> {
+        let (number, unit) = match value.char_indices().last() {
+            Some((index, unit @ ('d' | 'h' | 'm' | 's'))) => (&value[..index], Some(unit)),
+            _ => (value, None),
+        };
+
+        // a sign would be parsed by i64, only z= offsets can be negative
+        if !number.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(Error::InvalidNumber {
+                field: "typed time",
+                value: value.into(),
+            });
+        }
+
+        Ok(Self {
+            value: parse_number::<i64>(Some(number), "typed time")?,
+            unit,
+        })
+    }
+
+    /// The time in seconds
+    pub fn seconds(&self) -> i64 {
+        let unit = match self.unit {
+            Some('d') => 86400,
+            Some('h') => 3600,
+            Some('m') => 60,
+            _ => 1,
+        };
+
+        self.value.saturating_mul(unit)
+    }
+}
+
+impl fmt::Display for TypedTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)?;
+
+        match self.unit {
+            Some(unit) => write!(f, "{}", unit),
+            None => Ok(()),
+        }
+    }
+}
+
+/// SDP Repeat Times
+///
+/// r=604800 3600 0 90000
+/// r=7d 1h 0 25h
+///
+/// A session of the t= line above that repeats, e.g. the weekly broadcasts of
+/// a SAP announcement: every interval, it is active for the duration at each
+/// offset from the start time.  Both lines above are one hour on Mondays at
+/// 10am and Tuesdays at 11am, for a start time on a Monday at 10am.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Repeat {
+    pub interval: TypedTime,
+    pub duration: TypedTime,
+    pub offsets: Vec<TypedTime>,
+}
+
+impl Repeat {
+    pub fn new(value: &str) -> Result<Self> {
+        let mut split = value.split(' ').filter(|time| !time.is_empty());
+        let mut typed_time = |field| match split.next() {
+            Some(time) => TypedTime::new(time),
+            None => Err(Error::MissingField { field }),
+        };
+        let interval = typed_time("repeat interval")?;
+        let duration = typed_time("active duration")?;
+        let offsets = core::iter::once(typed_time("repeat offset"))
+            .chain(split.map(TypedTime::new))
+            .collect::<Result<_>>()?;
+
+        if interval.value == 0 {
+            return Err(Error::invalid_value("repeat interval", "is 0"));
+        }
+
+        Ok(Self {
+            interval,
+            duration,
+            offsets,
+        })
+    }
+}
+
+impl fmt::Display for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.interval, self.duration)?;
+
+        for offset in &self.offsets {
+            write!(f, " {}", offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// SDP Time Description
+///
+/// t=3034423619 3042462419
+/// r=7d 1h 0 25h
+///
+/// A t= line and the r= lines after it.  A session active in several periods,
+/// e.g. two broadcasts a week apart, has a t= line for each one.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TimeDescription {
+    pub time: Time,
+
+    /// The r lines of the t line, when the session repeats
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repeats: Vec<Repeat>,
+}
+
+impl TimeDescription {
+    pub fn new(time: Time) -> Self {
+        Self {
+            time,
+            repeats: Vec::new(),
+        }
+    }
+}
+
+/// A time zone adjustment of a z= line
+///
+/// z=2882844526 -1h 2898848070 0
+///
+/// From the NTP time of each adjustment, e.g. the end of daylight saving time,
+/// the times of the repeated sessions are shifted by its offset from the
+/// original time zone of the t= line.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ZoneAdjustment {
+    pub time: u64,
+    pub offset: TypedTime,
+}
+
+// the adjustment time and offset pairs of a z line
+pub(crate) fn parse_zone_adjustments(value: &str) -> Result<Vec<ZoneAdjustment>> {
+    let mut split = value.split(' ').filter(|time| !time.is_empty());
+    let mut adjustments = Vec::new();
+
+    while let Some(time) = split.next() {
+        let offset = split.next().ok_or(Error::MissingField {
+            field: "zone offset",
+        })?;
+        let offset = match offset.strip_prefix('-') {
+            Some(offset) => TypedTime::new(offset).map(|offset| TypedTime {
+                value: -offset.value,
+                ..offset
+            })?,
+            None => TypedTime::new(offset)?,
+        };
+
+        adjustments.push(ZoneAdjustment {
+            time: parse_number::<u64>(Some(time), "adjustment time")?,
+            offset,
+        });
+    }
+
+    match adjustments.is_empty() {
+        true => Err(Error::MissingField {
+            field: "adjustment time",
+        }),
+        false => Ok(adjustments),
+    }
+}
+
+// the pairs of a z line
+pub(crate) struct ZoneAdjustments<'a>(pub &'a [ZoneAdjustment]);
+
+impl<'a> fmt::Display for ZoneAdjustments<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, adjustment) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+
+            write!(f, "{} {}", adjustment.time, adjustment.offset)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::ParseOptions;
+    use crate::sdp::Sdp;
 
     #[test]
     fn it_parses_time() {
@@ -40,9 +286,124 @@ mod tests {
         let expected = Time {
             start_time: 0,
             stop_time: 0,
-            bounded: false,
         };
 
         assert_eq!(parsed, expected);
+        assert!(!parsed.is_bounded());
+    }
+
+    #[test]
+    fn it_parses_repeat_times() {
+        let parsed = Repeat::new("7d 1h 0 25h").unwrap();
+        let seconds: Vec<_> = parsed.offsets.iter().map(TypedTime::seconds).collect();
+
+        assert_eq!(parsed.interval.seconds(), 604800);
+        assert_eq!(parsed.duration.seconds(), 3600);
+        assert_eq!(seconds, vec![0, 90000]);
+        assert_eq!(parsed.to_string(), "7d 1h 0 25h");
+        assert_eq!(Repeat::new("604800 3600 0").unwrap().interval.unit, None);
+        assert!(Repeat::new("7d 1h").is_err());
+        assert!(Repeat::new("0 1h 0").is_err());
+        assert!(Repeat::new("7w 1h 0").is_err());
+        assert!(Repeat::new("-7d 1h 0").is_err());
+    }
+
+    #[test]
+    fn it_parses_zone_adjustments() {
+        let parsed = parse_zone_adjustments("2882844526 -1h 2898848070 0").unwrap();
+
+        assert_eq!(parsed[0].time, 2882844526);
+        assert_eq!(parsed[0].offset.seconds(), -3600);
+        assert_eq!(parsed[1].offset.seconds(), 0);
+        assert_eq!(
+            ZoneAdjustments(&parsed).to_string(),
+            "2882844526 -1h 2898848070 0"
+        );
+        assert!(parse_zone_adjustments("2882844526").is_err());
+        assert!(parse_zone_adjustments("").is_err());
+    }
+
+    #[test]
+    fn it_parses_a_scheduled_session() {
+        let sdp = "v=0\r
+o=- 2890844526 2890842807 IN IP4 198.51.100.1\r
+s=Weekly seminar\r
+c=IN IP4 233.252.0.1/127\r
+t=2873397496 2873404696\r
+r=7d 1h 0 25h\r
+z=2882844526 -1h 2898848070 0\r
+m=audio 49170 RTP/AVP 0\r
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let mut options = ParseOptions::default();
+        options.enforce_ordering = true;
+
+        assert_eq!(parsed.times[0].repeats[0].offsets[1].seconds(), 90000);
+        assert_eq!(parsed.zone_adjustments[0].offset.seconds(), -3600);
+        assert_eq!(parsed.to_string(), sdp);
+        assert!(Sdp::parse_with(sdp, &options).is_ok());
+        assert!(Sdp::parse(&sdp.replace("r=7d 1h 0 25h", "r=7d 1h")).is_err());
+    }
+
+    #[test]
+    fn it_parses_several_time_descriptions() {
+        let sdp = "v=0\r
+o=- 2890844526 2890842807 IN IP4 198.51.100.1\r
+s=Two seminars\r
+t=2873397496 2873404696\r
+r=7d 1h 0 25h\r
+r=30d 2h 0\r
+t=2874002296 2874009496\r
+m=audio 49170 RTP/AVP 0\r
+";
+        let parsed = Sdp::parse(sdp).unwrap();
+        let mut options = ParseOptions::default();
+        options.enforce_ordering = true;
+
+        assert_eq!(parsed.times.len(), 2);
+        assert_eq!(parsed.times[0].repeats.len(), 2);
+        assert_eq!(parsed.times[1].time.start_time, 2874002296);
+        assert!(parsed.times[1].repeats.is_empty());
+        assert_eq!(parsed.to_string(), sdp);
+        assert!(Sdp::parse_with(sdp, &options).is_ok());
+    }
+
+    #[test]
+    fn it_requires_a_t_line_before_an_r_line() {
+        let sdp = "v=0
+o=- 2890844526 2890842807 IN IP4 198.51.100.1
+s=
+r=7d 1h 0 25h
+t=2873397496 2873404696
+m=audio 49170 RTP/AVP 0";
+        let error = Sdp::parse(sdp).unwrap_err();
+
+        assert_eq!(error.line(), Some(4));
+        assert!(matches!(error.kind(), Error::InvalidLine { .. }));
+
+        let media = "v=0
+o=- 2890844526 2890842807 IN IP4 198.51.100.1
+s=
+t=2873397496 2873404696
+m=audio 49170 RTP/AVP 0
+r=7d 1h 0 25h";
+
+        assert_eq!(Sdp::parse(media).unwrap_err().line(), Some(6));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn it_converts_ntp_timestamps() {
+        // 2018-01-08 12:00:00 UTC
+        let parsed = Time::new("3724401600 0").unwrap();
+        let start = parsed.start().unwrap();
+
+        assert!(parsed.is_bounded());
+        assert_eq!(
+            start.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            1_515_412_800
+        );
+        assert_eq!(parsed.stop(), None);
+        assert_eq!(Time::default().start(), None);
     }
 }
@@ -1,26 +1,84 @@
 use crate::error::{Error, Result};
-use std::{fmt::Debug, str::FromStr};
+use core::fmt::{self, Display};
+use core::str::FromStr;
 
 /// Parse a numeric value from an option and handle the error
-pub(crate) fn parse_number<T>(value: Option<&str>, index: usize) -> Result<T>
+pub(crate) fn parse_number<T>(value: Option<&str>, field: &'static str) -> Result<T>
 where
     T: FromStr,
-    T::Err: Debug,
 {
-    let item = parse_str(value, index)?;
-    let result = item.parse::<T>().map_err(|e| {
-        Error::Parse(format!(
-            "Error parsing '{}' in '{:?}': {:?}",
-            item, value, e
-        ))
+    let item = parse_str(value, field)?;
+    let result = item.parse::<T>().map_err(|_| Error::InvalidNumber {
+        field,
+        value: item.into(),
     })?;
 
     Ok(result)
 }
 
+/// Parse a number written loosely, with surrounding whitespace or a leading
+/// '+', saturated at u64::MAX if it overflows, and whether it did
+pub(crate) fn parse_saturating(value: Option<&str>, field: &'static str) -> Result<(u64, bool)> {
+    let item = parse_str(value, field)?;
+    let digits = item.trim();
+    let digits = digits.strip_prefix('+').unwrap_or(digits);
+
+    match digits.parse::<u64>() {
+        Ok(number) => Ok((number, false)),
+        Err(_) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok((u64::MAX, true))
+        }
+        Err(_) => Err(Error::InvalidNumber {
+            field,
+            value: item.into(),
+        }),
+    }
+}
+
 /// Parse a &str from an option and handle the error
-pub(crate) fn parse_str(value: Option<&str>, index: usize) -> Result<&str> {
-    let item = value.ok_or_else(|| Error::Parse(format!("No item found at position {}", index)))?;
+pub(crate) fn parse_str<'a>(value: Option<&'a str>, field: &'static str) -> Result<&'a str> {
+    let item = value.ok_or(Error::MissingField { field })?;
 
     Ok(item)
 }
+
+/// The lines of a message without their "\n" or "\r\n" endings, like
+/// str::lines, with the newlines found by memchr
+pub(crate) fn lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text);
+
+    core::iter::from_fn(move || {
+        let text = rest?;
+
+        match memchr::memchr(b'\n', text.as_bytes()) {
+            Some(end) => {
+                rest = Some(&text[end + 1..]);
+                let line = &text[..end];
+                Some(line.strip_suffix('\r').unwrap_or(line))
+            }
+            None => {
+                rest = None;
+                Some(text).filter(|text| !text.is_empty())
+            }
+        }
+    })
+}
+
+/// Write a single SDP line, e.g. c=IN IP4 203.0.113.1
+pub(crate) fn write_line(f: &mut fmt::Formatter, key: char, value: impl Display) -> fmt::Result {
+    write!(f, "{}={}\r\n", key, value)
+}
+
+/// Write an attribute line, e.g. a=ice-ufrag:F7gI
+pub(crate) fn write_attribute(
+    f: &mut fmt::Formatter,
+    attribute: &str,
+    value: impl Display,
+) -> fmt::Result {
+    write!(f, "a={}:{}\r\n", attribute, value)
+}
+
+/// Write a value-less attribute line, e.g. a=sendrecv
+pub(crate) fn write_flag(f: &mut fmt::Formatter, attribute: &str) -> fmt::Result {
+    write!(f, "a={}\r\n", attribute)
+}
@@ -0,0 +1,358 @@
+//! Semantic validation of a parsed SDP.
+//!
+//! Parsing only checks that each line is well formed, validation checks the
+//! constraints between lines.
+
+use crate::group::GroupSemantics;
+use crate::media::{Format, Media};
+use crate::sdp::Sdp;
+use alloc::{format, string::String, vec, vec::Vec};
+use core::hash::{Hash, Hasher};
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found while validating a SDP
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// The line the problem belongs to, when the SDP was parsed from text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    pub message: String,
+}
+
+/// The line number an item was parsed from
+///
+/// Source positions don't take part in equality, so a parsed SDP is still
+/// equal to the same SDP built by hand or restored from JSON.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SourceLine(pub(crate) Option<usize>);
+
+impl SourceLine {
+    pub fn get(&self) -> Option<usize> {
+        self.0
+    }
+}
+
+impl PartialEq for SourceLine {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SourceLine {}
+
+impl Hash for SourceLine {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+const ICE_UFRAG_LENGTH: (usize, usize) = (4, 256);
+const ICE_PWD_LENGTH: (usize, usize) = (22, 256);
+
+pub(crate) fn validate(sdp: &Sdp) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    check_ice(&mut diagnostics, None, sdp.ice_ufrag, sdp.ice_pwd);
+    check_groups(&mut diagnostics, sdp);
+
+    for media in &sdp.media {
+        let line = media.line.get();
+
+        check_ice(&mut diagnostics, line, media.ice_ufrag, media.ice_pwd);
+        check_ports(&mut diagnostics, media);
+        check_ssrc_groups(&mut diagnostics, media);
+
+        // a rejected m line is only kept for the index of the sections after it
+        if !media.is_rejected() {
+            check_fingerprint(&mut diagnostics, sdp, media);
+            check_payloads(&mut diagnostics, media);
+            check_bundle_only(&mut diagnostics, sdp, media);
+        }
+    }
+
+    diagnostics
+}
+
+fn error(line: Option<usize>, message: String) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Error,
+        line,
+        message,
+    }
+}
+
+fn warning(line: Option<usize>, message: String) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Warning,
+        line,
+        message,
+    }
+}
+
+// RFC 8839 length limits on the ICE credentials
+fn check_ice(
+    diagnostics: &mut Vec<Diagnostic>,
+    line: Option<usize>,
+    ice_ufrag: Option<&str>,
+    ice_pwd: Option<&str>,
+) {
+    let credentials = [
+        ("ice-ufrag", ice_ufrag, ICE_UFRAG_LENGTH),
+        ("ice-pwd", ice_pwd, ICE_PWD_LENGTH),
+    ];
+
+    for (attribute, value, (min, max)) in credentials.iter() {
+        if let Some(value) = value {
+            let length = value.chars().count();
+
+            if length < *min || length > *max {
+                diagnostics.push(error(
+                    line,
+                    format!(
+                        "{} must be between {} and {} characters, found {}",
+                        attribute, min, max, length
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+// DTLS based protocols can't be secured without a fingerprint
+fn check_fingerprint(diagnostics: &mut Vec<Diagnostic>, sdp: &Sdp, media: &Media) {
+    if media.transport_protocol().is_dtls() && media.effective_fingerprints(sdp).is_empty() {
+        diagnostics.push(error(
+            media.line.get(),
+            format!("{} requires a fingerprint", media.protocol),
+        ));
+    }
+}
+
+// dynamic payload types need a rtpmap, only one, and codec lines should refer
+// to the m line
+fn check_payloads(diagnostics: &mut Vec<Diagnostic>, media: &Media) {
+    let line = media.line.get();
+
+    for &payload in &media.payloads {
+        if payload >= 96 && media.codec_for_payload(payload).is_none() {
+            diagnostics.push(error(
+                line,
+                format!("Dynamic payload type {} has no rtpmap", payload),
+            ));
+        }
+    }
+
+    for (index, rtpmap) in media.rtpmap.iter().enumerate() {
+        let earlier = media.rtpmap[..index]
+            .iter()
+            .filter(|earlier| earlier.payload == rtpmap.payload)
+            .count();
+
+        // once per payload type, at its second rtpmap
+        if earlier == 1 {
+            diagnostics.push(error(
+                rtpmap.line.get(),
+                format!("Payload type {} has several rtpmap lines", rtpmap.payload),
+            ));
+        }
+
+        if !media.payloads.contains(&rtpmap.payload) {
+            diagnostics.push(warning(
+                rtpmap.line.get(),
+                format!(
+                    "rtpmap for payload type {} not on the m line",
+                    rtpmap.payload
+                ),
+            ));
+        }
+    }
+
+    for fmtp in &media.fmtp {
+        let listed = match fmtp.payload {
+            Format::Payload(payload) => media.payloads.contains(&payload),
+            Format::Token(token) => media.formats.contains(&token),
+        };
+
+        if !listed {
+            diagnostics.push(warning(
+                fmtp.line.get(),
+                format!("fmtp for payload type {} not on the m line", fmtp.payload),
+            ));
+        }
+    }
+}
+
+// the ports of the m line, a pair per stream for RTP (RFC 8866 section 5.14),
+// must all be below 65536
+fn check_ports(diagnostics: &mut Vec<Diagnostic>, media: &Media) {
+    let count = match media.port_count {
+        Some(count) => u32::from(count),
+        None => return,
+    };
+    let per_stream = if media.transport_protocol().is_rtp() {
+        2
+    } else {
+        1
+    };
+    let last = u32::from(media.port) + (count * per_stream).saturating_sub(1);
+
+    if last > u32::from(u16::MAX) {
+        diagnostics.push(error(
+            media.line.get(),
+            format!("Ports {}/{} go past port {}", media.port, count, u16::MAX),
+        ));
+    }
+}
+
+// every ssrc of a ssrc group must have its own ssrc lines
+fn check_ssrc_groups(diagnostics: &mut Vec<Diagnostic>, media: &Media) {
+    for group in &media.ssrc_groups {
+        for ssrc in &group.ssrcs {
+            if !media.ssrc.iter().any(|line| line.id == *ssrc) {
+                diagnostics.push(error(
+                    group.line.get(),
+                    format!(
+                        "{} ssrc group refers to unknown ssrc {}",
+                        group.semantics, ssrc
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+// every mid of a group must name a media section
+fn check_groups(diagnostics: &mut Vec<Diagnostic>, sdp: &Sdp) {
+    for group in &sdp.groups {
+        for mid in &group.mids {
+            if !sdp.media.iter().any(|media| media.mid == Some(mid)) {
+                diagnostics.push(error(
+                    None,
+                    format!("{} group refers to unknown mid '{}'", group.semantics, mid),
+                ));
+            }
+        }
+    }
+}
+
+// a bundle-only section has no transport outside of its BUNDLE group
+fn check_bundle_only(diagnostics: &mut Vec<Diagnostic>, sdp: &Sdp, media: &Media) {
+    let bundled = sdp.groups.iter().any(|group| {
+        group.semantics_type() == GroupSemantics::Bundle
+            && matches!(media.mid, Some(mid) if group.mids.contains(&mid))
+    });
+
+    if media.bundle_only && !bundled {
+        diagnostics.push(error(
+            media.line.get(),
+            "bundle-only media section outside of a BUNDLE group".into(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::Rtpmap;
+
+    #[test]
+    fn it_validates_a_sdp() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE 0 1 2
+a=ice-ufrag:F7g
+a=ice-pwd:x9cml/YzichV2+XlhiMu8g
+m=audio 54400 UDP/TLS/RTP/SAVPF 0 96
+a=mid:0
+a=rtpmap:0 PCMU/8000
+a=fmtp:97 minptime=10
+m=video 55400 RTP/SAVPF 97
+a=mid:1
+a=rtpmap:97 VP8/90000
+a=rtpmap:97 H264/90000
+a=rtpmap:97 AV1/90000
+a=ssrc-group:FID 1 2
+a=ssrc:1 cname:foo";
+        let mut parsed = Sdp::parse(sdp).unwrap();
+
+        // parsing keeps the last rtpmap of a payload type
+        let vp8 = Rtpmap::new("97 VP8/90000").unwrap();
+        parsed.media[1].rtpmap.insert(0, vp8);
+        let expected = vec![
+            error(
+                None,
+                "ice-ufrag must be between 4 and 256 characters, found 3".into(),
+            ),
+            error(None, "BUNDLE group refers to unknown mid '2'".into()),
+            error(Some(8), "UDP/TLS/RTP/SAVPF requires a fingerprint".into()),
+            error(Some(8), "Dynamic payload type 96 has no rtpmap".into()),
+            warning(
+                Some(11),
+                "fmtp for payload type 97 not on the m line".into(),
+            ),
+            error(Some(17), "FID ssrc group refers to unknown ssrc 2".into()),
+            error(Some(16), "Payload type 97 has several rtpmap lines".into()),
+        ];
+
+        assert_eq!(parsed.validate(), expected);
+    }
+
+    #[test]
+    fn it_validates_port_ranges() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+m=audio 65535/4 RTP/AVP 0
+m=audio 65528/4 RTP/AVP 0
+m=image 65534/2 udptl t38
+m=audio 65533/2 RTP/AVP 0";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert_eq!(
+            validate(&parsed),
+            vec![
+                error(Some(5), "Ports 65535/4 go past port 65535".into()),
+                error(Some(8), "Ports 65533/2 go past port 65535".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_validates_bundle_only_sections() {
+        let sdp = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=group:BUNDLE 0 1
+m=audio 9 RTP/AVP 0
+a=mid:0
+m=video 0 RTP/AVP 31
+a=mid:1
+a=bundle-only
+m=video 0 RTP/AVP 31
+a=mid:2
+a=bundle-only";
+        let parsed = Sdp::parse(sdp).unwrap();
+
+        assert!(!parsed.media[1].is_rejected());
+        assert_eq!(
+            validate(&parsed),
+            vec![error(
+                Some(11),
+                "bundle-only media section outside of a BUNDLE group".into()
+            )]
+        );
+        assert_eq!(parsed.to_string(), sdp.replace('\n', "\r\n") + "\r\n");
+    }
+}
@@ -0,0 +1,145 @@
+//! Walking every part of a SDP, e.g. to collect statistics or check values
+//! without a loop over the media sections and another over their lines.
+
+use crate::extension::Extension;
+use crate::fingerprint::Fingerprint;
+use crate::media::{Candidate, Fmtp, Media, RtcpFb, Rtpmap, Ssrc};
+use crate::sdp::Sdp;
+
+/// What to do with each part of a SDP, see `Sdp::visit`
+///
+/// struct CountCandidates(usize);
+///
+/// impl<'a> Visitor<'a> for CountCandidates {
+///     fn visit_candidate(&mut self, _media: usize, _candidate: &Candidate<'a>) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// Every method does nothing unless implemented.  The parts of a media section
+/// are given the index of the section, and those that can be given at the
+/// session level as well, None for the session.
+pub trait Visitor<'a> {
+    fn visit_session(&mut self, _sdp: &Sdp<'a>) {}
+    fn visit_media(&mut self, _media: usize, _section: &Media<'a>) {}
+    fn visit_fingerprint(&mut self, _media: Option<usize>, _fingerprint: &Fingerprint<'a>) {}
+    fn visit_extension(&mut self, _media: Option<usize>, _extension: &Extension<'a>) {}
+    fn visit_rtpmap(&mut self, _media: usize, _rtpmap: &Rtpmap<'a>) {}
+    fn visit_fmtp(&mut self, _media: usize, _fmtp: &Fmtp<'a>) {}
+    fn visit_rtcp_fb(&mut self, _media: usize, _rtcp_fb: &RtcpFb<'a>) {}
+    fn visit_ssrc(&mut self, _media: usize, _ssrc: &Ssrc<'a>) {}
+    fn visit_candidate(&mut self, _media: usize, _candidate: &Candidate<'a>) {}
+}
+
+// the session, then each media section followed by its lines
+pub(crate) fn walk<'a>(sdp: &Sdp<'a>, visitor: &mut impl Visitor<'a>) {
+    visitor.visit_session(sdp);
+    sdp.fingerprints
+        .iter()
+        .for_each(|fingerprint| visitor.visit_fingerprint(None, fingerprint));
+    sdp.extensions
+        .iter()
+        .for_each(|extension| visitor.visit_extension(None, extension));
+
+    for (index, media) in sdp.media.iter().enumerate() {
+        visitor.visit_media(index, media);
+        media
+            .fingerprints
+            .iter()
+            .for_each(|fingerprint| visitor.visit_fingerprint(Some(index), fingerprint));
+        media
+            .rtpmap
+            .iter()
+            .for_each(|rtpmap| visitor.visit_rtpmap(index, rtpmap));
+        media
+            .fmtp
+            .iter()
+            .for_each(|fmtp| visitor.visit_fmtp(index, fmtp));
+        media
+            .rtc_fb
+            .iter()
+            .for_each(|rtcp_fb| visitor.visit_rtcp_fb(index, rtcp_fb));
+        media
+            .ssrc
+            .iter()
+            .for_each(|ssrc| visitor.visit_ssrc(index, ssrc));
+        media
+            .candidates
+            .iter()
+            .for_each(|candidate| visitor.visit_candidate(index, candidate));
+        media
+            .extensions
+            .iter()
+            .for_each(|extension| visitor.visit_extension(Some(index), extension));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=fingerprint:sha-256 49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97:D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E
+m=audio 54400 RTP/SAVPF 0 96
+a=rtpmap:0 PCMU/8000
+a=rtpmap:96 opus/48000
+a=ssrc:1399694169 cname:foo
+a=candidate:1 1 UDP 2113667327 203.0.113.1 54400 typ host
+m=video 55400 RTP/SAVPF 97
+a=rtpmap:97 H264/90000
+a=fingerprint:sha-256 D2:7D:62:C9:9A:7F:B9:A3:F4:70:03:E7:43:91:73:23:5E:49:66:12:17:0D:1C:91:AE:57:4C:C6:36:DD:D5:97
+a=candidate:1 1 UDP 2113667327 203.0.113.1 55400 typ host
+a=candidate:2 1 UDP 1694498815 198.51.100.7 55400 typ srflx";
+
+    #[derive(Default)]
+    struct Walked {
+        media: Vec<usize>,
+        fingerprints: Vec<Option<usize>>,
+        candidates: usize,
+    }
+
+    impl<'a> Visitor<'a> for Walked {
+        fn visit_media(&mut self, media: usize, _section: &Media<'a>) {
+            self.media.push(media);
+        }
+
+        fn visit_fingerprint(&mut self, media: Option<usize>, _fingerprint: &Fingerprint<'a>) {
+            self.fingerprints.push(media);
+        }
+
+        fn visit_candidate(&mut self, _media: usize, _candidate: &Candidate<'a>) {
+            self.candidates += 1;
+        }
+    }
+
+    #[test]
+    fn it_iterates_across_media_sections() {
+        let parsed = Sdp::parse(SDP).unwrap();
+        let candidates: Vec<_> = parsed
+            .candidates()
+            .map(|(media, candidate)| (media, candidate.port))
+            .collect();
+        let codecs: Vec<_> = parsed
+            .rtpmaps()
+            .map(|(media, rtpmap)| (media, rtpmap.codec))
+            .collect();
+
+        assert_eq!(candidates, vec![(0, 54400), (1, 55400), (1, 55400)]);
+        assert_eq!(codecs, vec![(0, "PCMU"), (0, "opus"), (1, "H264")]);
+        assert_eq!(parsed.ssrcs().next().unwrap().1.id, 1399694169);
+    }
+
+    #[test]
+    fn it_visits_a_sdp() {
+        let mut walked = Walked::default();
+        Sdp::parse(SDP).unwrap().visit(&mut walked);
+
+        assert_eq!(walked.media, vec![0, 1]);
+        assert_eq!(walked.fingerprints, vec![None, Some(1)]);
+        assert_eq!(walked.candidates, 3);
+    }
+}
@@ -0,0 +1,123 @@
+//! Issues a lenient parse recovers from.
+//!
+//! With `ParseOptions::lenient` set, a line the parser could do without is
+//! reported rather than failing the whole message, so QA tools can flag
+//! suspicious offers and still look at the rest of them.
+
+use alloc::string::String;
+
+/// The kind of issue a lenient parse recovered from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum WarningKind {
+    /// An attribute the parser doesn't support, whose line was skipped
+    UnknownAttribute,
+
+    /// A number above the range of its attribute, replaced by the largest
+    /// value, e.g. a=quality:12 read as 10, or a session id overflowing u64
+    Clamped,
+
+    /// A line off its grammar that could still be read, e.g. an o= line with
+    /// its fields separated by several spaces
+    Malformed,
+
+    /// A line repeating an earlier line of the same section
+    Duplicate,
+}
+
+/// An issue a lenient parse recovered from, and the line it was found on
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Warning {
+    pub kind: WarningKind,
+    pub line: usize,
+    pub message: String,
+}
+
+// the attributes with a bounded number, with their largest value
+const BOUNDED: [(&str, u64, &str); 1] = [("quality", 10, "10")];
+
+// the largest value of a bounded attribute, for a number above it
+pub(crate) fn clamp(name: &str, value: &str) -> Option<&'static str> {
+    let number = value.parse::<u64>().ok()?;
+
+    BOUNDED
+        .iter()
+        .find(|(bounded, max, _)| *bounded == name && number > *max)
+        .map(|(_, _, max)| *max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseOptions;
+    use crate::sdp::{self, Sdp};
+
+    const SDP: &str = "v=0
+o=- 20518 0 IN IP4 203.0.113.1
+s=
+t=0 0
+a=x-unknown:1
+m=video 9 RTP/AVP 96
+a=rtpmap:96 VP8/90000
+a=quality:12
+a=rtpmap:96 VP8/90000";
+
+    #[test]
+    fn it_reports_warnings() {
+        let (parsed, warnings) = sdp::parse_with_warnings(SDP);
+        let parsed = parsed.unwrap();
+        let kinds: Vec<_> = warnings.iter().map(|w| (w.kind, w.line)).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (WarningKind::UnknownAttribute, 5),
+                (WarningKind::Clamped, 8),
+                (WarningKind::Duplicate, 9),
+            ]
+        );
+        assert_eq!(parsed.media[0].quality, Some(10));
+        assert!(parsed.extensions.is_empty());
+        assert_eq!(
+            warnings[1].message,
+            "quality 12 is out of range, read as 10"
+        );
+        assert!(Sdp::parse(SDP).is_err());
+    }
+
+    #[test]
+    fn it_still_fails_on_unrecoverable_lines() {
+        let invalid = SDP.replace("a=quality:12", "a=quality:high");
+        let (parsed, warnings) = sdp::parse_with_warnings(&invalid);
+
+        assert_eq!(parsed.unwrap_err().line(), Some(8));
+        assert_eq!(warnings.len(), 1);
+
+        let mut options = ParseOptions::default();
+        options.lenient = true;
+
+        assert_eq!(
+            Sdp::parse_with(SDP, &options).unwrap().media[0].quality,
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn it_reads_the_origin_of_a_broken_gateway() {
+        let broken = SDP.replace("20518 0", "+20518  99999999999999999999");
+        let (parsed, warnings) = sdp::parse_with_warnings(&broken);
+
+        assert_eq!(parsed.unwrap().origin.session_version, u64::MAX);
+        assert_eq!(
+            (warnings[0].kind, warnings[0].line),
+            (WarningKind::Clamped, 2)
+        );
+        assert!(Sdp::parse(&broken).is_err());
+
+        let spaced = SDP.replace("20518 0", "20518  0");
+        let (parsed, warnings) = sdp::parse_with_warnings(&spaced);
+
+        assert_eq!(parsed.unwrap().origin.session_id, 20518);
+        assert_eq!(warnings[0].kind, WarningKind::Malformed);
+    }
+}
@@ -0,0 +1,35 @@
+//! JavaScript bindings, enabled with the `wasm` feature
+//!
+//! The parsed model borrows from the SDP text, so it can't cross the boundary
+//! as is.  Each export converts its result into an owned JavaScript value
+//! before the text is dropped.
+
+use crate::sdp::Sdp;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Parse a SDP message into a plain JavaScript object
+#[wasm_bindgen(js_name = parseSdp)]
+pub fn parse_sdp(text: &str) -> Result<JsValue, JsValue> {
+    let sdp = Sdp::parse(text).map_err(|error| to_js(&error.diagnostic()))?;
+
+    Ok(to_js(&sdp))
+}
+
+/// Parse and validate a SDP message, returning an array of diagnostics
+#[wasm_bindgen(js_name = validateSdp)]
+pub fn validate_sdp(text: &str) -> Result<JsValue, JsValue> {
+    let sdp = Sdp::parse(text).map_err(|error| to_js(&error.diagnostic()))?;
+
+    Ok(to_js(&sdp.validate()))
+}
+
+// optional fields are skipped rather than set to undefined, and maps become
+// plain objects
+fn to_js<T: Serialize + ?Sized>(value: &T) -> JsValue {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+
+    value
+        .serialize(&serializer)
+        .unwrap_or_else(|error| JsValue::from_str(&error.to_string()))
+}